@@ -0,0 +1,356 @@
+use chrono::{DateTime, Utc};
+use log::{debug, warn};
+use ring::digest::{digest, SHA256};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// How the client uses the response cache for a run.
+///
+/// # Variants
+/// * `Normal` - Serve fresh entries from the cache and store new responses
+/// * `Refresh` - Always hit the network, but store the new responses
+/// * `Disabled` - Neither read nor write the cache
+/// * `Offline` - Serve cached entries regardless of age and never touch the network
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CacheMode {
+    #[default]
+    Normal,
+    Refresh,
+    Disabled,
+    Offline,
+}
+
+/// A response body read from the cache, with how long ago it was fetched.
+#[derive(Debug, Clone)]
+pub struct CachedResponse {
+    pub body: Vec<u8>,
+    pub age: Duration,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct CacheEntry {
+    fetched_at: DateTime<Utc>,
+    body: Value,
+}
+
+/// Default time-to-live for cached executor listings.
+pub const DEFAULT_EXECUTORS_TTL: Duration = Duration::from_secs(60);
+/// Default time-to-live for cached template listings.
+pub const DEFAULT_TEMPLATES_TTL: Duration = Duration::from_secs(10 * 60);
+/// Default time-to-live for cached pod listings.
+pub const DEFAULT_PODS_TTL: Duration = Duration::from_secs(15);
+
+/// On-disk cache for GET responses, with a TTL per endpoint.
+///
+/// Only endpoints that have a TTL are cached (by default `executors`, `templates` and
+/// `pods`). Each entry is a JSON file holding the raw response body and the time it was
+/// fetched, so cached data goes through exactly the same parsing as live data. Entries are
+/// kept in a subdirectory per API account (see [`ResponseCache::scoped`]) so switching
+/// API keys never serves another account's pods.
+///
+/// # Examples
+/// ```rust
+/// use lium_api::{CacheMode, LiumApiClient, ResponseCache};
+/// use std::time::Duration;
+/// # let home = std::env::temp_dir();
+/// # let key = "your-api-key".to_string();
+///
+/// let cache = ResponseCache::new(home.join(".lium/cache"))
+///     .with_ttl("pods", Duration::from_secs(5))
+///     .with_mode(CacheMode::Refresh);
+/// let client = LiumApiClient::from_api_key(key).with_cache(cache);
+/// ```
+#[derive(Debug, Clone)]
+pub struct ResponseCache {
+    dir: PathBuf,
+    ttls: HashMap<String, Duration>,
+    mode: CacheMode,
+}
+
+impl ResponseCache {
+    /// Creates a cache rooted at `dir` with the default TTLs.
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        let ttls = HashMap::from([
+            ("executors".to_string(), DEFAULT_EXECUTORS_TTL),
+            ("templates".to_string(), DEFAULT_TEMPLATES_TTL),
+            ("pods".to_string(), DEFAULT_PODS_TTL),
+        ]);
+
+        Self {
+            dir: dir.into(),
+            ttls,
+            mode: CacheMode::Normal,
+        }
+    }
+
+    /// Sets the TTL for an endpoint, making it cacheable.
+    pub fn with_ttl(mut self, endpoint: &str, ttl: Duration) -> Self {
+        self.ttls.insert(normalize(endpoint), ttl);
+        self
+    }
+
+    /// Sets how the cache is used for this run.
+    pub fn with_mode(mut self, mode: CacheMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Returns a cache that stores entries in a subdirectory unique to `account`.
+    ///
+    /// The account string (typically base URL and API key) is hashed with SHA-256, so the
+    /// key never appears on disk and every build of lium picks the same directory.
+    pub fn scoped(mut self, account: &str) -> Self {
+        let hash = digest(&SHA256, account.as_bytes());
+        let name: String = hash.as_ref()[..8]
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect();
+        self.dir = self.dir.join(name);
+        self
+    }
+
+    /// Returns the directory entries are stored in.
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Returns the cache mode.
+    pub fn mode(&self) -> CacheMode {
+        self.mode
+    }
+
+    /// Returns true if responses for `endpoint` are cached.
+    pub fn is_cacheable(&self, endpoint: &str) -> bool {
+        self.mode != CacheMode::Disabled && self.ttls.contains_key(&normalize(endpoint))
+    }
+
+    /// Looks up a usable entry for `endpoint` according to the cache mode.
+    ///
+    /// # Returns
+    /// * `Some(CachedResponse)` - A fresh entry, or any entry when offline
+    /// * `None` - No entry, an expired entry, or the mode skips reading the cache
+    pub fn lookup(&self, endpoint: &str) -> Option<CachedResponse> {
+        let endpoint = normalize(endpoint);
+        let ttl = *self.ttls.get(&endpoint)?;
+
+        match self.mode {
+            CacheMode::Refresh | CacheMode::Disabled => return None,
+            CacheMode::Normal | CacheMode::Offline => {}
+        }
+
+        let cached = self.read(&endpoint)?;
+        if self.mode == CacheMode::Offline || cached.age <= ttl {
+            debug!("Cache hit for {} (age {:?})", endpoint, cached.age);
+            Some(cached)
+        } else {
+            debug!(
+                "Cache entry for {} expired (age {:?})",
+                endpoint, cached.age
+            );
+            None
+        }
+    }
+
+    /// Returns how long ago `endpoint` was cached, regardless of TTL or mode.
+    pub fn age(&self, endpoint: &str) -> Option<Duration> {
+        self.read(&normalize(endpoint)).map(|cached| cached.age)
+    }
+
+    /// Stores a response body for `endpoint`.
+    ///
+    /// Bodies that are not valid JSON, and endpoints without a TTL, are ignored. Write
+    /// failures are logged rather than returned, since the cache is only an optimization.
+    pub fn store(&self, endpoint: &str, body: &[u8]) {
+        if !self.is_cacheable(endpoint) || self.mode == CacheMode::Offline {
+            return;
+        }
+
+        let Ok(body) = serde_json::from_slice::<Value>(body) else {
+            return;
+        };
+        let entry = CacheEntry {
+            fetched_at: Utc::now(),
+            body,
+        };
+
+        if let Err(e) = self.write(&normalize(endpoint), &entry) {
+            warn!("Failed to write cache entry for {}: {}", endpoint, e);
+        }
+    }
+
+    /// Removes the entries for the given endpoints.
+    pub fn invalidate(&self, endpoints: &[&str]) {
+        for endpoint in endpoints {
+            let path = self.entry_path(&normalize(endpoint));
+            if path.exists() {
+                debug!("Invalidating cache entry for {}", endpoint);
+                if let Err(e) = fs::remove_file(&path) {
+                    warn!("Failed to remove cache entry {}: {}", path.display(), e);
+                }
+            }
+        }
+    }
+
+    /// Removes every entry for this account.
+    pub fn clear(&self) -> std::io::Result<()> {
+        if self.dir.exists() {
+            fs::remove_dir_all(&self.dir)?;
+        }
+        Ok(())
+    }
+
+    fn read(&self, endpoint: &str) -> Option<CachedResponse> {
+        let content = fs::read(self.entry_path(endpoint)).ok()?;
+        let entry: CacheEntry = serde_json::from_slice(&content).ok()?;
+        let age = (Utc::now() - entry.fetched_at).to_std().unwrap_or_default();
+
+        Some(CachedResponse {
+            body: serde_json::to_vec(&entry.body).ok()?,
+            age,
+        })
+    }
+
+    fn write(&self, endpoint: &str, entry: &CacheEntry) -> std::io::Result<()> {
+        fs::create_dir_all(&self.dir)?;
+
+        // Write then rename so a concurrent reader never sees a partial entry
+        let path = self.entry_path(endpoint);
+        let temp_path = path.with_extension(format!("{}.tmp", std::process::id()));
+        fs::write(&temp_path, serde_json::to_vec(entry)?)?;
+        fs::rename(&temp_path, &path)
+    }
+
+    fn entry_path(&self, endpoint: &str) -> PathBuf {
+        self.dir
+            .join(format!("{}.json", endpoint.replace('/', "_")))
+    }
+}
+
+fn normalize(endpoint: &str) -> String {
+    endpoint.trim_matches('/').to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cache(dir: &tempfile::TempDir) -> ResponseCache {
+        ResponseCache::new(dir.path()).scoped("http://lium.test|key")
+    }
+
+    #[test]
+    fn test_store_and_lookup_respects_ttl_and_mode() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = cache(&dir);
+
+        assert!(cache.lookup("pods").is_none());
+        cache.store("pods", br#"[{"id": "pod-1"}]"#);
+        let hit = cache.lookup("/pods/").unwrap();
+        assert_eq!(
+            serde_json::from_slice::<Value>(&hit.body).unwrap(),
+            serde_json::json!([{"id": "pod-1"}])
+        );
+
+        // Endpoints without a TTL are never cached
+        cache.store("users/me", br#"{"id": "user"}"#);
+        assert!(cache.lookup("users/me").is_none());
+
+        let expired = cache.clone().with_ttl("pods", Duration::ZERO);
+        std::thread::sleep(Duration::from_millis(5));
+        assert!(expired.lookup("pods").is_none());
+        assert!(expired
+            .clone()
+            .with_mode(CacheMode::Offline)
+            .lookup("pods")
+            .is_some());
+        assert!(cache
+            .clone()
+            .with_mode(CacheMode::Refresh)
+            .lookup("pods")
+            .is_none());
+    }
+
+    #[test]
+    fn test_invalidate_and_disabled_mode() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = cache(&dir);
+
+        cache.store("executors", b"[]");
+        cache.store("pods", b"[]");
+        cache.invalidate(&["pods"]);
+        assert!(cache.lookup("pods").is_none());
+        assert!(cache.lookup("executors").is_some());
+
+        let disabled = ResponseCache::new(dir.path())
+            .scoped("other-account")
+            .with_mode(CacheMode::Disabled);
+        disabled.store("executors", b"[]");
+        assert!(!disabled.dir().exists());
+    }
+
+    #[test]
+    fn test_scoped_dir_is_stable_across_builds() {
+        // First 8 bytes of SHA-256("http://lium.test|key"); must never change between
+        // releases, or upgrading would orphan every existing cache entry
+        let cache = ResponseCache::new("/cache").scoped("http://lium.test|key");
+        assert_eq!(cache.dir(), Path::new("/cache/3b31023771a8f611"));
+        assert_ne!(
+            ResponseCache::new("/cache")
+                .scoped("http://lium.test|other-key")
+                .dir(),
+            cache.dir()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_client_serves_cache_and_invalidates_after_rent() {
+        use crate::{InMemoryTransport, LiumApiClient, RetryPolicy};
+        use reqwest::Method;
+
+        let dir = tempfile::tempdir().unwrap();
+        let transport = InMemoryTransport::new().with_pods(&[]).with_json(
+            Method::POST,
+            "executors/exec-1/rent",
+            serde_json::json!({"id": "pod-1"}),
+        );
+        let client = LiumApiClient::with_base_url("key".to_string(), "http://fake".to_string())
+            .with_retry_policy(RetryPolicy::disabled())
+            .with_transport(transport.clone())
+            .with_cache(ResponseCache::new(dir.path()));
+
+        client.get_pods().await.unwrap();
+        client.get_pods().await.unwrap();
+        assert_eq!(transport.requests().len(), 1);
+
+        client
+            .rent_pod("exec-1", &lium_core::RentPodRequest::new("pod", "tmpl"))
+            .await
+            .unwrap();
+        client.get_pods().await.unwrap();
+        assert_eq!(
+            transport.request_log(),
+            vec![
+                (Method::GET, "pods".to_string()),
+                (Method::POST, "executors/exec-1/rent".to_string()),
+                (Method::GET, "pods".to_string()),
+            ]
+        );
+
+        // Offline mode serves whatever is cached and refuses everything else
+        let offline = LiumApiClient::with_base_url("key".to_string(), "http://fake".to_string())
+            .with_transport(transport.clone())
+            .with_cache(ResponseCache::new(dir.path()).with_mode(CacheMode::Offline));
+        assert!(offline.is_offline());
+        assert!(offline.get_pods().await.unwrap().is_empty());
+        assert!(offline.cache_age("pods").is_some());
+        assert!(matches!(
+            offline.get_executors().await,
+            Err(crate::ApiError::Offline(_))
+        ));
+        assert_eq!(transport.requests().len(), 3);
+    }
+}
@@ -1,10 +1,18 @@
+use crate::cache::{CacheMode, ResponseCache};
 use crate::errors::{ApiError, HttpError, Result};
+use crate::retry::{parse_retry_after, RetryPolicy};
+use crate::transport::{HttpRequest, HttpResponse, HttpSettings, HttpTransport, ReqwestTransport};
 use lium_core::{
-    ApiExecutorResponse, ApiPodResponse, ApiTemplateResponse, ExecutorInfo, PodInfo, TemplateInfo,
+    ApiExecutorResponse, ApiPodResponse, ApiTemplateResponse, ExecutorInfo, FundingWallet,
+    ImageRegistration, PodInfo, RentPodRequest, RentPodResponse, TemplateInfo, TemplateUpdate,
+    UnrentPodResponse, UserProfile,
 };
+use lium_utils::redact::{register_secret, MASK};
 use log::{debug, error, info, trace};
-use reqwest::{Client, Response, StatusCode};
+use reqwest::{Method, StatusCode};
 use serde_json::Value;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 /// Trait for providing configuration to the API client.
 ///
@@ -18,6 +26,7 @@ use serde_json::Value;
 /// # Examples
 /// ```rust
 /// use lium_api::client::ApiConfig;
+/// use lium_api::ApiError;
 ///
 /// struct MyConfig {
 ///     api_key: String,
@@ -25,12 +34,12 @@ use serde_json::Value;
 /// }
 ///
 /// impl ApiConfig for MyConfig {
-///     type Error = String;
-///     
+///     type Error = ApiError;
+///
 ///     fn get_api_key(&self) -> Result<String, Self::Error> {
 ///         Ok(self.api_key.clone())
 ///     }
-///     
+///
 ///     fn get_base_url(&self) -> Result<Option<String>, Self::Error> {
 ///         Ok(self.base_url.clone())
 ///     }
@@ -58,6 +67,29 @@ pub trait ApiConfig {
     fn get_base_url(&self) -> std::result::Result<Option<String>, Self::Error> {
         Ok(None)
     }
+
+    /// Retrieves the response cache to use, if any.
+    ///
+    /// This method is optional and defaults to no caching if not implemented.
+    ///
+    /// # Returns
+    /// * `Ok(Some(ResponseCache))` - Cache GET responses on disk
+    /// * `Ok(None)` - Always hit the network
+    /// * `Err(Self::Error)` - An error if the cache settings could not be retrieved
+    fn get_cache(&self) -> std::result::Result<Option<ResponseCache>, Self::Error> {
+        Ok(None)
+    }
+
+    /// Retrieves the network settings (timeouts, proxy, CA bundle) for the HTTP client.
+    ///
+    /// This method is optional and defaults to [`HttpSettings::default`] if not implemented.
+    ///
+    /// # Returns
+    /// * `Ok(HttpSettings)` - The settings to build the HTTP client with
+    /// * `Err(Self::Error)` - An error if the settings could not be retrieved
+    fn get_http_settings(&self) -> std::result::Result<HttpSettings, Self::Error> {
+        Ok(HttpSettings::default())
+    }
 }
 
 /// HTTP client for interacting with the Celium Compute API.
@@ -66,9 +98,11 @@ pub trait ApiConfig {
 /// It handles authentication, request formatting, and response parsing.
 ///
 /// # Fields
-/// * `client` - The underlying HTTP client for making requests
+/// * `transport` - The transport used to send HTTP requests (reqwest by default)
 /// * `api_key` - The API key used for authentication
 /// * `base_url` - The base URL for the API endpoints
+/// * `retry_policy` - Retry behaviour for transient failures (see [`RetryPolicy`])
+/// * `cache` - Optional on-disk cache for GET responses (see [`ResponseCache`])
 ///
 /// # Examples
 /// ```rust
@@ -81,12 +115,16 @@ pub trait ApiConfig {
 /// ```
 #[derive(Debug, Clone)]
 pub struct LiumApiClient {
-    /// The underlying HTTP client for making requests
-    client: Client,
+    /// The transport used to send HTTP requests
+    transport: Arc<dyn HttpTransport>,
     /// The API key used for authentication
     api_key: String,
     /// The base URL for the API endpoints
     base_url: String,
+    /// Retry behaviour for transient failures
+    retry_policy: RetryPolicy,
+    /// Optional on-disk cache for GET responses
+    cache: Option<ResponseCache>,
 }
 
 /// Implementation of the LiumApiClient struct.
@@ -109,7 +147,9 @@ pub struct LiumApiClient {
 /// configuration and environment variable issues.
 ///
 /// # Examples
-/// ```rust
+/// ```rust,no_run
+/// use lium_api::LiumApiClient;
+///
 /// // Create with API key only
 /// let client = LiumApiClient::from_api_key("your-api-key".to_string());
 ///
@@ -121,6 +161,7 @@ pub struct LiumApiClient {
 ///
 /// // Create from environment
 /// let client = LiumApiClient::from_env()?;
+/// # Ok::<(), lium_api::ApiError>(())
 /// ```
 impl LiumApiClient {
     /// Create a new API client
@@ -134,36 +175,32 @@ impl LiumApiClient {
     /// A new instance of LiumApiClient configured with the provided credentials.
     ///
     /// # Security
-    /// The API key is logged partially (first 4 and last 4 characters) for debugging purposes.
-    /// Full API key is never logged to maintain security.
+    /// The API key is registered with the redaction layer (see [`lium_utils::redact`]),
+    /// so it is masked in all console and log output; it is never logged itself.
     ///
     /// # Examples
     /// ```rust
+    /// # use lium_api::LiumApiClient;
     /// let client = LiumApiClient::new(
     ///     "your-api-key".to_string(),
     ///     Some("https://custom-api.example.com".to_string())
     /// );
     /// ```
     pub fn new(api_key: String, base_url: Option<String>) -> Self {
-        let client = Client::new();
         let base_url = base_url.unwrap_or_else(|| "https://celiumcompute.ai/api".to_string());
 
+        register_secret(&api_key);
+
         debug!("Creating LiumApiClient");
-        debug!(
-            "  API Key: {}...{}",
-            &api_key[..4.min(api_key.len())],
-            if api_key.len() > 4 {
-                &api_key[api_key.len().saturating_sub(4)..]
-            } else {
-                ""
-            }
-        );
+        debug!("  API Key: {}", MASK);
         debug!("  Base URL: {}", base_url);
 
         Self {
-            client,
+            transport: Arc::new(ReqwestTransport::new()),
             api_key,
             base_url,
+            retry_policy: RetryPolicy::default(),
+            cache: None,
         }
     }
 
@@ -174,8 +211,10 @@ impl LiumApiClient {
     /// * `Err(ApiError)` - If the LIUM_API_KEY environment variable is not set
     ///
     /// # Examples
-    /// ```rust
+    /// ```rust,no_run
+    /// # use lium_api::LiumApiClient;
     /// let client = LiumApiClient::from_env()?;
+    /// # Ok::<(), lium_api::ApiError>(())
     /// ```
     pub fn from_env() -> Result<Self> {
         debug!("Creating LiumApiClient from environment variable");
@@ -201,6 +240,7 @@ impl LiumApiClient {
     ///
     /// # Examples
     /// ```rust
+    /// # use lium_api::LiumApiClient;
     /// let client = LiumApiClient::from_api_key("your-api-key".to_string());
     /// ```
     pub fn from_api_key(api_key: String) -> Self {
@@ -219,6 +259,7 @@ impl LiumApiClient {
     ///
     /// # Examples
     /// ```rust
+    /// # use lium_api::LiumApiClient;
     /// let client = LiumApiClient::with_base_url(
     ///     "your-api-key".to_string(),
     ///     "https://custom-api.example.com".to_string()
@@ -236,29 +277,37 @@ impl LiumApiClient {
     ///
     /// # Returns
     /// * `Ok(LiumApiClient)` - A new client instance if configuration is valid
-    /// * `Err(C::Error)` - If there's an error retrieving configuration values
+    /// * `Err(C::Error)` - If there's an error retrieving configuration values, or the
+    ///   HTTP settings are invalid (bad proxy URL, unreadable CA bundle)
     ///
     /// # Examples
     /// ```rust
+    /// # use lium_api::{client::ApiConfig, ApiError, LiumApiClient};
+    /// # struct MyConfig;
+    /// # impl MyConfig {
+    /// #     fn new() -> Self {
+    /// #         MyConfig
+    /// #     }
+    /// # }
+    /// # impl ApiConfig for MyConfig {
+    /// #     type Error = ApiError;
+    /// #     fn get_api_key(&self) -> Result<String, ApiError> {
+    /// #         Ok("your-api-key".to_string())
+    /// #     }
+    /// # }
     /// let config = MyConfig::new();
     /// let client = LiumApiClient::from_config(&config)?;
+    /// # Ok::<(), ApiError>(())
     /// ```
     pub fn from_config<C>(config: &C) -> std::result::Result<Self, C::Error>
     where
         C: ApiConfig,
+        C::Error: From<ApiError>,
     {
         debug!("Creating LiumApiClient from config");
         let api_key = config.get_api_key()?;
-
-        debug!(
-            "Got API key from config: {}...{}",
-            &api_key[..4.min(api_key.len())],
-            if api_key.len() > 4 {
-                &api_key[api_key.len().saturating_sub(4)..]
-            } else {
-                ""
-            }
-        );
+        register_secret(&api_key);
+        debug!("Got API key from config");
 
         let base_url = config.get_base_url()?;
 
@@ -268,16 +317,143 @@ impl LiumApiClient {
             debug!("Using default base URL");
         }
 
-        Ok(Self::new(api_key, base_url))
+        let http_client = config.get_http_settings()?.build_client()?;
+        let client =
+            Self::new(api_key, base_url).with_transport(ReqwestTransport::with_client(http_client));
+        Ok(match config.get_cache()? {
+            Some(cache) => client.with_cache(cache),
+            None => client,
+        })
+    }
+
+    /// Replaces the retry policy used for transient failures.
+    ///
+    /// GET and DELETE requests are retried according to the policy by default; POST
+    /// requests (such as `rent_pod`) are only retried when `retry_posts` is enabled.
+    ///
+    /// # Arguments
+    /// * `retry_policy` - The retry policy to use
+    ///
+    /// # Returns
+    /// The client configured with the new retry policy
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use lium_api::{LiumApiClient, RetryPolicy};
+    /// let client = LiumApiClient::from_api_key("your-api-key".to_string())
+    ///     .with_retry_policy(RetryPolicy::default().with_max_attempts(5));
+    /// ```
+    pub fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        debug!("Using retry policy: {:?}", retry_policy);
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// Returns the retry policy used by this client.
+    pub fn retry_policy(&self) -> &RetryPolicy {
+        &self.retry_policy
+    }
+
+    /// Replaces the transport used to send HTTP requests.
+    ///
+    /// This is mainly useful for tests, which can pass an `InMemoryTransport` to serve
+    /// canned responses and inspect the requests the client made.
+    ///
+    /// # Arguments
+    /// * `transport` - The transport to use
+    ///
+    /// # Returns
+    /// The client configured with the new transport
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use lium_api::{InMemoryTransport, LiumApiClient};
+    /// let transport = InMemoryTransport::new().with_pods(&[]);
+    /// let client = LiumApiClient::from_api_key("test-key".to_string())
+    ///     .with_transport(transport.clone());
+    /// ```
+    pub fn with_transport(mut self, transport: impl HttpTransport + 'static) -> Self {
+        debug!("Using custom HTTP transport: {:?}", transport);
+        self.transport = Arc::new(transport);
+        self
+    }
+
+    /// Enables the on-disk response cache.
+    ///
+    /// The cache is scoped to this client's base URL and API key. Cacheable GET requests
+    /// are served from it while fresh, and mutating calls (`rent_pod`, `unrent_pod`,
+    /// `post_image`) invalidate the affected entries. In [`CacheMode::Offline`] the client
+    /// never touches the network.
+    ///
+    /// # Arguments
+    /// * `cache` - The cache to use
+    ///
+    /// # Returns
+    /// The client configured with the cache
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use lium_api::{CacheMode, LiumApiClient, ResponseCache};
+    /// # let cache_dir = std::env::temp_dir().join("lium-cache");
+    /// let client = LiumApiClient::from_api_key("your-api-key".to_string())
+    ///     .with_cache(ResponseCache::new(cache_dir).with_mode(CacheMode::Refresh));
+    /// ```
+    pub fn with_cache(mut self, cache: ResponseCache) -> Self {
+        let cache = cache.scoped(&format!("{}|{}", self.base_url, self.api_key));
+        debug!("Using response cache at {}", cache.dir().display());
+        self.cache = Some(cache);
+        self
+    }
+
+    /// Returns the response cache, if enabled.
+    pub fn cache(&self) -> Option<&ResponseCache> {
+        self.cache.as_ref()
+    }
+
+    /// Returns true if the client serves only cached data.
+    pub fn is_offline(&self) -> bool {
+        self.cache
+            .as_ref()
+            .is_some_and(|cache| cache.mode() == CacheMode::Offline)
+    }
+
+    /// Returns how old the cached data for `endpoint` is, if any is cached.
+    pub fn cache_age(&self, endpoint: &str) -> Option<Duration> {
+        self.cache.as_ref().and_then(|cache| cache.age(endpoint))
+    }
+
+    /// Returns a copy of this client that always fetches fresh data but keeps the cache updated.
+    ///
+    /// Useful for polling loops (such as waiting for image verification), which must see
+    /// changes as soon as the API reports them. Offline clients are returned unchanged.
+    pub fn refreshing(&self) -> Self {
+        let mut client = self.clone();
+        if let Some(cache) = client.cache.take() {
+            client.cache = Some(match cache.mode() {
+                CacheMode::Offline | CacheMode::Disabled => cache,
+                _ => cache.with_mode(CacheMode::Refresh),
+            });
+        }
+        client
+    }
+
+    /// Removes cached entries after a call that changed them on the server.
+    fn invalidate_cache(&self, endpoints: &[&str]) {
+        if let Some(cache) = &self.cache {
+            cache.invalidate(endpoints);
+        }
     }
 
     /// Makes a GET request to the specified endpoint.
     ///
+    /// Cacheable endpoints are served from the response cache while fresh, and successful
+    /// responses are written back to it.
+    ///
     /// # Arguments
     /// * `endpoint` - The API endpoint to request, without leading slash
     ///
     /// # Returns
-    /// * `Ok(Response)` - The HTTP response if successful
+    /// * `Ok(HttpResponse)` - The HTTP response if successful
     /// * `Err(ApiError)` - If the request fails
     ///
     /// # Authentication
@@ -285,55 +461,25 @@ impl LiumApiClient {
     /// with different API versions.
     ///
     /// # Examples
-    /// ```rust
+    /// ```rust,ignore
     /// let response = client.get("users/me").await?;
     /// ```
-    async fn get(&self, endpoint: &str) -> Result<Response> {
-        let url = format!(
-            "{}/{}",
-            self.base_url.trim_end_matches('/'),
-            endpoint.trim_start_matches('/')
-        );
-
-        debug!("HTTP GET request to: {}", url);
-        trace!("Request headers:");
-        trace!(
-            "  X-API-Key: {}...{}",
-            &self.api_key[..4.min(self.api_key.len())],
-            if self.api_key.len() > 4 {
-                &self.api_key[self.api_key.len().saturating_sub(4)..]
-            } else {
-                ""
-            }
-        );
-        trace!(
-            "  Authorization: Bearer {}...{}",
-            &self.api_key[..4.min(self.api_key.len())],
-            if self.api_key.len() > 4 {
-                &self.api_key[self.api_key.len().saturating_sub(4)..]
-            } else {
-                ""
-            }
-        );
-        trace!("  Content-Type: application/json");
-
-        let response = self
-            .client
-            .get(&url)
-            // Try multiple header formats - the API will use whichever it expects
-            .header("X-API-Key", &self.api_key)
-            .header("Authorization", format!("Bearer {}", self.api_key))
-            .header("Content-Type", "application/json")
-            .send()
-            .await
-            .map_err(|e| {
-                error!("GET request failed: {:?}", e);
-                HttpError::Request(e)
-            })?;
+    async fn get(&self, endpoint: &str) -> Result<HttpResponse> {
+        let cache = self
+            .cache
+            .as_ref()
+            .filter(|cache| cache.is_cacheable(endpoint));
+
+        if let Some(cached) = cache.and_then(|cache| cache.lookup(endpoint)) {
+            return Ok(HttpResponse::new(StatusCode::OK, cached.body));
+        }
 
-        debug!("Response status: {}", response.status());
+        let response = self.send(Method::GET, endpoint, None, true).await?;
+        if let Some(cache) = cache {
+            cache.store(endpoint, &response.body);
+        }
 
-        self.handle_response(response).await
+        Ok(response)
     }
 
     /// Makes a POST request to the specified endpoint with an optional JSON body.
@@ -343,77 +489,45 @@ impl LiumApiClient {
     /// * `body` - Optional JSON body to send with the request
     ///
     /// # Returns
-    /// * `Ok(Response)` - The HTTP response if successful
+    /// * `Ok(HttpResponse)` - The HTTP response if successful
     /// * `Err(ApiError)` - If the request fails
     ///
     /// # Authentication
     /// The request includes both X-API-Key and Authorization headers for compatibility
     /// with different API versions.
     ///
+    /// # Retries
+    /// POST requests are not idempotent, so they are only retried when the client's
+    /// retry policy has `retry_posts` enabled.
+    ///
     /// # Examples
-    /// ```rust
+    /// ```rust,ignore
     /// let body = serde_json::json!({
     ///     "name": "example",
     ///     "value": 42
     /// });
     /// let response = client.post("resources", Some(body)).await?;
     /// ```
-    async fn post(&self, endpoint: &str, body: Option<Value>) -> Result<Response> {
-        let url = format!(
-            "{}/{}",
-            self.base_url.trim_end_matches('/'),
-            endpoint.trim_start_matches('/')
-        );
-
-        debug!("HTTP POST request to: {}", url);
-        trace!("Request headers:");
-        trace!(
-            "  X-API-Key: {}...{}",
-            &self.api_key[..4.min(self.api_key.len())],
-            if self.api_key.len() > 4 {
-                &self.api_key[self.api_key.len().saturating_sub(4)..]
-            } else {
-                ""
-            }
-        );
-        trace!(
-            "  Authorization: Bearer {}...{}",
-            &self.api_key[..4.min(self.api_key.len())],
-            if self.api_key.len() > 4 {
-                &self.api_key[self.api_key.len().saturating_sub(4)..]
-            } else {
-                ""
-            }
-        );
-        trace!("  Content-Type: application/json");
-
-        if let Some(ref body) = body {
-            trace!(
-                "Request body: {}",
-                serde_json::to_string_pretty(body).unwrap_or_else(|_| "Invalid JSON".to_string())
-            );
-        }
-
-        let mut request = self
-            .client
-            .post(&url)
-            // Try multiple header formats
-            .header("X-API-Key", &self.api_key)
-            .header("Authorization", format!("Bearer {}", self.api_key))
-            .header("Content-Type", "application/json");
-
-        if let Some(body) = body {
-            request = request.json(&body);
-        }
-
-        let response = request.send().await.map_err(|e| {
-            error!("POST request failed: {:?}", e);
-            HttpError::Request(e)
-        })?;
-
-        debug!("Response status: {}", response.status());
+    async fn post(&self, endpoint: &str, body: Option<Value>) -> Result<HttpResponse> {
+        let retryable = self.retry_policy.retry_posts;
+        self.send(Method::POST, endpoint, body, retryable).await
+    }
 
-        self.handle_response(response).await
+    /// Makes a PUT request to the specified endpoint with a JSON body.
+    ///
+    /// # Arguments
+    /// * `endpoint` - The API endpoint to request, without leading slash
+    /// * `body` - JSON body to send with the request
+    ///
+    /// # Returns
+    /// * `Ok(HttpResponse)` - The HTTP response if successful
+    /// * `Err(ApiError)` - If the request fails
+    ///
+    /// # Retries
+    /// PUT requests replace a resource's fields with the same values every time, so
+    /// they are idempotent and retried like GET requests.
+    async fn put(&self, endpoint: &str, body: Value) -> Result<HttpResponse> {
+        self.send(Method::PUT, endpoint, Some(body), true).await
     }
 
     /// Makes a DELETE request to the specified API endpoint.
@@ -425,63 +539,146 @@ impl LiumApiClient {
     /// * `endpoint` - The API endpoint to send the DELETE request to (e.g., "executors/123/rent")
     ///
     /// # Returns
-    /// * `Ok(Response)` - The HTTP response if the request was successful
+    /// * `Ok(HttpResponse)` - The HTTP response if the request was successful
     /// * `Err(ApiError)` - An error if the request failed
     ///
     /// # Security
-    /// The API key is logged partially (first 4 and last 4 characters) for debugging purposes.
-    /// Full API key is never logged to maintain security.
+    /// The API key is never logged; credential headers are logged masked.
     ///
     /// # Examples
-    /// ```rust
+    /// ```rust,ignore
     /// let response = client.delete("executors/123/rent").await?;
     /// ```
-    async fn delete(&self, endpoint: &str) -> Result<Response> {
+    async fn delete(&self, endpoint: &str) -> Result<HttpResponse> {
+        self.send(Method::DELETE, endpoint, None, true).await
+    }
+
+    /// Sends a request, retrying transient failures according to the retry policy.
+    ///
+    /// Responses with a retryable status (429, 503, 502, 504, 408) and connection or
+    /// timeout errors are retried with exponential backoff. A `Retry-After` header on
+    /// the response overrides the computed delay.
+    ///
+    /// # Arguments
+    /// * `method` - The HTTP method to use
+    /// * `endpoint` - The API endpoint to request, without leading slash
+    /// * `body` - Optional JSON body to send with the request
+    /// * `retryable` - Whether the request may be retried at all
+    ///
+    /// # Returns
+    /// * `Ok(HttpResponse)` - The HTTP response if successful
+    /// * `Err(ApiError)` - The last error once retries are exhausted
+    ///
+    /// # Logging
+    /// Each attempt logs the method, URL, status, size and duration at `debug` level;
+    /// request and response bodies are logged at `trace` level. Credential headers are
+    /// always logged masked.
+    async fn send(
+        &self,
+        method: Method,
+        endpoint: &str,
+        body: Option<Value>,
+        retryable: bool,
+    ) -> Result<HttpResponse> {
+        if self.is_offline() {
+            return Err(ApiError::Offline(format!(
+                "no cached data for {} {}; run without --offline to fetch it",
+                method, endpoint
+            )));
+        }
+
         let url = format!(
             "{}/{}",
             self.base_url.trim_end_matches('/'),
             endpoint.trim_start_matches('/')
         );
+        let max_attempts = if retryable {
+            self.retry_policy.max_attempts.max(1)
+        } else {
+            1
+        };
 
-        debug!("HTTP DELETE request to: {}", url);
+        debug!("HTTP {} request to: {}", method, url);
         trace!("Request headers:");
-        trace!(
-            "  X-API-Key: {}...{}",
-            &self.api_key[..4.min(self.api_key.len())],
-            if self.api_key.len() > 4 {
-                &self.api_key[self.api_key.len().saturating_sub(4)..]
-            } else {
-                ""
-            }
-        );
-        trace!(
-            "  Authorization: Bearer {}...{}",
-            &self.api_key[..4.min(self.api_key.len())],
-            if self.api_key.len() > 4 {
-                &self.api_key[self.api_key.len().saturating_sub(4)..]
-            } else {
-                ""
-            }
-        );
+        trace!("  X-API-Key: {}", MASK);
+        trace!("  Authorization: Bearer {}", MASK);
         trace!("  Content-Type: application/json");
 
-        let response = self
-            .client
-            .delete(&url)
-            // Try multiple header formats for maximum compatibility
-            .header("X-API-Key", &self.api_key)
-            .header("Authorization", format!("Bearer {}", self.api_key))
-            .header("Content-Type", "application/json")
-            .send()
-            .await
-            .map_err(|e| {
-                error!("DELETE request failed: {:?}", e);
-                HttpError::Request(e)
-            })?;
+        if let Some(ref body) = body {
+            trace!(
+                "Request body: {}",
+                serde_json::to_string_pretty(body).unwrap_or_else(|_| "Invalid JSON".to_string())
+            );
+        }
 
-        debug!("Response status: {}", response.status());
+        let request = HttpRequest {
+            method: method.clone(),
+            url: url.clone(),
+            endpoint: endpoint.trim_start_matches('/').to_string(),
+            headers: vec![
+                // Try multiple header formats - the API will use whichever it expects
+                ("X-API-Key".to_string(), self.api_key.clone()),
+                (
+                    "Authorization".to_string(),
+                    format!("Bearer {}", self.api_key),
+                ),
+                ("Content-Type".to_string(), "application/json".to_string()),
+            ],
+            body,
+        };
+
+        let mut attempt = 1;
+        loop {
+            let started = Instant::now();
+            match self.transport.send(request.clone()).await {
+                Ok(response) => {
+                    let status = response.status();
+                    debug!(
+                        "HTTP {} {} -> {} ({} bytes in {:?})",
+                        method,
+                        url,
+                        status,
+                        response.body.len(),
+                        started.elapsed()
+                    );
+                    trace!("Response body: {}", response.text());
+
+                    if attempt < max_attempts && RetryPolicy::is_retryable_status(status) {
+                        let retry_after = parse_retry_after(response.headers());
+                        let delay = self.retry_policy.delay_for(attempt, retry_after);
+                        debug!(
+                            "{} {} returned {}, retrying in {:?} (attempt {}/{})",
+                            method, url, status, delay, attempt, max_attempts
+                        );
+                        tokio::time::sleep(delay).await;
+                        attempt += 1;
+                        continue;
+                    }
+
+                    if attempt > 1 {
+                        debug!("{} {} completed after {} attempts", method, url, attempt);
+                    }
 
-        self.handle_response(response).await
+                    return self.handle_response(response);
+                }
+                Err(e) if attempt < max_attempts && RetryPolicy::is_retryable_error(&e) => {
+                    let delay = self.retry_policy.delay_for(attempt, None);
+                    debug!(
+                        "{} {} failed: {}, retrying in {:?} (attempt {}/{})",
+                        method, url, e, delay, attempt, max_attempts
+                    );
+                    tokio::time::sleep(delay).await;
+                    attempt += 1;
+                }
+                Err(e) => {
+                    debug!(
+                        "{} request failed after {} attempt(s): {:?}",
+                        method, attempt, e
+                    );
+                    return Err(e.into());
+                }
+            }
+        }
     }
 
     /// Handles HTTP responses and converts them into appropriate Result types.
@@ -493,7 +690,7 @@ impl LiumApiClient {
     /// * `response` - The HTTP response to process
     ///
     /// # Returns
-    /// * `Ok(Response)` - The original response if successful
+    /// * `Ok(HttpResponse)` - The original response if successful
     /// * `Err(ApiError)` - An appropriate error based on the response status
     ///
     /// # Error Handling
@@ -506,48 +703,46 @@ impl LiumApiClient {
     /// * Other - Generic HTTP error with status code and message
     ///
     /// # Examples
-    /// ```rust
+    /// ```rust,ignore
     /// let response = client.get("endpoint").await?;
-    /// let processed_response = client.handle_response(response).await?;
+    /// let processed_response = client.handle_response(response)?;
     /// ```
-    async fn handle_response(&self, response: Response) -> Result<Response> {
+    fn handle_response(&self, response: HttpResponse) -> Result<HttpResponse> {
         let status = response.status();
 
         if status.is_success() {
             debug!("Request successful with status: {}", status);
             Ok(response)
         } else {
-            let error_text = response
-                .text()
-                .await
-                .unwrap_or_else(|_| "Unknown error".to_string());
+            let error_text = response.text();
 
-            error!("Request failed with status: {}", status);
+            // The error is returned to the caller, so it is only logged for diagnostics
+            debug!("Request failed with status: {}", status);
             debug!("Error response body: {}", error_text);
 
             let api_error = match status {
                 StatusCode::UNAUTHORIZED => {
-                    error!("Authentication failed (401 Unauthorized)");
+                    debug!("Authentication failed (401 Unauthorized)");
                     HttpError::AuthenticationFailed
                 }
                 StatusCode::FORBIDDEN => {
-                    error!("Invalid API key (403 Forbidden)");
+                    debug!("Invalid API key (403 Forbidden)");
                     HttpError::InvalidApiKey
                 }
                 StatusCode::TOO_MANY_REQUESTS => {
-                    error!("Rate limited (429 Too Many Requests)");
+                    debug!("Rate limited (429 Too Many Requests)");
                     HttpError::RateLimited
                 }
                 StatusCode::SERVICE_UNAVAILABLE => {
-                    error!("Service unavailable (503)");
+                    debug!("Service unavailable (503)");
                     HttpError::ServiceUnavailable
                 }
                 StatusCode::REQUEST_TIMEOUT => {
-                    error!("Request timeout (408)");
+                    debug!("Request timeout (408)");
                     HttpError::Timeout
                 }
                 _ => {
-                    error!("HTTP error with status code: {}", status.as_u16());
+                    debug!("HTTP error with status code: {}", status.as_u16());
                     HttpError::HttpError {
                         status: status.as_u16(),
                         message: error_text,
@@ -571,16 +766,19 @@ impl LiumApiClient {
     ///
     /// # Examples
     /// ```rust
+    /// # use lium_api::{LiumApiClient, Result};
+    /// # async fn example(client: LiumApiClient) -> Result<()> {
     /// let executors = client.get_executors().await?;
     /// for executor in executors {
     ///     println!("Executor: {}", executor.id);
     /// }
+    /// # Ok(())
+    /// # }
     /// ```
     pub async fn get_executors(&self) -> Result<Vec<ExecutorInfo>> {
         debug!("Fetching executors");
         let response = self.get("executors").await?;
-        let raw_executors: Vec<ApiExecutorResponse> =
-            response.json().await.map_err(HttpError::Request)?;
+        let raw_executors: Vec<ApiExecutorResponse> = response.json()?;
 
         info!("Successfully fetched {} executors", raw_executors.len());
 
@@ -601,15 +799,19 @@ impl LiumApiClient {
     ///
     /// # Examples
     /// ```rust
+    /// # use lium_api::{LiumApiClient, Result};
+    /// # async fn example(client: LiumApiClient) -> Result<()> {
     /// let pods = client.get_pods().await?;
     /// for pod in pods {
-    ///     println!("Pod: {} on executor {}", pod.name, pod.executor_id);
+    ///     println!("Pod: {} ({})", pod.name, pod.status);
     /// }
+    /// # Ok(())
+    /// # }
     /// ```
     pub async fn get_pods(&self) -> Result<Vec<PodInfo>> {
         debug!("Fetching pods");
         let response = self.get("pods").await?;
-        let raw_pods: Vec<ApiPodResponse> = response.json().await.map_err(HttpError::Request)?;
+        let raw_pods: Vec<ApiPodResponse> = response.json()?;
 
         info!("Successfully fetched {} pods", raw_pods.len());
 
@@ -621,53 +823,56 @@ impl LiumApiClient {
 
     /// Rents a new pod on a specified executor using a template.
     ///
-    /// This method creates a new pod on the specified executor using the provided template
-    /// and configuration. The pod will be accessible using the provided SSH public keys.
+    /// This method creates a new pod on the specified executor using the template,
+    /// SSH keys and container options (environment variables, port mappings, volume size,
+    /// startup command) carried by the request.
     ///
     /// # Arguments
     /// * `executor_id` - The ID of the executor to rent the pod on
-    /// * `pod_name` - A unique name for the pod
-    /// * `template_id` - The ID of the template to use for the pod
-    /// * `user_public_keys` - Vector of SSH public keys that will have access to the pod
+    /// * `request` - Pod name, template, SSH public keys and pod options
+    ///
+    /// # Retries
+    /// Renting is not idempotent and is only retried when `retry_posts` is enabled
+    /// on the client's retry policy.
     ///
     /// # Returns
-    /// * `Ok(Value)` - The API response containing pod details if successful
-    /// * `Err(ApiError)` - An error if the request failed
+    /// * `Ok(RentPodResponse)` - The created pod's details if successful
+    /// * `Err(ApiError)` - An error if the request failed or the response has an unexpected shape
     ///
     /// # Examples
     /// ```rust
-    /// let result = client.rent_pod(
-    ///     "exec-123",
-    ///     "my-pod",
-    ///     "template-456",
-    ///     vec!["ssh-rsa AAAAB3NzaC1yc2EAAAADA...".to_string()]
-    /// ).await?;
+    /// # use lium_api::{LiumApiClient, Result};
+    /// # use lium_core::{parse_env_vars, RentPodRequest};
+    /// # async fn example(client: LiumApiClient) -> Result<()> {
+    /// let request = RentPodRequest::new("my-pod", "template-456")
+    ///     .with_ssh_keys(vec!["ssh-rsa AAAAB3NzaC1yc2EAAAADA...".to_string()])
+    ///     .with_env(parse_env_vars("DEBUG=1")?);
+    /// let result = client.rent_pod("exec-123", &request).await?;
+    /// # Ok(())
+    /// # }
     /// ```
     pub async fn rent_pod(
         &self,
         executor_id: &str,
-        pod_name: &str,
-        template_id: &str,
-        user_public_keys: Vec<String>,
-    ) -> Result<Value> {
+        request: &RentPodRequest,
+    ) -> Result<RentPodResponse> {
         debug!("Renting pod");
         debug!("  executor_id: {}", executor_id);
-        debug!("  pod_name: {}", pod_name);
-        debug!("  template_id: {}", template_id);
-        debug!("  ssh_keys count: {}", user_public_keys.len());
+        debug!("  pod_name: {}", request.pod_name);
+        debug!("  template_id: {}", request.template_id);
+        debug!("  ssh_keys count: {}", request.user_public_keys.len());
+        debug!("  env vars: {}", request.spec.env.len());
+        debug!("  port mappings: {}", request.spec.ports.len());
 
-        let body = serde_json::json!({
-            "pod_name": pod_name,
-            "template_id": template_id,
-            "user_public_key": user_public_keys,  // Note: API expects "user_public_key" not "ssh_public_keys"
-        });
+        let body = serde_json::to_value(request)?;
 
         // Use the correct endpoint: /executors/{executor_id}/rent
         let endpoint = format!("executors/{}/rent", executor_id);
         let response = self.post(&endpoint, Some(body)).await?;
-        let result: Value = response.json().await.map_err(HttpError::Request)?;
+        self.invalidate_cache(&["pods", "executors"]);
+        let result: RentPodResponse = response.json()?;
 
-        info!("Successfully rented pod: {}", pod_name);
+        info!("Successfully rented pod: {}", request.pod_name);
 
         Ok(result)
     }
@@ -682,20 +887,25 @@ impl LiumApiClient {
     /// * `executor_id` - The ID of the executor whose pod should be unrented
     ///
     /// # Returns
-    /// * `Ok(Value)` - The API response confirming the unrent if successful
+    /// * `Ok(UnrentPodResponse)` - The API response confirming the unrent if successful
     /// * `Err(ApiError)` - An error if the request failed
     ///
     /// # Examples
     /// ```rust
+    /// # use lium_api::{LiumApiClient, Result};
+    /// # async fn example(client: LiumApiClient) -> Result<()> {
     /// let result = client.unrent_pod("exec-123").await?;
+    /// # Ok(())
+    /// # }
     /// ```
-    pub async fn unrent_pod(&self, executor_id: &str) -> Result<Value> {
+    pub async fn unrent_pod(&self, executor_id: &str) -> Result<UnrentPodResponse> {
         debug!("Unrenting pod with executor_id: {}", executor_id);
 
         // Use DELETE /executors/{executor_id}/rent
         let endpoint = format!("executors/{}/rent", executor_id);
         let response = self.delete(&endpoint).await?;
-        let result: Value = response.json().await.map_err(HttpError::Request)?;
+        self.invalidate_cache(&["pods", "executors"]);
+        let result: UnrentPodResponse = response.json()?;
 
         info!("Successfully unrented pod");
 
@@ -714,16 +924,19 @@ impl LiumApiClient {
     ///
     /// # Examples
     /// ```rust
+    /// # use lium_api::{LiumApiClient, Result};
+    /// # async fn example(client: LiumApiClient) -> Result<()> {
     /// let templates = client.get_templates().await?;
     /// for template in templates {
     ///     println!("Template: {} ({})", template.name, template.id);
     /// }
+    /// # Ok(())
+    /// # }
     /// ```
     pub async fn get_templates(&self) -> Result<Vec<TemplateInfo>> {
         debug!("Fetching templates");
         let response = self.get("templates").await?;
-        let raw_templates: Vec<ApiTemplateResponse> =
-            response.json().await.map_err(HttpError::Request)?;
+        let raw_templates: Vec<ApiTemplateResponse> = response.json()?;
 
         info!("Successfully fetched {} templates", raw_templates.len());
 
@@ -732,6 +945,104 @@ impl LiumApiClient {
         Ok(templates)
     }
 
+    /// Retrieves a single template by ID.
+    ///
+    /// Unlike [`LiumApiClient::get_templates`], this always hits the network, so the
+    /// verification status it reports is current.
+    ///
+    /// # Arguments
+    /// * `template_id` - The ID of the template to fetch
+    ///
+    /// # Returns
+    /// * `Ok(TemplateInfo)` - The template, including its verification status and image digest
+    /// * `Err(ApiError)` - An error if the template does not exist or the request failed
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use lium_api::{LiumApiClient, Result};
+    /// # async fn example(client: LiumApiClient) -> Result<()> {
+    /// let template = client.get_template("tmpl-123").await?;
+    /// println!("{} is {:?}", template.name, template.status);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn get_template(&self, template_id: &str) -> Result<TemplateInfo> {
+        debug!("Fetching template: {}", template_id);
+        let response = self.get(&format!("templates/{}", template_id)).await?;
+        let raw_template: ApiTemplateResponse = response.json()?;
+
+        Ok(raw_template.into())
+    }
+
+    /// Updates a template's name and/or description.
+    ///
+    /// # Arguments
+    /// * `template_id` - The ID of the template to update
+    /// * `update` - The fields to change; unset fields are left as they are
+    ///
+    /// # Returns
+    /// * `Ok(TemplateInfo)` - The updated template
+    /// * `Err(ApiError)` - An error if the template does not exist or the request failed
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use lium_api::{LiumApiClient, Result};
+    /// # use lium_core::TemplateUpdate;
+    /// # async fn example(client: LiumApiClient) -> Result<()> {
+    /// let update = TemplateUpdate::new().with_description("PyTorch 2.3 with CUDA 12.1");
+    /// let template = client.update_template("tmpl-123", &update).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn update_template(
+        &self,
+        template_id: &str,
+        update: &TemplateUpdate,
+    ) -> Result<TemplateInfo> {
+        debug!("Updating template {}: {:?}", template_id, update);
+        let body = serde_json::to_value(update)?;
+
+        let response = self
+            .put(&format!("templates/{}", template_id), body)
+            .await?;
+        self.invalidate_cache(&["templates"]);
+        let raw_template: ApiTemplateResponse = response.json()?;
+
+        info!("Successfully updated template: {}", template_id);
+
+        Ok(raw_template.into())
+    }
+
+    /// Deletes a template.
+    ///
+    /// Pods already running from the template are not affected.
+    ///
+    /// # Arguments
+    /// * `template_id` - The ID of the template to delete
+    ///
+    /// # Returns
+    /// * `Ok(())` - If the template was deleted
+    /// * `Err(ApiError)` - An error if the template does not exist or the request failed
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use lium_api::{LiumApiClient, Result};
+    /// # async fn example(client: LiumApiClient) -> Result<()> {
+    /// client.delete_template("tmpl-123").await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn delete_template(&self, template_id: &str) -> Result<()> {
+        debug!("Deleting template: {}", template_id);
+
+        self.delete(&format!("templates/{}", template_id)).await?;
+        self.invalidate_cache(&["templates"]);
+
+        info!("Successfully deleted template: {}", template_id);
+
+        Ok(())
+    }
+
     /// Registers a new Docker image with the Celium Compute platform.
     ///
     /// This method allows you to register a Docker image that can be used in pods.
@@ -743,18 +1054,27 @@ impl LiumApiClient {
     /// * `tag` - The tag of the image (e.g., "latest", "v1.0.0")
     ///
     /// # Returns
-    /// * `Ok(Value)` - The API response confirming the image registration if successful
+    /// * `Ok(ImageRegistration)` - The registered image if successful
     /// * `Err(ApiError)` - An error if the request failed
     ///
     /// # Examples
     /// ```rust
+    /// # use lium_api::{LiumApiClient, Result};
+    /// # async fn example(client: LiumApiClient) -> Result<()> {
     /// let result = client.post_image(
     ///     "myorg/myapp",
     ///     "sha256:1234567890abcdef...",
     ///     "v1.0.0"
     /// ).await?;
+    /// # Ok(())
+    /// # }
     /// ```
-    pub async fn post_image(&self, image_name: &str, digest: &str, tag: &str) -> Result<Value> {
+    pub async fn post_image(
+        &self,
+        image_name: &str,
+        digest: &str,
+        tag: &str,
+    ) -> Result<ImageRegistration> {
         debug!("Posting Docker image: {}:{}", image_name, tag);
         let body = serde_json::json!({
             "image_name": image_name,
@@ -763,7 +1083,8 @@ impl LiumApiClient {
         });
 
         let response = self.post("images", Some(body)).await?;
-        let result: Value = response.json().await.map_err(HttpError::Request)?;
+        self.invalidate_cache(&["templates"]);
+        let result: ImageRegistration = response.json()?;
 
         info!("Successfully posted Docker image");
 
@@ -776,20 +1097,26 @@ impl LiumApiClient {
     /// Each wallet represents a funding source that can be used for platform operations.
     ///
     /// # Returns
-    /// * `Ok(Value)` - A JSON value containing the list of funding wallets and their details
+    /// * `Ok(Vec<FundingWallet>)` - The funding wallets linked to the account
     /// * `Err(ApiError)` - An error if the request fails or the response cannot be parsed
     ///
     /// # Examples
     /// ```rust
+    /// # use lium_api::{LiumApiClient, Result};
+    /// # async fn example(client: LiumApiClient) -> Result<()> {
     /// let wallets = client.get_funding_wallets().await?;
-    /// println!("Available funding wallets: {}", wallets);
+    /// for wallet in wallets {
+    ///     println!("Wallet: {}", wallet.wallet_hash);
+    /// }
+    /// # Ok(())
+    /// # }
     /// ```
-    pub async fn get_funding_wallets(&self) -> Result<Value> {
+    pub async fn get_funding_wallets(&self) -> Result<Vec<FundingWallet>> {
         debug!("Fetching funding wallets");
         let response = self.get("funding/wallets").await?;
-        let result: Value = response.json().await.map_err(HttpError::Request)?;
+        let result: Vec<FundingWallet> = response.json()?;
 
-        info!("Successfully fetched funding wallets");
+        info!("Successfully fetched {} funding wallets", result.len());
 
         Ok(result)
     }
@@ -801,7 +1128,7 @@ impl LiumApiClient {
     /// that should be handled securely.
     ///
     /// # Returns
-    /// * `Ok(Value)` - A JSON value containing the user's profile information
+    /// * `Ok(UserProfile)` - The user's profile information
     /// * `Err(ApiError)` - An error if the request fails or the response cannot be parsed
     ///
     /// # Security
@@ -810,13 +1137,17 @@ impl LiumApiClient {
     ///
     /// # Examples
     /// ```rust
+    /// # use lium_api::{LiumApiClient, Result};
+    /// # async fn example(client: LiumApiClient) -> Result<()> {
     /// let user_info = client.get_users_me().await?;
-    /// println!("User profile: {}", user_info);
+    /// println!("App ID: {}", user_info.app_id);
+    /// # Ok(())
+    /// # }
     /// ```
-    pub async fn get_users_me(&self) -> Result<Value> {
+    pub async fn get_users_me(&self) -> Result<UserProfile> {
         debug!("Fetching user information");
         let response = self.get("users/me").await?;
-        let result: Value = response.json().await.map_err(HttpError::Request)?;
+        let result: UserProfile = response.json()?;
 
         info!("Successfully fetched user information");
 
@@ -839,20 +1170,23 @@ impl LiumApiClient {
     ///
     /// # Examples
     /// ```rust
+    /// # use lium_api::{LiumApiClient, Result};
+    /// # async fn example(client: LiumApiClient) -> Result<()> {
     /// let access_key = client.get_access_key().await?;
     /// // Use access_key for wallet operations
+    /// # Ok(())
+    /// # }
     /// ```
     pub async fn get_access_key(&self) -> Result<String> {
         debug!("Getting access key from user info");
         let user_info = self.get_users_me().await?;
         let access_key = user_info
-            .get("access_key")
-            .and_then(|v| v.as_str())
+            .access_key
             .ok_or_else(|| HttpError::Config("Access key not found in user info".to_string()))?;
 
         debug!("Successfully retrieved access key");
 
-        Ok(access_key.to_string())
+        Ok(access_key)
     }
 
     /// Retrieves the application ID required for wallet operations.
@@ -867,20 +1201,20 @@ impl LiumApiClient {
     ///
     /// # Examples
     /// ```rust
+    /// # use lium_api::{LiumApiClient, Result};
+    /// # async fn example(client: LiumApiClient) -> Result<()> {
     /// let app_id = client.get_app_id().await?;
     /// // Use app_id for wallet operations
+    /// # Ok(())
+    /// # }
     /// ```
     pub async fn get_app_id(&self) -> Result<String> {
         debug!("Getting app ID from user info");
         let user_info = self.get_users_me().await?;
-        let app_id = user_info
-            .get("app_id")
-            .and_then(|v| v.as_str())
-            .ok_or_else(|| HttpError::Config("App ID not found in user info".to_string()))?;
 
         debug!("Successfully retrieved app ID");
 
-        Ok(app_id.to_string())
+        Ok(user_info.app_id)
     }
 
     /// Adds a wallet for funding operations to the user's account.
@@ -908,12 +1242,16 @@ impl LiumApiClient {
     ///
     /// # Examples
     /// ```rust
+    /// # use lium_api::{LiumApiClient, Result};
+    /// # async fn example(client: LiumApiClient) -> Result<()> {
     /// let result = client.add_wallet(
     ///     "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY",
     ///     "access-key-123",
     ///     "0x1234...",
     ///     "app-456"
     /// ).await?;
+    /// # Ok(())
+    /// # }
     /// ```
     pub async fn add_wallet(
         &self,
@@ -931,7 +1269,7 @@ impl LiumApiClient {
         });
 
         let response = self.post("funding/wallets", Some(body)).await?;
-        let result: Value = response.json().await.map_err(HttpError::Request)?;
+        let result: Value = response.json()?;
 
         info!("Successfully added wallet");
 
@@ -951,11 +1289,15 @@ impl LiumApiClient {
     ///
     /// # Examples
     /// ```rust
+    /// # use lium_api::{LiumApiClient, Result};
+    /// # async fn example(client: LiumApiClient) -> Result<()> {
     /// if client.test_connection().await? {
     ///     println!("API is accessible");
     /// } else {
     ///     println!("API is not accessible");
     /// }
+    /// # Ok(())
+    /// # }
     /// ```
     pub async fn test_connection(&self) -> Result<bool> {
         debug!("Testing API connection");
@@ -979,7 +1321,7 @@ impl LiumApiClient {
     ///
     /// # Arguments
     /// * `request` - A JSON value containing the command execution details
-    ///              (e.g., pod ID, command to execute, timeout settings)
+    ///   (e.g., pod ID, command to execute, timeout settings)
     ///
     /// # Returns
     /// * `Ok(String)` - The command output if successful
@@ -987,6 +1329,8 @@ impl LiumApiClient {
     ///
     /// # Examples
     /// ```rust
+    /// # use lium_api::{LiumApiClient, Result};
+    /// # async fn example(client: LiumApiClient) -> Result<()> {
     /// let request = serde_json::json!({
     ///     "pod_id": "pod-123",
     ///     "command": "ls -la",
@@ -994,12 +1338,13 @@ impl LiumApiClient {
     /// });
     /// let output = client.exec_pod(&request).await?;
     /// println!("Command output: {}", output);
+    /// # Ok(())
+    /// # }
     /// ```
     pub async fn exec_pod(&self, request: &Value) -> Result<String> {
         debug!("Executing command in pod");
         let response = self.post("pods/exec", Some(request.clone())).await?;
-        let response = self.handle_response(response).await?;
-        let result: Value = response.json().await?;
+        let result: Value = response.json()?;
 
         // Extract command output from response
         if let Some(output) = result.get("output").and_then(|v| v.as_str()) {
@@ -1012,8 +1357,5 @@ impl LiumApiClient {
     }
 }
 
-// TODO: Add retry logic for transient failures
-// TODO: Add request/response logging
-// TODO: Add rate limiting handling
 // TODO: Add pagination support for large result sets
 // TODO: Add caching for frequently accessed data
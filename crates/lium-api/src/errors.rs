@@ -12,15 +12,22 @@ use thiserror::Error;
 /// * `Request` - Errors from HTTP requests, wrapped from reqwest::Error
 /// * `Json` - Errors related to JSON serialization/deserialization
 /// * `Config` - Configuration-related errors with a descriptive message
+/// * `Offline` - The request needs the network but the client is in offline mode
+/// * `Timeout` - A wait (such as for a pod to become ready) ran out of time
 ///
 /// # Examples
 /// ```rust
+/// # use lium_api::{ApiError, LiumApiClient};
+/// # async fn example(client: LiumApiClient) {
+/// # let result = client.get_pods().await;
 /// match result {
 ///     Ok(data) => println!("Success: {:?}", data),
 ///     Err(ApiError::Http(e)) => println!("HTTP error: {}", e),
 ///     Err(ApiError::Config(msg)) => println!("Config error: {}", msg),
 ///     // ... handle other variants
+/// #   Err(e) => println!("Error: {}", e),
 /// }
+/// # }
 /// ```
 #[derive(Error, Debug)]
 pub enum ApiError {
@@ -41,6 +48,12 @@ pub enum ApiError {
 
     #[error("Configuration error: {0}")]
     Config(String),
+
+    #[error("Offline: {0}")]
+    Offline(String),
+
+    #[error("Timed out: {0}")]
+    Timeout(String),
 }
 
 /// HTTP-specific errors that can occur during API communication.
@@ -61,11 +74,14 @@ pub enum ApiError {
 ///
 /// # Examples
 /// ```rust
+/// # use lium_api::HttpError;
+/// # let http_result: Result<&str, HttpError> = Err(HttpError::RateLimited);
 /// match http_result {
 ///     Ok(response) => println!("Success: {:?}", response),
 ///     Err(HttpError::AuthenticationFailed) => println!("Auth failed"),
 ///     Err(HttpError::RateLimited) => println!("Rate limited"),
 ///     // ... handle other variants
+/// #   Err(e) => println!("Error: {}", e),
 /// }
 /// ```
 #[derive(Error, Debug)]
@@ -103,6 +119,7 @@ pub enum HttpError {
 ///
 /// # Examples
 /// ```rust
+/// # use lium_api::Result;
 /// fn some_function() -> Result<String> {
 ///     // Function implementation
 ///     Ok("success".to_string())
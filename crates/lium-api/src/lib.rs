@@ -14,30 +14,36 @@
 //!
 //! ## Core Components
 //!
+//! - `cache`: On-disk response cache with per-endpoint TTLs and offline mode
 //! - `client`: Main API client implementation with methods for all endpoints
 //! - `errors`: Comprehensive error handling and custom error types
+//! - `ready`: Waiting for a rented pod to become reachable over SSH
+//! - `retry`: Retry policy with exponential backoff for transient failures
 //! - `sdk`: Additional SDK functionality and utilities
+//! - `transport`: Pluggable HTTP transport, with reqwest and in-memory implementations
+//! - `watch`: Pod change events built by diffing successive pod listings
 //!
 //! ## Usage
 //!
-//! ```rust
-//! use lium_api::{Client, ApiError};
+//! ```rust,no_run
+//! use lium_api::{ApiError, LiumApiClient};
+//! use lium_core::RentPodRequest;
 //!
 //! #[tokio::main]
 //! async fn main() -> Result<(), ApiError> {
-//!     let client = Client::new("https://api.lium.com", "your-api-key")?;
-//!     
+//!     let client = LiumApiClient::with_base_url(
+//!         "your-api-key".to_string(),
+//!         "https://api.lium.com".to_string(),
+//!     );
+//!
 //!     // Get available executors
 //!     let executors = client.get_executors().await?;
-//!     
+//!
 //!     // Create a new pod
-//!     let pod = client.rent_pod(
-//!         "exec-123",
-//!         "my-pod",
-//!         "template-456",
-//!         vec!["ssh-rsa AAAAB3NzaC1yc2EAAAADA...".to_string()]
-//!     ).await?;
-//!     
+//!     let request = RentPodRequest::new("my-pod", "template-456")
+//!         .with_ssh_keys(vec!["ssh-rsa AAAAB3NzaC1yc2EAAAADA...".to_string()]);
+//!     let pod = client.rent_pod("exec-123", &request).await?;
+//!
 //!     Ok(())
 //! }
 //! ```
@@ -65,14 +71,24 @@
 //! - `thiserror`: Error handling
 //! - `log`: Logging
 
+pub mod cache;
 pub mod client;
 pub mod errors;
+pub mod ready;
+pub mod retry;
 pub mod sdk;
+pub mod transport;
+pub mod watch;
 
 // Re-export common types for convenience
+pub use cache::*;
 pub use client::*;
 pub use errors::*;
+pub use ready::{probe_ssh, ReadyOptions, ReadyProgress};
+pub use retry::*;
 pub use sdk::*;
+pub use transport::*;
+pub use watch::{PodDiffer, PodEvent};
 
 // Re-export core types that API consumers will need
 pub use lium_core::{ExecutorInfo, PodInfo, Result as CoreResult, TemplateInfo};
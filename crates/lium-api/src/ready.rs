@@ -0,0 +1,376 @@
+use crate::client::LiumApiClient;
+use crate::errors::{ApiError, Result};
+use lium_core::{LiumError, PodInfo};
+use lium_utils::parse_ssh_command;
+use std::time::Duration;
+use tokio::io::AsyncReadExt;
+use tokio::net::TcpStream;
+use tokio::time::{sleep, timeout, Instant};
+
+/// Default time between pod listings while waiting for a pod to become ready.
+pub const DEFAULT_READY_POLL_INTERVAL: Duration = Duration::from_secs(5);
+/// Default time allowed for a single SSH probe to connect and send its banner.
+pub const DEFAULT_SSH_PROBE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Statuses after which a pod will never become ready.
+const TERMINAL_STATUSES: &[&str] = &["failed", "error", "stopped", "terminated", "deleted"];
+
+/// Settings for [`crate::Lium::wait_for_pod_ready_with`].
+///
+/// # Fields
+/// * `timeout` - Overall time allowed for the pod to become reachable
+/// * `poll_interval` - Time between pod listings and SSH probes
+/// * `probe_timeout` - Time allowed for a single SSH probe
+///
+/// # Examples
+/// ```rust
+/// # use lium_api::ReadyOptions;
+/// # use std::time::Duration;
+/// let options = ReadyOptions::new(Duration::from_secs(600))
+///     .with_poll_interval(Duration::from_secs(10));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReadyOptions {
+    pub timeout: Duration,
+    pub poll_interval: Duration,
+    pub probe_timeout: Duration,
+}
+
+impl ReadyOptions {
+    /// Creates options with the given overall timeout and default intervals.
+    pub fn new(timeout: Duration) -> Self {
+        Self {
+            timeout,
+            poll_interval: DEFAULT_READY_POLL_INTERVAL,
+            probe_timeout: DEFAULT_SSH_PROBE_TIMEOUT,
+        }
+    }
+
+    /// Sets the time between pod listings and SSH probes.
+    pub fn with_poll_interval(mut self, poll_interval: Duration) -> Self {
+        self.poll_interval = poll_interval;
+        self
+    }
+
+    /// Sets the time allowed for a single SSH probe.
+    pub fn with_probe_timeout(mut self, probe_timeout: Duration) -> Self {
+        self.probe_timeout = probe_timeout;
+        self
+    }
+}
+
+/// Progress reported while waiting for a pod to become ready.
+///
+/// # Variants
+/// * `Waiting` - The pod is listed but not running with an SSH command yet
+/// * `NotListed` - The pod does not appear in the pod listing yet
+/// * `Probing` - The pod is running; probing its SSH port
+/// * `ProbeFailed` - The SSH port did not answer with an SSH banner yet
+/// * `PollFailed` - Listing pods failed; polling continues until the timeout
+/// * `Ready` - The SSH port answered; the pod is ready
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReadyProgress {
+    Waiting {
+        status: String,
+    },
+    NotListed,
+    Probing {
+        host: String,
+        port: u16,
+    },
+    ProbeFailed {
+        host: String,
+        port: u16,
+        reason: String,
+    },
+    PollFailed {
+        error: String,
+    },
+    Ready,
+}
+
+/// Polls the pod listing until `pod_ref` is running with an SSH command, then probes
+/// its SSH port until it answers.
+///
+/// `pod_ref` matches a pod's id, HUID or name. Progress is reported through `on_progress`
+/// every time it is checked. A failed pod listing is reported as
+/// [`ReadyProgress::PollFailed`] and retried on the next poll; if listings are still
+/// failing at the deadline, the last listing error is returned instead of a timeout.
+pub(crate) async fn wait_for_pod_ready(
+    client: &LiumApiClient,
+    pod_ref: &str,
+    options: ReadyOptions,
+    mut on_progress: impl FnMut(&ReadyProgress),
+) -> Result<PodInfo> {
+    let client = client.refreshing();
+    let deadline = Instant::now() + options.timeout;
+    let mut last_seen = "not listed".to_string();
+
+    loop {
+        let (pods, last_error) = match client.get_pods().await {
+            Ok(pods) => (Some(pods), None),
+            Err(e) => {
+                on_progress(&ReadyProgress::PollFailed {
+                    error: e.to_string(),
+                });
+                (None, Some(e))
+            }
+        };
+        match pods.map(|pods| {
+            pods.into_iter()
+                .find(|pod| pod.id == pod_ref || pod.huid == pod_ref || pod.name == pod_ref)
+        }) {
+            // The listing failed and was reported; try again on the next poll
+            None => {}
+            Some(None) => on_progress(&ReadyProgress::NotListed),
+            Some(Some(pod)) => {
+                let status = pod.status.to_lowercase();
+                if TERMINAL_STATUSES.contains(&status.as_str()) {
+                    return Err(ApiError::Core(LiumError::OperationFailed(format!(
+                        "Pod {} entered status {} while waiting for it to become ready",
+                        pod_ref, pod.status
+                    ))));
+                }
+
+                last_seen = format!("status {}", pod.status);
+                match pod.ssh_cmd.as_deref().filter(|_| status == "running") {
+                    None => on_progress(&ReadyProgress::Waiting {
+                        status: pod.status.clone(),
+                    }),
+                    Some(ssh_cmd) => {
+                        let (host, port, _) = parse_ssh_command(ssh_cmd)?;
+                        on_progress(&ReadyProgress::Probing {
+                            host: host.clone(),
+                            port,
+                        });
+
+                        match probe_ssh(&host, port, options.probe_timeout).await {
+                            Ok(()) => {
+                                on_progress(&ReadyProgress::Ready);
+                                return Ok(pod);
+                            }
+                            Err(reason) => {
+                                last_seen = format!("SSH on {}:{} not answering", host, port);
+                                on_progress(&ReadyProgress::ProbeFailed { host, port, reason });
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        let now = Instant::now();
+        if now >= deadline {
+            if let Some(e) = last_error {
+                return Err(e);
+            }
+            return Err(ApiError::Timeout(format!(
+                "pod {} was not ready after {}s (last seen: {})",
+                pod_ref,
+                options.timeout.as_secs(),
+                last_seen
+            )));
+        }
+        sleep(options.poll_interval.min(deadline - now)).await;
+    }
+}
+
+/// Connects to `host:port` and checks that it answers with an SSH banner.
+///
+/// A bare TCP connect is not enough: container port forwarders often accept
+/// connections before sshd inside the pod is listening.
+///
+/// # Returns
+/// * `Ok(())` - The server sent an `SSH-` identification line
+/// * `Err(String)` - Why the probe failed
+pub async fn probe_ssh(
+    host: &str,
+    port: u16,
+    probe_timeout: Duration,
+) -> std::result::Result<(), String> {
+    let probe = async {
+        let mut stream = TcpStream::connect((host, port))
+            .await
+            .map_err(|e| e.to_string())?;
+
+        let mut banner = [0u8; 4];
+        stream
+            .read_exact(&mut banner)
+            .await
+            .map_err(|e| format!("no SSH banner: {}", e))?;
+
+        if &banner == b"SSH-" {
+            Ok(())
+        } else {
+            Err("server did not answer with an SSH banner".to_string())
+        }
+    };
+
+    timeout(probe_timeout, probe)
+        .await
+        .unwrap_or_else(|_| Err(format!("timed out after {}s", probe_timeout.as_secs())))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{HttpResponse, InMemoryTransport, RetryPolicy};
+    use reqwest::{Method, StatusCode};
+    use serde_json::json;
+    use tokio::io::AsyncWriteExt;
+    use tokio::net::TcpListener;
+
+    fn pod(status: &str, ssh_cmd: Option<String>) -> serde_json::Value {
+        json!([{
+            "id": "pod-1",
+            "pod_name": "train",
+            "status": status,
+            "ssh_connect_cmd": ssh_cmd,
+            "ports_mapping": {},
+            "created_at": "2024-01-01T00:00:00Z",
+            "updated_at": "2024-01-01T00:00:00Z",
+            "executor": {},
+            "template": {},
+        }])
+    }
+
+    fn client(transport: &InMemoryTransport) -> LiumApiClient {
+        LiumApiClient::with_base_url("key".to_string(), "http://fake".to_string())
+            .with_retry_policy(RetryPolicy::disabled())
+            .with_transport(transport.clone())
+    }
+
+    fn fast(timeout: Duration) -> ReadyOptions {
+        ReadyOptions::new(timeout)
+            .with_poll_interval(Duration::from_millis(1))
+            .with_probe_timeout(Duration::from_millis(200))
+    }
+
+    #[tokio::test]
+    async fn test_waits_for_running_pod_and_ssh_banner() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        tokio::spawn(async move {
+            while let Ok((mut socket, _)) = listener.accept().await {
+                let _ = socket.write_all(b"SSH-2.0-OpenSSH_9.6\r\n").await;
+            }
+        });
+
+        let ssh_cmd = format!("ssh root@127.0.0.1 -p {}", port);
+        let transport = InMemoryTransport::new().with_json(
+            Method::GET,
+            "pods",
+            pod("RUNNING", Some(ssh_cmd.clone())),
+        );
+        transport.enqueue(
+            Method::GET,
+            "pods",
+            HttpResponse::json_body(StatusCode::OK, &pod("PENDING", None)),
+        );
+
+        let mut progress = Vec::new();
+        let ready = wait_for_pod_ready(
+            &client(&transport),
+            "train",
+            fast(Duration::from_secs(5)),
+            |p| progress.push(p.clone()),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(ready.ssh_cmd.as_deref(), Some(ssh_cmd.as_str()));
+        assert_eq!(
+            progress,
+            vec![
+                ReadyProgress::Waiting {
+                    status: "PENDING".to_string()
+                },
+                ReadyProgress::Probing {
+                    host: "127.0.0.1".to_string(),
+                    port
+                },
+                ReadyProgress::Ready,
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_times_out_and_fails_fast_on_terminal_status() {
+        let transport =
+            InMemoryTransport::new().with_json(Method::GET, "pods", pod("PENDING", None));
+        let result = wait_for_pod_ready(
+            &client(&transport),
+            "pod-1",
+            fast(Duration::from_millis(20)),
+            |_| {},
+        )
+        .await;
+        assert!(matches!(result, Err(ApiError::Timeout(_))));
+
+        let transport =
+            InMemoryTransport::new().with_json(Method::GET, "pods", pod("FAILED", None));
+        let result = wait_for_pod_ready(
+            &client(&transport),
+            "pod-1",
+            fast(Duration::from_secs(5)),
+            |_| {},
+        )
+        .await;
+        assert!(matches!(result, Err(ApiError::Core(_))));
+        assert_eq!(transport.requests().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_keeps_polling_through_failed_listings() {
+        let transport =
+            InMemoryTransport::new().with_json(Method::GET, "pods", pod("PENDING", None));
+        transport.enqueue(
+            Method::GET,
+            "pods",
+            HttpResponse::json_body(StatusCode::SERVICE_UNAVAILABLE, &json!({"detail": "down"})),
+        );
+
+        let mut progress = Vec::new();
+        let result = wait_for_pod_ready(
+            &client(&transport),
+            "pod-1",
+            fast(Duration::from_millis(50)),
+            |p| progress.push(p.clone()),
+        )
+        .await;
+
+        // The failed listing is reported and the next one is still checked
+        assert!(matches!(progress[0], ReadyProgress::PollFailed { .. }));
+        assert_eq!(
+            progress[1],
+            ReadyProgress::Waiting {
+                status: "PENDING".to_string()
+            }
+        );
+        assert!(matches!(result, Err(ApiError::Timeout(_))));
+    }
+
+    #[tokio::test]
+    async fn test_returns_last_listing_error_at_deadline() {
+        let transport = InMemoryTransport::new().with_response(
+            Method::GET,
+            "pods",
+            HttpResponse::json_body(StatusCode::SERVICE_UNAVAILABLE, &json!({"detail": "down"})),
+        );
+
+        let mut polls = 0;
+        let result = wait_for_pod_ready(
+            &client(&transport),
+            "pod-1",
+            fast(Duration::from_millis(20)),
+            |p| {
+                assert!(matches!(p, ReadyProgress::PollFailed { .. }));
+                polls += 1;
+            },
+        )
+        .await;
+
+        assert!(polls > 1);
+        assert!(matches!(result, Err(ApiError::Http(_))));
+    }
+}
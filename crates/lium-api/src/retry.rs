@@ -0,0 +1,227 @@
+use crate::errors::HttpError;
+use rand::Rng;
+use reqwest::header::{HeaderMap, RETRY_AFTER};
+use reqwest::StatusCode;
+use std::time::Duration;
+
+/// Retry policy for transient API failures.
+///
+/// Controls how many times the client re-sends a request after a transient failure
+/// (rate limiting, service unavailability, timeouts or connection errors) and how long
+/// it waits between attempts. Delays grow exponentially from `base_delay`, are capped at
+/// `max_delay`, and have random jitter applied so concurrent clients don't retry in lockstep.
+/// A `Retry-After` header sent by the server always takes precedence over the computed delay.
+///
+/// # Fields
+/// * `max_attempts` - Total number of attempts, including the first one (1 disables retries)
+/// * `base_delay` - Delay before the first retry
+/// * `max_delay` - Upper bound for any single delay, including `Retry-After` values
+/// * `jitter` - Fraction of the delay (0.0 - 1.0) that is randomized
+/// * `retry_posts` - Whether non-idempotent POST requests (e.g. `rent_pod`) are retried
+///
+/// # Examples
+/// ```rust
+/// use lium_api::{LiumApiClient, RetryPolicy};
+/// use std::time::Duration;
+///
+/// let policy = RetryPolicy::default()
+///     .with_max_attempts(5)
+///     .with_base_delay(Duration::from_millis(250))
+///     .with_retry_posts(true);
+/// let client = LiumApiClient::from_api_key("your-api-key".to_string()).with_retry_policy(policy);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct RetryPolicy {
+    pub max_attempts: u32,
+    pub base_delay: Duration,
+    pub max_delay: Duration,
+    pub jitter: f64,
+    pub retry_posts: bool,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            base_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(30),
+            jitter: 0.2,
+            retry_posts: false,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Creates a policy that never retries.
+    pub fn disabled() -> Self {
+        Self {
+            max_attempts: 1,
+            ..Self::default()
+        }
+    }
+
+    /// Sets the total number of attempts (values below 1 are treated as 1).
+    pub fn with_max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = max_attempts.max(1);
+        self
+    }
+
+    /// Sets the delay before the first retry.
+    pub fn with_base_delay(mut self, base_delay: Duration) -> Self {
+        self.base_delay = base_delay;
+        self
+    }
+
+    /// Sets the upper bound for a single delay.
+    pub fn with_max_delay(mut self, max_delay: Duration) -> Self {
+        self.max_delay = max_delay;
+        self
+    }
+
+    /// Sets the jitter fraction, clamped to 0.0 - 1.0.
+    pub fn with_jitter(mut self, jitter: f64) -> Self {
+        self.jitter = jitter.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Enables or disables retrying POST requests.
+    pub fn with_retry_posts(mut self, retry_posts: bool) -> Self {
+        self.retry_posts = retry_posts;
+        self
+    }
+
+    /// Returns true if a response with this status should be retried.
+    pub fn is_retryable_status(status: StatusCode) -> bool {
+        matches!(
+            status,
+            StatusCode::TOO_MANY_REQUESTS
+                | StatusCode::SERVICE_UNAVAILABLE
+                | StatusCode::BAD_GATEWAY
+                | StatusCode::GATEWAY_TIMEOUT
+                | StatusCode::REQUEST_TIMEOUT
+        )
+    }
+
+    /// Returns true if a transport-level error should be retried.
+    pub fn is_retryable_error(error: &HttpError) -> bool {
+        match error {
+            HttpError::Timeout | HttpError::ServiceUnavailable | HttpError::RateLimited => true,
+            HttpError::Request(e) => e.is_timeout() || e.is_connect(),
+            _ => false,
+        }
+    }
+
+    /// Exponential backoff delay (without jitter) before retry number `attempt` (1-based).
+    pub fn backoff_delay(&self, attempt: u32) -> Duration {
+        let exponent = attempt.saturating_sub(1).min(16);
+        self.base_delay
+            .saturating_mul(1u32 << exponent)
+            .min(self.max_delay)
+    }
+
+    /// Delay before retry number `attempt`, honoring a server-provided `Retry-After`.
+    pub fn delay_for(&self, attempt: u32, retry_after: Option<Duration>) -> Duration {
+        if let Some(retry_after) = retry_after {
+            return retry_after.min(self.max_delay);
+        }
+
+        let delay = self.backoff_delay(attempt);
+        if self.jitter <= 0.0 {
+            return delay;
+        }
+
+        // Scale the delay by a random factor in [1 - jitter, 1 + jitter]
+        let factor = rand::thread_rng().gen_range((1.0 - self.jitter)..=(1.0 + self.jitter));
+        delay.mul_f64(factor).min(self.max_delay)
+    }
+}
+
+/// Parses the `Retry-After` header, which is either a number of seconds or an HTTP date.
+pub fn parse_retry_after(headers: &HeaderMap) -> Option<Duration> {
+    let value = headers.get(RETRY_AFTER)?.to_str().ok()?.trim();
+
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+
+    let date = chrono::DateTime::parse_from_rfc2822(value).ok()?;
+    let remaining = date.with_timezone(&chrono::Utc) - chrono::Utc::now();
+    Some(remaining.to_std().unwrap_or(Duration::ZERO))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::header::HeaderValue;
+
+    #[test]
+    fn test_backoff_is_exponential_and_capped() {
+        let policy = RetryPolicy::default()
+            .with_base_delay(Duration::from_millis(100))
+            .with_max_delay(Duration::from_millis(1000))
+            .with_jitter(0.0);
+
+        assert_eq!(policy.delay_for(1, None), Duration::from_millis(100));
+        assert_eq!(policy.delay_for(2, None), Duration::from_millis(200));
+        assert_eq!(policy.delay_for(3, None), Duration::from_millis(400));
+        assert_eq!(policy.delay_for(5, None), Duration::from_millis(1000));
+        assert_eq!(policy.delay_for(40, None), Duration::from_millis(1000));
+    }
+
+    #[test]
+    fn test_jitter_stays_within_bounds() {
+        let policy = RetryPolicy::default()
+            .with_base_delay(Duration::from_millis(1000))
+            .with_jitter(0.5);
+
+        for _ in 0..100 {
+            let delay = policy.delay_for(1, None);
+            assert!(delay >= Duration::from_millis(500));
+            assert!(delay <= Duration::from_millis(1500));
+        }
+    }
+
+    #[test]
+    fn test_retry_after_takes_precedence() {
+        let policy = RetryPolicy::default().with_max_delay(Duration::from_secs(10));
+
+        assert_eq!(
+            policy.delay_for(1, Some(Duration::from_secs(3))),
+            Duration::from_secs(3)
+        );
+        assert_eq!(
+            policy.delay_for(1, Some(Duration::from_secs(120))),
+            Duration::from_secs(10)
+        );
+    }
+
+    #[test]
+    fn test_parse_retry_after() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(parse_retry_after(&headers), None);
+
+        headers.insert(RETRY_AFTER, HeaderValue::from_static("7"));
+        assert_eq!(parse_retry_after(&headers), Some(Duration::from_secs(7)));
+
+        headers.insert(
+            RETRY_AFTER,
+            HeaderValue::from_static("Wed, 21 Oct 2015 07:28:00 GMT"),
+        );
+        assert_eq!(parse_retry_after(&headers), Some(Duration::ZERO));
+
+        headers.insert(RETRY_AFTER, HeaderValue::from_static("soon"));
+        assert_eq!(parse_retry_after(&headers), None);
+    }
+
+    #[test]
+    fn test_retryable_statuses() {
+        assert!(RetryPolicy::is_retryable_status(
+            StatusCode::TOO_MANY_REQUESTS
+        ));
+        assert!(RetryPolicy::is_retryable_status(
+            StatusCode::SERVICE_UNAVAILABLE
+        ));
+        assert!(!RetryPolicy::is_retryable_status(StatusCode::UNAUTHORIZED));
+        assert!(!RetryPolicy::is_retryable_status(StatusCode::NOT_FOUND));
+    }
+}
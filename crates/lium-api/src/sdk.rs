@@ -1,6 +1,13 @@
 use crate::client::LiumApiClient;
 use crate::errors::{ApiError, Result};
-use lium_core::{ExecutorInfo, PodInfo, TemplateInfo};
+use crate::ready::{self, ReadyOptions, ReadyProgress};
+use crate::watch::{self, PodEvent};
+use futures::Stream;
+use lium_core::{
+    ExecutorInfo, FundingWallet, ImageRegistration, PodInfo, RentPodRequest, RentPodResponse,
+    TemplateInfo, TemplateUpdate, UnrentPodResponse, UserProfile,
+};
+use std::time::Duration;
 
 /// Main SDK struct for Lium
 ///
@@ -9,25 +16,23 @@ use lium_core::{ExecutorInfo, PodInfo, TemplateInfo};
 /// and other platform resources.
 ///
 /// # Examples
-/// ```rust
-/// use lium_api::Lium;
+/// ```rust,no_run
+/// use lium_api::{ApiError, Lium};
+/// use lium_core::RentPodRequest;
 ///
 /// #[tokio::main]
 /// async fn main() -> Result<(), ApiError> {
 ///     // Create a new Lium instance
 ///     let lium = Lium::new("your-api-key".to_string());
-///     
+///
 ///     // List available executors
 ///     let executors = lium.list_executors(None).await?;
-///     
+///
 ///     // Start a pod
-///     let pod = lium.start_pod(
-///         "exec-123",
-///         "my-pod",
-///         "template-456",
-///         vec!["ssh-rsa AAAAB3NzaC1yc2EAAAADA...".to_string()]
-///     ).await?;
-///     
+///     let request = RentPodRequest::new("my-pod", "template-456")
+///         .with_ssh_keys(vec!["ssh-rsa AAAAB3NzaC1yc2EAAAADA...".to_string()]);
+///     let pod = lium.start_pod("exec-123", &request).await?;
+///
 ///     Ok(())
 /// }
 /// ```
@@ -47,6 +52,7 @@ impl Lium {
     ///
     /// # Examples
     /// ```rust
+    /// # use lium_api::Lium;
     /// let lium = Lium::new("your-api-key".to_string());
     /// ```
     pub fn new(api_key: String) -> Self {
@@ -61,8 +67,10 @@ impl Lium {
     /// * `Err(ApiError)` - An error if the environment variable is not set
     ///
     /// # Examples
-    /// ```rust
+    /// ```rust,no_run
+    /// # use lium_api::Lium;
     /// let lium = Lium::from_env()?;
+    /// # Ok::<(), lium_api::ApiError>(())
     /// ```
     pub fn from_env() -> Result<Self> {
         let api_key = std::env::var("LIUM_API_KEY").map_err(|_| {
@@ -71,6 +79,24 @@ impl Lium {
         Ok(Self::new(api_key))
     }
 
+    /// Creates a new Lium instance from an existing API client.
+    ///
+    /// Useful when the client needs a custom base URL, retry policy or transport.
+    ///
+    /// # Arguments
+    /// * `api_client` - The API client to use
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use lium_api::{InMemoryTransport, Lium, LiumApiClient};
+    /// let client = LiumApiClient::from_api_key("your-api-key".to_string())
+    ///     .with_transport(InMemoryTransport::new());
+    /// let lium = Lium::from_client(client);
+    /// ```
+    pub fn from_client(api_client: LiumApiClient) -> Self {
+        Self { api_client }
+    }
+
     /// Lists all available executors, optionally filtered by GPU type.
     ///
     /// # Arguments
@@ -82,11 +108,15 @@ impl Lium {
     ///
     /// # Examples
     /// ```rust
+    /// # use lium_api::{Lium, Result};
+    /// # async fn example(lium: Lium) -> Result<()> {
     /// // List all executors
     /// let all_executors = lium.list_executors(None).await?;
     ///
     /// // List only A100 executors
     /// let a100_executors = lium.list_executors(Some("a100".to_string())).await?;
+    /// # Ok(())
+    /// # }
     /// ```
     pub async fn list_executors(&self, gpu_type: Option<String>) -> Result<Vec<ExecutorInfo>> {
         let mut executors = self.api_client.get_executors().await?;
@@ -106,15 +136,120 @@ impl Lium {
     ///
     /// # Examples
     /// ```rust
+    /// # use lium_api::{Lium, Result};
+    /// # async fn example(lium: Lium) -> Result<()> {
     /// let pods = lium.list_pods().await?;
     /// for pod in pods {
     ///     println!("Pod: {} (HUID: {})", pod.name, pod.huid);
     /// }
+    /// # Ok(())
+    /// # }
     /// ```
     pub async fn list_pods(&self) -> Result<Vec<PodInfo>> {
         self.api_client.get_pods().await
     }
 
+    /// Waits until a pod is running and reachable over SSH.
+    ///
+    /// Polls the pod listing until the pod's status is running and it has an SSH command,
+    /// then probes the SSH port until it answers with an SSH banner. Use this after
+    /// [`Lium::start_pod`], which returns as soon as the rental is accepted.
+    ///
+    /// # Arguments
+    /// * `pod_ref` - The pod's id, HUID or name
+    /// * `timeout` - How long to wait before giving up
+    ///
+    /// # Returns
+    /// * `Ok(PodInfo)` - The pod, as listed when it became ready
+    /// * `Err(ApiError::Timeout)` - The pod was not ready in time
+    /// * `Err(ApiError)` - The pod failed, or listing pods was still failing at the deadline
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use lium_api::{Lium, Result};
+    /// # use std::time::Duration;
+    /// # async fn example(lium: Lium) -> Result<()> {
+    /// # let request = lium_core::RentPodRequest::new("my-pod", "template-456");
+    /// let rented = lium.start_pod("exec-123", &request).await?;
+    /// let pod = lium
+    ///     .wait_for_pod_ready(&rented.id, Duration::from_secs(300))
+    ///     .await?;
+    /// println!("Connect with: {}", pod.ssh_cmd.unwrap_or_default());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn wait_for_pod_ready(&self, pod_ref: &str, timeout: Duration) -> Result<PodInfo> {
+        self.wait_for_pod_ready_with(pod_ref, ReadyOptions::new(timeout), |_| {})
+            .await
+    }
+
+    /// Waits until a pod is ready, with custom intervals and progress reporting.
+    ///
+    /// Behaves like [`Lium::wait_for_pod_ready`], calling `on_progress` after every
+    /// pod listing and SSH probe so callers can show what they are waiting for.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use lium_api::{Lium, Result};
+    /// # use lium_api::ReadyOptions;
+    /// # async fn example(lium: Lium) -> Result<()> {
+    /// # let timeout = std::time::Duration::from_secs(300);
+    /// let pod = lium
+    ///     .wait_for_pod_ready_with("brave-cat-42", ReadyOptions::new(timeout), |progress| {
+    ///         println!("{:?}", progress)
+    ///     })
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn wait_for_pod_ready_with(
+        &self,
+        pod_ref: &str,
+        options: ReadyOptions,
+        on_progress: impl FnMut(&ReadyProgress),
+    ) -> Result<PodInfo> {
+        ready::wait_for_pod_ready(&self.api_client, pod_ref, options, on_progress).await
+    }
+
+    /// Watches the account's pods, yielding an event whenever something changes.
+    ///
+    /// Pods are listed every `interval` and each listing is diffed against the previous
+    /// one by pod id. The first listing reports every existing pod as `PodAdded`. Polling
+    /// bypasses the response cache, and a failed poll yields an `Err` without ending the
+    /// stream. The stream never ends on its own; drop it to stop watching.
+    ///
+    /// # Arguments
+    /// * `interval` - Time to wait between listings
+    ///
+    /// # Returns
+    /// A stream of [`PodEvent`]s (`PodAdded`, `PodRemoved`, `StatusChanged`, `SshReady`)
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use lium_api::{Lium, Result};
+    /// # use lium_api::PodEvent;
+    /// # use std::time::Duration;
+    /// # async fn example(lium: Lium) -> Result<()> {
+    /// use futures::StreamExt;
+    ///
+    /// let mut events = Box::pin(lium.watch_pods(Duration::from_secs(5)));
+    /// while let Some(event) = events.next().await {
+    ///     match event? {
+    ///         PodEvent::SshReady(pod) => println!("{} is ready: {:?}", pod.name, pod.ssh_cmd),
+    ///         PodEvent::PodRemoved(pod) => println!("{} is gone", pod.name),
+    ///         _ => {}
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn watch_pods(
+        &self,
+        interval: Duration,
+    ) -> impl Stream<Item = Result<PodEvent>> + Send + 'static {
+        watch::watch_pods(&self.api_client, interval)
+    }
+
     /// Retrieves all available pod templates.
     ///
     /// # Returns
@@ -123,46 +258,106 @@ impl Lium {
     ///
     /// # Examples
     /// ```rust
+    /// # use lium_api::{Lium, Result};
+    /// # async fn example(lium: Lium) -> Result<()> {
     /// let templates = lium.get_templates().await?;
     /// for template in templates {
     ///     println!("Template: {} (ID: {})", template.name, template.id);
     /// }
+    /// # Ok(())
+    /// # }
     /// ```
     pub async fn get_templates(&self) -> Result<Vec<TemplateInfo>> {
         self.api_client.get_templates().await
     }
 
-    /// Starts a new pod on the specified executor using the given template.
+    /// Retrieves a single template, including its verification status and image digest.
+    ///
+    /// # Arguments
+    /// * `template_id` - The ID of the template to fetch
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use lium_api::{Lium, Result};
+    /// # async fn example(lium: Lium) -> Result<()> {
+    /// let template = lium.get_template("tmpl-123").await?;
+    /// println!("Digest: {:?}", template.docker_image_digest);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn get_template(&self, template_id: &str) -> Result<TemplateInfo> {
+        self.api_client.get_template(template_id).await
+    }
+
+    /// Updates a template's name and/or description.
+    ///
+    /// # Arguments
+    /// * `template_id` - The ID of the template to update
+    /// * `update` - The fields to change
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use lium_api::{Lium, Result};
+    /// # use lium_core::TemplateUpdate;
+    /// # async fn example(lium: Lium) -> Result<()> {
+    /// let update = TemplateUpdate::new().with_description("Nightly build");
+    /// let template = lium.update_template("tmpl-123", &update).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn update_template(
+        &self,
+        template_id: &str,
+        update: &TemplateUpdate,
+    ) -> Result<TemplateInfo> {
+        self.api_client.update_template(template_id, update).await
+    }
+
+    /// Deletes a template.
+    ///
+    /// # Arguments
+    /// * `template_id` - The ID of the template to delete
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use lium_api::{Lium, Result};
+    /// # async fn example(lium: Lium) -> Result<()> {
+    /// lium.delete_template("tmpl-123").await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn delete_template(&self, template_id: &str) -> Result<()> {
+        self.api_client.delete_template(template_id).await
+    }
+
+    /// Starts a new pod on the specified executor.
     ///
     /// # Arguments
     /// * `executor_id` - The ID of the executor to start the pod on
-    /// * `pod_name` - A unique name for the pod
-    /// * `template_id` - The ID of the template to use
-    /// * `ssh_public_keys` - A list of SSH public keys to authorize for pod access
+    /// * `request` - Pod name, template, SSH public keys and pod options (env, ports, ...)
     ///
     /// # Returns
-    /// * `Ok(Value)` - The API response containing pod details
+    /// * `Ok(RentPodResponse)` - The created pod's details
     /// * `Err(ApiError)` - An error if the request fails
     ///
     /// # Examples
     /// ```rust
-    /// let pod = lium.start_pod(
-    ///     "exec-123",
-    ///     "my-pod",
-    ///     "template-456",
-    ///     vec!["ssh-rsa AAAAB3NzaC1yc2EAAAADA...".to_string()]
-    /// ).await?;
+    /// # use lium_api::{Lium, Result};
+    /// # use lium_core::{parse_port_mappings, RentPodRequest};
+    /// # async fn example(lium: Lium) -> Result<()> {
+    /// let request = RentPodRequest::new("my-pod", "template-456")
+    ///     .with_ssh_keys(vec!["ssh-rsa AAAAB3NzaC1yc2EAAAADA...".to_string()])
+    ///     .with_ports(parse_port_mappings("8888:8888")?);
+    /// let pod = lium.start_pod("exec-123", &request).await?;
+    /// # Ok(())
+    /// # }
     /// ```
     pub async fn start_pod(
         &self,
         executor_id: &str,
-        pod_name: &str,
-        template_id: &str,
-        ssh_public_keys: Vec<String>,
-    ) -> Result<serde_json::Value> {
-        self.api_client
-            .rent_pod(executor_id, pod_name, template_id, ssh_public_keys)
-            .await
+        request: &RentPodRequest,
+    ) -> Result<RentPodResponse> {
+        self.api_client.rent_pod(executor_id, request).await
     }
 
     /// Stops a pod running on the specified executor.
@@ -171,14 +366,18 @@ impl Lium {
     /// * `executor_id` - The ID of the executor running the pod to stop
     ///
     /// # Returns
-    /// * `Ok(Value)` - The API response confirming pod termination
+    /// * `Ok(UnrentPodResponse)` - The API response confirming pod termination
     /// * `Err(ApiError)` - An error if the request fails
     ///
     /// # Examples
     /// ```rust
+    /// # use lium_api::{Lium, Result};
+    /// # async fn example(lium: Lium) -> Result<()> {
     /// lium.stop_pod("exec-123").await?;
+    /// # Ok(())
+    /// # }
     /// ```
-    pub async fn stop_pod(&self, executor_id: &str) -> Result<serde_json::Value> {
+    pub async fn stop_pod(&self, executor_id: &str) -> Result<UnrentPodResponse> {
         self.api_client.unrent_pod(executor_id).await
     }
 
@@ -194,9 +393,13 @@ impl Lium {
     ///
     /// # Examples
     /// ```rust
+    /// # use lium_api::{Lium, Result};
+    /// # async fn example(lium: Lium) -> Result<()> {
     /// if let Some(pod) = lium.get_pod_by_name_or_huid("my-pod").await? {
     ///     println!("Found pod: {}", pod.name);
     /// }
+    /// # Ok(())
+    /// # }
     /// ```
     pub async fn get_pod_by_name_or_huid(&self, name_or_huid: &str) -> Result<Option<PodInfo>> {
         let pods = self.list_pods().await?;
@@ -218,10 +421,14 @@ impl Lium {
     ///
     /// # Examples
     /// ```rust
+    /// # use lium_api::{Lium, Result};
+    /// # async fn example(lium: Lium) -> Result<()> {
     /// if let Some(executor) = lium.get_executor_by_huid("exec-123").await? {
-    ///     println!("Found executor: {}", executor.name);
+    ///     println!("Found executor: {}", executor.machine_name);
     /// }
-    /// Get executor by HUID
+    /// # Ok(())
+    /// # }
+    /// ```
     pub async fn get_executor_by_huid(&self, huid: &str) -> Result<Option<ExecutorInfo>> {
         let executors = self.list_executors(None).await?;
 
@@ -234,12 +441,12 @@ impl Lium {
     }
 
     /// Get funding wallets
-    pub async fn get_funding_wallets(&self) -> Result<serde_json::Value> {
+    pub async fn get_funding_wallets(&self) -> Result<Vec<FundingWallet>> {
         self.api_client.get_funding_wallets().await
     }
 
     /// Get user info
-    pub async fn get_user_info(&self) -> Result<serde_json::Value> {
+    pub async fn get_user_info(&self) -> Result<UserProfile> {
         self.api_client.get_users_me().await
     }
 
@@ -249,7 +456,7 @@ impl Lium {
         image_name: &str,
         digest: &str,
         tag: &str,
-    ) -> Result<serde_json::Value> {
+    ) -> Result<ImageRegistration> {
         self.api_client.post_image(image_name, digest, tag).await
     }
 }
@@ -0,0 +1,238 @@
+use super::{HttpRequest, HttpResponse, HttpTransport, TransportFuture};
+use lium_core::{ApiExecutorResponse, ApiPodResponse, ApiTemplateResponse};
+use reqwest::{Method, StatusCode};
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+
+type RouteKey = (Method, String);
+
+#[derive(Debug, Default)]
+struct InMemoryState {
+    routes: HashMap<RouteKey, HttpResponse>,
+    queued: HashMap<RouteKey, VecDeque<HttpResponse>>,
+    requests: Vec<HttpRequest>,
+}
+
+/// In-memory transport that serves canned responses and records every request.
+///
+/// Responses are registered per method and endpoint (relative to the API base URL).
+/// One-shot responses queued with [`InMemoryTransport::enqueue`] are served first, which
+/// makes it easy to simulate transient failures followed by success. Requests to
+/// unregistered endpoints get a 404. Clones share state, so a test can keep one handle
+/// to inspect [`InMemoryTransport::requests`] after passing another to the client.
+///
+/// # Examples
+/// ```rust
+/// use lium_api::{InMemoryTransport, LiumApiClient};
+/// # use lium_core::ApiPodResponse;
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() -> lium_api::Result<()> {
+/// # let pods: Vec<ApiPodResponse> = Vec::new();
+///
+/// let transport = InMemoryTransport::new().with_pods(&pods);
+/// let client = LiumApiClient::from_api_key("test-key".to_string())
+///     .with_transport(transport.clone());
+///
+/// let pods = client.get_pods().await?;
+/// assert_eq!(transport.requests().len(), 1);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct InMemoryTransport {
+    state: Arc<Mutex<InMemoryState>>,
+}
+
+impl InMemoryTransport {
+    /// Creates a transport with no routes.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Serves the given executors from `GET executors`.
+    pub fn with_executors(self, executors: &[ApiExecutorResponse]) -> Self {
+        self.with_serialized(Method::GET, "executors", executors)
+    }
+
+    /// Serves the given pods from `GET pods`.
+    pub fn with_pods(self, pods: &[ApiPodResponse]) -> Self {
+        self.with_serialized(Method::GET, "pods", pods)
+    }
+
+    /// Serves the given templates from `GET templates`.
+    pub fn with_templates(self, templates: &[ApiTemplateResponse]) -> Self {
+        self.with_serialized(Method::GET, "templates", templates)
+    }
+
+    /// Serves a 200 response with the given JSON body for `method endpoint`.
+    pub fn with_json(self, method: Method, endpoint: &str, body: Value) -> Self {
+        self.with_response(
+            method,
+            endpoint,
+            HttpResponse::json_body(StatusCode::OK, &body),
+        )
+    }
+
+    /// Serves the given response for every `method endpoint` request.
+    pub fn with_response(self, method: Method, endpoint: &str, response: HttpResponse) -> Self {
+        self.lock()
+            .routes
+            .insert((method, normalize(endpoint)), response);
+        self
+    }
+
+    /// Queues a one-shot response for `method endpoint`, served before any registered route.
+    pub fn enqueue(&self, method: Method, endpoint: &str, response: HttpResponse) {
+        self.lock()
+            .queued
+            .entry((method, normalize(endpoint)))
+            .or_default()
+            .push_back(response);
+    }
+
+    /// Returns all requests received so far, in order.
+    pub fn requests(&self) -> Vec<HttpRequest> {
+        self.lock().requests.clone()
+    }
+
+    /// Returns `(method, endpoint)` for every request received so far, in order.
+    pub fn request_log(&self) -> Vec<(Method, String)> {
+        self.lock()
+            .requests
+            .iter()
+            .map(|request| (request.method.clone(), request.endpoint.clone()))
+            .collect()
+    }
+
+    /// Forgets all recorded requests.
+    pub fn clear_requests(&self) {
+        self.lock().requests.clear();
+    }
+
+    fn with_serialized<T: Serialize>(self, method: Method, endpoint: &str, items: &[T]) -> Self {
+        let body = serde_json::to_value(items).unwrap_or(Value::Array(Vec::new()));
+        self.with_json(method, endpoint, body)
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, InMemoryState> {
+        // A poisoned lock only means another test thread panicked; the data is still usable
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl HttpTransport for InMemoryTransport {
+    fn send(&self, request: HttpRequest) -> TransportFuture<'_> {
+        let response = {
+            let mut state = self.lock();
+            let key = (request.method.clone(), normalize(&request.endpoint));
+            state.requests.push(request);
+
+            state
+                .queued
+                .get_mut(&key)
+                .and_then(|queue| queue.pop_front())
+                .or_else(|| state.routes.get(&key).cloned())
+                .unwrap_or_else(|| {
+                    HttpResponse::json_body(
+                        StatusCode::NOT_FOUND,
+                        &serde_json::json!({ "detail": "Not Found" }),
+                    )
+                })
+        };
+
+        Box::pin(async move { Ok(response) })
+    }
+}
+
+fn normalize(endpoint: &str) -> String {
+    endpoint.trim_matches('/').to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{LiumApiClient, RetryPolicy};
+    use std::time::Duration;
+
+    fn test_client(transport: &InMemoryTransport) -> LiumApiClient {
+        LiumApiClient::with_base_url("test-key-1234".to_string(), "http://fake".to_string())
+            .with_retry_policy(
+                RetryPolicy::default()
+                    .with_base_delay(Duration::from_millis(1))
+                    .with_jitter(0.0),
+            )
+            .with_transport(transport.clone())
+    }
+
+    #[tokio::test]
+    async fn test_serves_routes_and_records_requests() {
+        let transport = InMemoryTransport::new().with_json(
+            Method::GET,
+            "users/me",
+            serde_json::json!({"id": "user-1", "app_id": "app-1"}),
+        );
+        let client = test_client(&transport);
+
+        assert_eq!(client.get_app_id().await.unwrap(), "app-1");
+
+        let requests = transport.requests();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].method, Method::GET);
+        assert_eq!(requests[0].url, "http://fake/users/me");
+        assert_eq!(requests[0].header("x-api-key"), Some("test-key-1234"));
+    }
+
+    #[tokio::test]
+    async fn test_unknown_route_is_not_found() {
+        let transport = InMemoryTransport::new();
+        let client = test_client(&transport);
+
+        assert!(client.get_pods().await.is_err());
+        assert_eq!(
+            transport.request_log(),
+            vec![(Method::GET, "pods".to_string())]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_get_retries_transient_failures() {
+        let transport = InMemoryTransport::new().with_pods(&[]);
+        transport.enqueue(
+            Method::GET,
+            "pods",
+            HttpResponse::new(StatusCode::SERVICE_UNAVAILABLE, ""),
+        );
+        transport.enqueue(
+            Method::GET,
+            "pods",
+            HttpResponse::new(StatusCode::TOO_MANY_REQUESTS, ""),
+        );
+        let client = test_client(&transport);
+
+        assert!(client.get_pods().await.unwrap().is_empty());
+        assert_eq!(transport.requests().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_post_is_not_retried_by_default() {
+        let transport = InMemoryTransport::new().with_json(
+            Method::POST,
+            "executors/exec-1/rent",
+            serde_json::json!({"id": "pod-1"}),
+        );
+        transport.enqueue(
+            Method::POST,
+            "executors/exec-1/rent",
+            HttpResponse::new(StatusCode::SERVICE_UNAVAILABLE, ""),
+        );
+        let client = test_client(&transport);
+
+        assert!(client
+            .rent_pod("exec-1", &lium_core::RentPodRequest::new("pod", "template"))
+            .await
+            .is_err());
+        assert_eq!(transport.requests().len(), 1);
+    }
+}
@@ -0,0 +1,128 @@
+//! HTTP transport abstraction used by `LiumApiClient`.
+//!
+//! The client builds transport-agnostic [`HttpRequest`]s and hands them to an
+//! [`HttpTransport`]. The default [`ReqwestTransport`] sends them over the network, while
+//! [`InMemoryTransport`] serves canned responses and records every request, which makes
+//! it possible to test code built on the client without a real Celium backend.
+
+mod memory;
+mod reqwest;
+
+pub use self::memory::InMemoryTransport;
+pub use self::reqwest::{
+    HttpSettings, ReqwestTransport, DEFAULT_CONNECT_TIMEOUT, DEFAULT_REQUEST_TIMEOUT,
+};
+
+use crate::errors::{HttpError, Result};
+use ::reqwest::header::HeaderMap;
+use ::reqwest::{Method, StatusCode};
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use std::fmt::Debug;
+use std::future::Future;
+use std::pin::Pin;
+
+/// Future returned by [`HttpTransport::send`].
+pub type TransportFuture<'a> =
+    Pin<Box<dyn Future<Output = std::result::Result<HttpResponse, HttpError>> + Send + 'a>>;
+
+/// A request to be sent by an [`HttpTransport`].
+///
+/// # Fields
+/// * `method` - The HTTP method
+/// * `url` - The absolute request URL
+/// * `endpoint` - The endpoint relative to the API base URL (e.g. "executors/123/rent")
+/// * `headers` - Request headers as name/value pairs
+/// * `body` - Optional JSON body
+#[derive(Debug, Clone, PartialEq)]
+pub struct HttpRequest {
+    pub method: Method,
+    pub url: String,
+    pub endpoint: String,
+    pub headers: Vec<(String, String)>,
+    pub body: Option<Value>,
+}
+
+impl HttpRequest {
+    /// Returns the value of the first header with the given (case-insensitive) name.
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+/// A fully buffered HTTP response returned by an [`HttpTransport`].
+///
+/// # Fields
+/// * `status` - The HTTP status code
+/// * `headers` - Response headers
+/// * `body` - Raw response body
+#[derive(Debug, Clone)]
+pub struct HttpResponse {
+    pub status: StatusCode,
+    pub headers: HeaderMap,
+    pub body: Vec<u8>,
+}
+
+impl HttpResponse {
+    /// Creates a response with the given status and body and no headers.
+    pub fn new(status: StatusCode, body: impl Into<Vec<u8>>) -> Self {
+        Self {
+            status,
+            headers: HeaderMap::new(),
+            body: body.into(),
+        }
+    }
+
+    /// Creates a response with the given status and a serialized JSON body.
+    pub fn json_body(status: StatusCode, value: &Value) -> Self {
+        Self::new(status, value.to_string())
+    }
+
+    /// Returns the HTTP status code.
+    pub fn status(&self) -> StatusCode {
+        self.status
+    }
+
+    /// Returns the response headers.
+    pub fn headers(&self) -> &HeaderMap {
+        &self.headers
+    }
+
+    /// Returns the body decoded as (lossy) UTF-8 text.
+    pub fn text(&self) -> String {
+        String::from_utf8_lossy(&self.body).into_owned()
+    }
+
+    /// Deserializes the body as JSON.
+    pub fn json<T: DeserializeOwned>(&self) -> Result<T> {
+        Ok(serde_json::from_slice(&self.body)?)
+    }
+}
+
+/// Sends HTTP requests on behalf of `LiumApiClient`.
+///
+/// Implementations only move bytes: authentication headers, retries and status handling
+/// are done by the client. Transport-level failures are reported as `HttpError`, with
+/// `HttpError::Timeout` or `HttpError::Request` for errors the retry policy may retry.
+///
+/// # Examples
+/// ```rust
+/// use lium_api::{HttpRequest, HttpResponse, HttpTransport, TransportFuture};
+/// use reqwest::StatusCode;
+///
+/// #[derive(Debug)]
+/// struct AlwaysOk;
+///
+/// impl HttpTransport for AlwaysOk {
+///     fn send(&self, _request: HttpRequest) -> TransportFuture<'_> {
+///         Box::pin(async { Ok(HttpResponse::new(StatusCode::OK, "{}")) })
+///     }
+/// }
+/// ```
+pub trait HttpTransport: Debug + Send + Sync {
+    /// Sends a request and returns the buffered response.
+    fn send(&self, request: HttpRequest) -> TransportFuture<'_>;
+}
@@ -0,0 +1,232 @@
+use crate::client::LiumApiClient;
+use crate::errors::Result;
+use futures::stream::{self, Stream, StreamExt};
+use lium_core::PodInfo;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::time::Duration;
+
+/// A change observed between two successive pod listings.
+///
+/// # Variants
+/// * `PodAdded` - A pod appeared (every pod in the first listing is reported as added)
+/// * `PodRemoved` - A pod disappeared; carries its last known state
+/// * `StatusChanged` - A pod's status changed, e.g. from `PENDING` to `RUNNING`
+/// * `SshReady` - A pod got an SSH command and can be connected to
+///
+/// Events serialize with an `event` tag, e.g. `{"event": "status_changed", "pod": ...}`.
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum PodEvent {
+    PodAdded(PodInfo),
+    PodRemoved(PodInfo),
+    StatusChanged {
+        pod: PodInfo,
+        from: String,
+        to: String,
+    },
+    SshReady(PodInfo),
+}
+
+impl PodEvent {
+    /// Returns the pod the event is about.
+    pub fn pod(&self) -> &PodInfo {
+        match self {
+            PodEvent::PodAdded(pod)
+            | PodEvent::PodRemoved(pod)
+            | PodEvent::SshReady(pod)
+            | PodEvent::StatusChanged { pod, .. } => pod,
+        }
+    }
+}
+
+/// Diffs successive pod listings, keyed by pod id.
+///
+/// This is the state machine behind [`crate::Lium::watch_pods`]; it is public so callers
+/// that already poll `get_pods()` themselves can reuse the diffing.
+///
+/// # Examples
+/// ```rust
+/// # use lium_api::{LiumApiClient, PodDiffer, Result};
+/// # async fn example(client: LiumApiClient) -> Result<()> {
+/// let mut differ = PodDiffer::new();
+/// for event in differ.diff(client.get_pods().await?) {
+///     println!("{:?}", event);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Default)]
+pub struct PodDiffer {
+    known: HashMap<String, PodInfo>,
+}
+
+impl PodDiffer {
+    /// Creates a differ with no known pods.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records a new listing and returns the events since the previous one.
+    ///
+    /// Events are ordered: removals first, then additions and changes in listing order.
+    /// A pod that is added with an SSH command already set yields `PodAdded` followed by
+    /// `SshReady`.
+    pub fn diff(&mut self, pods: Vec<PodInfo>) -> Vec<PodEvent> {
+        let mut events = Vec::new();
+
+        let current: HashMap<&str, &PodInfo> =
+            pods.iter().map(|pod| (pod.id.as_str(), pod)).collect();
+        let mut removed: Vec<PodInfo> = self
+            .known
+            .values()
+            .filter(|pod| !current.contains_key(pod.id.as_str()))
+            .cloned()
+            .collect();
+        removed.sort_by(|a, b| a.id.cmp(&b.id));
+        events.extend(removed.into_iter().map(PodEvent::PodRemoved));
+
+        for pod in &pods {
+            match self.known.get(&pod.id) {
+                None => {
+                    events.push(PodEvent::PodAdded(pod.clone()));
+                    if pod.ssh_cmd.is_some() {
+                        events.push(PodEvent::SshReady(pod.clone()));
+                    }
+                }
+                Some(previous) => {
+                    if previous.status != pod.status {
+                        events.push(PodEvent::StatusChanged {
+                            pod: pod.clone(),
+                            from: previous.status.clone(),
+                            to: pod.status.clone(),
+                        });
+                    }
+                    if previous.ssh_cmd.is_none() && pod.ssh_cmd.is_some() {
+                        events.push(PodEvent::SshReady(pod.clone()));
+                    }
+                }
+            }
+        }
+
+        self.known = pods.into_iter().map(|pod| (pod.id.clone(), pod)).collect();
+        events
+    }
+}
+
+/// Polls `get_pods()` every `interval` and yields the changes between listings.
+///
+/// Polling always bypasses the response cache. A failed poll yields an `Err` item and
+/// the stream keeps polling, so a transient API outage does not end the watch.
+pub(crate) fn watch_pods(
+    client: &LiumApiClient,
+    interval: Duration,
+) -> impl Stream<Item = Result<PodEvent>> + Send + 'static {
+    let client = client.refreshing();
+
+    stream::unfold(
+        (client, PodDiffer::new(), true),
+        move |(client, mut differ, first)| async move {
+            if !first {
+                tokio::time::sleep(interval).await;
+            }
+
+            let batch: Vec<Result<PodEvent>> = match client.get_pods().await {
+                Ok(pods) => differ.diff(pods).into_iter().map(Ok).collect(),
+                Err(e) => vec![Err(e)],
+            };
+
+            Some((stream::iter(batch), (client, differ, false)))
+        },
+    )
+    .flatten()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{HttpResponse, InMemoryTransport, Lium, RetryPolicy};
+    use reqwest::{Method, StatusCode};
+    use serde_json::json;
+
+    fn pod(id: &str, status: &str, ssh_cmd: Option<&str>) -> serde_json::Value {
+        json!({
+            "id": id,
+            "pod_name": id,
+            "status": status,
+            "ssh_connect_cmd": ssh_cmd,
+            "ports_mapping": {},
+            "created_at": "2024-01-01T00:00:00Z",
+            "updated_at": "2024-01-01T00:00:00Z",
+            "executor": {},
+            "template": {},
+        })
+    }
+
+    fn pods(values: Vec<serde_json::Value>) -> Vec<PodInfo> {
+        let raw: Vec<lium_core::ApiPodResponse> =
+            serde_json::from_value(serde_json::Value::Array(values)).unwrap();
+        raw.into_iter().map(Into::into).collect()
+    }
+
+    #[test]
+    fn test_differ_reports_lifecycle() {
+        let mut differ = PodDiffer::new();
+
+        let events = differ.diff(pods(vec![pod("a", "PENDING", None)]));
+        assert!(matches!(&events[..], [PodEvent::PodAdded(p)] if p.id == "a"));
+
+        assert!(differ
+            .diff(pods(vec![pod("a", "PENDING", None)]))
+            .is_empty());
+
+        let events = differ.diff(pods(vec![pod("a", "RUNNING", Some("ssh root@h"))]));
+        assert!(matches!(
+            &events[..],
+            [PodEvent::StatusChanged { from, to, .. }, PodEvent::SshReady(_)]
+                if from == "PENDING" && to == "RUNNING"
+        ));
+
+        let events = differ.diff(pods(vec![pod("b", "RUNNING", Some("ssh root@b"))]));
+        assert!(matches!(
+            &events[..],
+            [PodEvent::PodRemoved(a), PodEvent::PodAdded(b), PodEvent::SshReady(_)]
+                if a.id == "a" && b.id == "b"
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_watch_pods_streams_events_and_survives_errors() {
+        let transport = InMemoryTransport::new().with_json(Method::GET, "pods", json!([]));
+        transport.enqueue(
+            Method::GET,
+            "pods",
+            HttpResponse::json_body(StatusCode::OK, &json!([pod("a", "PENDING", None)])),
+        );
+        transport.enqueue(
+            Method::GET,
+            "pods",
+            HttpResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "boom"),
+        );
+        transport.enqueue(
+            Method::GET,
+            "pods",
+            HttpResponse::json_body(StatusCode::OK, &json!([pod("a", "RUNNING", None)])),
+        );
+        let client = LiumApiClient::with_base_url("key".to_string(), "http://fake".to_string())
+            .with_retry_policy(RetryPolicy::disabled())
+            .with_transport(transport.clone());
+
+        let lium = Lium::from_client(client);
+        let events: Vec<_> = lium
+            .watch_pods(Duration::from_millis(1))
+            .take(4)
+            .collect()
+            .await;
+
+        assert!(matches!(&events[0], Ok(PodEvent::PodAdded(_))));
+        assert!(events[1].is_err());
+        assert!(matches!(&events[2], Ok(PodEvent::StatusChanged { to, .. }) if to == "RUNNING"));
+        assert!(matches!(&events[3], Ok(PodEvent::PodRemoved(_))));
+    }
+}
@@ -1,4 +1,13 @@
-use crate::{config::Config, ConfigCommands, Result};
+use crate::{
+    config::{
+        ApiConfig, Config, ConfigError, ConfigOrigin, ProfileConfig, SshConfig, DEFAULT_PROFILE,
+    },
+    display::{print_info, print_line, print_success, print_warning, prompt_confirm},
+    CliError, ConfigCommands, ProfileCommands, Result,
+};
+use colored::Colorize;
+use dialoguer::Password;
+use lium_utils::register_secret;
 
 /// Handles the `config` command for configuration management and inspection.
 ///
@@ -14,21 +23,23 @@ use crate::{config::Config, ConfigCommands, Result};
 /// * `Result<()>` - Success or error with detailed configuration operation information
 ///
 /// # Supported Operations
-/// - **Show**: Display current configuration in human-readable format
-/// - **Set**: Modify specific configuration values (placeholder)
-/// - **Get**: Retrieve specific configuration values (placeholder)
-/// - **Reset**: Reset configuration to defaults (placeholder)
+/// - **Show**: Display current configuration, optionally with each value's origin
+/// - **Set**: Validate and save a value by dotted key (e.g. `ssh.user`)
+/// - **Get**: Print a raw value by dotted key, for use in scripts
+/// - **Reset**: Back up the configuration file and reset it to defaults
 /// - **Init**: Run the interactive setup wizard
+/// - **Profiles**: List, select, add and remove named account profiles
 ///
 /// # Examples
 /// ```rust
+/// # async fn example() -> lium_cli::Result<()> {
 /// use lium_cli::commands::config::handle;
 /// use lium_cli::{ConfigCommands, config::Config};
 ///
 /// let config = Config::new()?;
 ///
 /// // Show current configuration
-/// handle(ConfigCommands::Show, &config).await?;
+/// handle(ConfigCommands::Show { origin: false }, &config).await?;
 ///
 /// // Set a configuration value
 /// handle(ConfigCommands::Set {
@@ -40,30 +51,36 @@ use crate::{config::Config, ConfigCommands, Result};
 /// handle(ConfigCommands::Get {
 ///     key: "api.base_url".to_string()
 /// }, &config).await?;
+/// # Ok(())
+/// # }
 /// ```
 ///
 /// # TODO
-/// - Implement actual set/get/reset functionality
-/// - Add configuration validation and type checking
-/// - Support for nested configuration paths
-/// - Add configuration backup and restore capabilities
+/// - Add configuration restore from backups
 pub async fn handle(action: ConfigCommands, config: &Config) -> Result<()> {
     match action {
-        ConfigCommands::Show => handle_show(config).await,
+        ConfigCommands::Show { origin } => handle_show(origin, config).await,
         ConfigCommands::Set { key, value } => handle_set(key, value, config).await,
         ConfigCommands::Get { key } => handle_get(key, config).await,
-        ConfigCommands::Reset => handle_reset().await,
+        ConfigCommands::Reset { yes } => handle_reset(yes, config).await,
         ConfigCommands::Init => handle_init().await,
+        ConfigCommands::Profiles { action } => handle_profiles(action, config).await,
     }
 }
 
 /// Shows the current configuration in a formatted, human-readable display.
 ///
 /// Displays the complete user configuration including API settings, SSH configuration,
-/// and other preferences. Sensitive information like API keys are partially masked
-/// for security.
+/// and other preferences. Plaintext API keys and Docker tokens are redacted; values
+/// that reference the secret store (`secret:<name>`) are shown as they are. If any
+/// credential is still stored in plaintext, `lium secrets migrate` is suggested.
+///
+/// With `origin`, prints every effective value instead, one per line, annotated with
+/// the layer it came from (environment variable, project `.lium.toml`, profile or
+/// user config file).
 ///
 /// # Arguments
+/// * `origin` - Show effective values with their origins instead of the user config file
 /// * `config` - User configuration to display
 ///
 /// # Returns
@@ -75,7 +92,7 @@ pub async fn handle(action: ConfigCommands, config: &Config) -> Result<()> {
 /// Configuration file: ~/.lium/config.toml
 ///
 /// [api]
-/// api_key = "lium_****...****abc123"
+/// api_key = "secret:api_key"
 /// base_url = "https://api.lium.ai"
 ///
 /// [ssh]
@@ -88,34 +105,127 @@ pub async fn handle(action: ConfigCommands, config: &Config) -> Result<()> {
 ///
 /// # TODO
 /// - Add configuration validation status
-/// - Show configuration source (file, environment, defaults)
 /// - Add configuration health checks
-async fn handle_show(config: &Config) -> Result<()> {
-    println!("📋 Current Configuration:");
-    println!("{}", config.show_config());
+async fn handle_show(origin: bool, config: &Config) -> Result<()> {
+    print_line("📋 Current Configuration:");
+    print_line(&format!(
+        "Active profile: {}",
+        config.active_profile().unwrap_or(DEFAULT_PROFILE)
+    ));
+    if let Some((path, _)) = &config.project {
+        print_line(&format!("Project config: {}", path.display()));
+    }
+
+    if origin {
+        print_line("");
+        for line in origin_lines(config) {
+            print_line(&line);
+        }
+        return Ok(());
+    }
+
+    print_line(&config.show_config());
+    if config.has_plaintext_credentials() {
+        print_warning("Credentials are stored in plaintext in the config file");
+        print_info("Move them into the encrypted secret store with 'lium secrets migrate'");
+    }
     Ok(())
 }
 
-/// Set configuration value
+/// Validates and saves a configuration value by dotted key.
+///
+/// Keys under `api`, `ssh`, `template` and `docker` are written to the active
+/// profile when one is selected.
+///
+/// # Arguments
+/// * `key` - Dotted configuration key, e.g. `template.default_id`
+/// * `value` - The new value
+/// * `config` - Current user configuration
+///
+/// # Returns
+/// * `Result<()>` - Success, or an error for unknown keys (with suggestions) and invalid values
 async fn handle_set(key: String, value: String, config: &Config) -> Result<()> {
-    println!("⚠️  Configuration setting not yet implemented");
-    println!("Key: {}, Value: {}", key, value);
-    println!("💡 Use individual commands like 'lium init' to set up configuration");
+    let mut config = config.clone();
+    config.update(|config| config.set_key(&key, &value))?;
+
+    match config.active_profile() {
+        Some(profile) => print_success(&format!("Set {} (profile '{}')", key, profile)),
+        None => print_success(&format!("Set {}", key)),
+    }
+    if let Some(origin @ (ConfigOrigin::ProjectFile(_) | ConfigOrigin::Env(_))) =
+        config.origin_of(&key)
+    {
+        print_warning(&format!(
+            "{} is overridden by {}; the saved value applies where that override is absent",
+            key, origin
+        ));
+    }
     Ok(())
 }
 
-/// Get configuration value
+/// Lines printed by `config show --origin`: `key = value  # origin`
+fn origin_lines(config: &Config) -> Vec<String> {
+    config
+        .origins()
+        .into_iter()
+        .map(|(key, value, origin)| {
+            format!("{} = {}  {}", key, value, format!("# {}", origin).dimmed())
+        })
+        .collect()
+}
+
+/// Prints a configuration value by dotted key.
+///
+/// Only the raw value is written to stdout so the output can be captured by scripts,
+/// e.g. `user=$(lium config get ssh.user)`.
+///
+/// # Arguments
+/// * `key` - Dotted configuration key, e.g. `api.base_url`
+/// * `config` - Current user configuration
+///
+/// # Returns
+/// * `Result<()>` - Success, or `CliError::NotFound` if the key is unset
 async fn handle_get(key: String, config: &Config) -> Result<()> {
-    println!("⚠️  Configuration getting not yet implemented");
-    println!("Key: {}", key);
-    println!("💡 Use 'lium config show' to see all configuration");
-    Ok(())
+    match config.get_key(&key)? {
+        Some(value) => {
+            println!("{}", value);
+            Ok(())
+        }
+        None => Err(CliError::NotFound(format!("{} is not set", key))),
+    }
 }
 
-/// Reset configuration to defaults
-async fn handle_reset() -> Result<()> {
-    println!("⚠️  Configuration reset not yet implemented");
-    println!("💡 For now, you can manually delete ~/.lium/config.toml");
+/// Resets the configuration file to defaults after backing it up.
+///
+/// # Arguments
+/// * `yes` - Skip the confirmation prompt
+/// * `config` - Current user configuration
+///
+/// # Returns
+/// * `Result<()>` - Success or error if the backup or new file cannot be written
+async fn handle_reset(yes: bool, config: &Config) -> Result<()> {
+    if !yes
+        && !prompt_confirm(
+            &format!(
+                "Reset {} to defaults? Profiles will be cleared",
+                config.config_path.display()
+            ),
+            false,
+        )?
+    {
+        print_info("Cancelled");
+        return Ok(());
+    }
+
+    let mut config = config.clone();
+    match config.reset()? {
+        Some(backup) => print_success(&format!(
+            "Configuration reset. Previous settings saved to {}",
+            backup.display()
+        )),
+        None => print_success("Configuration reset"),
+    }
+    print_info("Run 'lium init' to set up your API key again");
     Ok(())
 }
 
@@ -124,3 +234,251 @@ async fn handle_init() -> Result<()> {
     // Forward to the main init command
     crate::commands::init::handle().await
 }
+
+/// Handles `config profiles` subcommands.
+///
+/// Each change is applied to the latest configuration file under the config lock
+/// (see [`Config::update`]); the current run keeps using the profile it started with.
+///
+/// # Arguments
+/// * `action` - The profile operation to perform
+/// * `config` - Current user configuration
+///
+/// # Returns
+/// * `Result<()>` - Success or error if the profile is unknown, already exists, or saving fails
+async fn handle_profiles(action: ProfileCommands, config: &Config) -> Result<()> {
+    let mut config = config.clone();
+
+    match action {
+        ProfileCommands::List => {
+            for line in profile_list_lines(&config) {
+                println!("{}", line);
+            }
+            return Ok(());
+        }
+        ProfileCommands::Use { name } => {
+            config.update(|config| config.set_default_profile(&name))?;
+            print_success(&format!("Default profile set to '{}'", name));
+        }
+        ProfileCommands::Add {
+            name,
+            api_key,
+            base_url,
+            ssh_key,
+        } => {
+            let api_key = match api_key {
+                Some(key) => key,
+                None => Password::new()
+                    .with_prompt(format!("API key for profile '{}'", name))
+                    .allow_empty_password(false)
+                    .interact()?,
+            };
+            register_secret(&api_key);
+            let profile = new_profile(api_key, base_url, ssh_key);
+            config.update(|config| config.add_profile(&name, profile))?;
+            print_success(&format!("Added profile '{}'", name));
+            print_info(&format!(
+                "Use it with 'lium --profile {0} ...' or make it the default with 'lium config profiles use {0}'",
+                name
+            ));
+        }
+        ProfileCommands::Remove { name, yes } => {
+            if !config.has_profile(&name) {
+                return Err(ConfigError::ProfileNotFound(name).into());
+            }
+            if !yes && !prompt_confirm(&format!("Remove profile '{}'?", name), false)? {
+                print_info("Cancelled");
+                return Ok(());
+            }
+            config.update(|config| config.remove_profile(&name))?;
+            print_success(&format!("Removed profile '{}'", name));
+        }
+    }
+
+    Ok(())
+}
+
+/// Builds a profile from the values given to `config profiles add`
+fn new_profile(
+    api_key: String,
+    base_url: Option<String>,
+    ssh_key: Option<String>,
+) -> ProfileConfig {
+    ProfileConfig {
+        api: Some(ApiConfig {
+            api_key: Some(api_key.trim().to_string()),
+            base_url,
+            ..Default::default()
+        }),
+        ssh: ssh_key.map(|key_path| SshConfig {
+            key_path: Some(key_path),
+            ..Default::default()
+        }),
+        ..Default::default()
+    }
+}
+
+/// Lines printed by `config profiles list`: the top-level settings first, then each
+/// named profile, marking the active profile with `*` and the default with `(default)`
+fn profile_list_lines(config: &Config) -> Vec<String> {
+    let active = config.active_profile().unwrap_or(DEFAULT_PROFILE);
+    let default = config
+        .data
+        .default_profile
+        .as_deref()
+        .unwrap_or(DEFAULT_PROFILE);
+
+    std::iter::once(DEFAULT_PROFILE.to_string())
+        .chain(config.profile_names())
+        .map(|name| {
+            let marker = if name == active { "*" } else { " " };
+            let mut line = format!("{} {}", marker, name);
+            if name == default {
+                line.push_str(&format!(" {}", "(default)".dimmed()));
+            }
+            line
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::test_config;
+
+    #[test]
+    fn test_set_get_validate_dotted_keys() {
+        let dir = tempfile::tempdir().unwrap();
+        let key_path = dir.path().join("id_ed25519.pub");
+        std::fs::write(&key_path, "ssh-ed25519 AAAA test").unwrap();
+        let key_path = key_path.to_string_lossy().to_string();
+
+        let mut config = test_config();
+        config
+            .set_key("api.base_url", "https://lium.example/")
+            .unwrap();
+        config.set_key("ssh.key_path", &key_path).unwrap();
+        config.set_key("theme.name", "dark").unwrap();
+        assert_eq!(
+            config.get_key("api.base_url").unwrap().as_deref(),
+            Some("https://lium.example")
+        );
+        assert_eq!(config.get_key("ssh.key_path").unwrap(), Some(key_path));
+        assert_eq!(
+            config.get_key("theme.name").unwrap().as_deref(),
+            Some("dark")
+        );
+        assert_eq!(config.get_key("docker.username").unwrap(), None);
+
+        for (key, value) in [
+            ("api.base_url", "not a url"),
+            ("api.base_url", "ftp://lium.example"),
+            ("ssh.key_path", "/nonexistent/key.pub"),
+            ("ssh.user", "two words"),
+            ("theme.name", "neon"),
+        ] {
+            assert!(
+                matches!(
+                    config.set_key(key, value),
+                    Err(CliError::Config(ConfigError::InvalidValue { .. }))
+                ),
+                "{} = {} should be rejected",
+                key,
+                value
+            );
+        }
+
+        let err = config.set_key("ssh.usr", "ubuntu").unwrap_err().to_string();
+        assert!(err.contains("Did you mean: ssh.user?"), "{}", err);
+        let err = config.get_key("base_url").unwrap_err().to_string();
+        assert!(err.contains("api.base_url"), "{}", err);
+        let err = config.get_key("nonsense").unwrap_err().to_string();
+        assert!(err.contains("Valid keys:"), "{}", err);
+    }
+
+    #[test]
+    fn test_reset_backs_up_old_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut config = test_config();
+        config.config_path = dir.path().join("config.toml");
+        config.set_key("ssh.user", "ubuntu").unwrap();
+        config.save().unwrap();
+
+        let backup = config.reset().unwrap().unwrap();
+        assert!(std::fs::read_to_string(backup).unwrap().contains("ubuntu"));
+        assert_eq!(config.get_key("ssh.user").unwrap(), None);
+        assert!(!std::fs::read_to_string(&config.config_path)
+            .unwrap()
+            .contains("ubuntu"));
+    }
+
+    fn team_config() -> Config {
+        let mut config = test_config();
+        config.data = toml::from_str(
+            r#"
+            default_profile = "team"
+
+            [api]
+            api_key = "personal-key"
+
+            [ssh]
+            key_path = "~/.ssh/id_ed25519.pub"
+
+            [profiles.team.api]
+            api_key = "team-key"
+            base_url = "https://lium.team.internal"
+            "#,
+        )
+        .unwrap();
+        config
+    }
+
+    #[test]
+    fn test_profile_list_marks_active_and_default() {
+        colored::control::set_override(false);
+        let mut config = team_config();
+        config
+            .add_profile("staging", ProfileConfig::default())
+            .unwrap();
+        config.select_profile(Some("staging")).unwrap();
+
+        assert_eq!(
+            profile_list_lines(&config),
+            vec!["  default", "* staging", "  team (default)"]
+        );
+    }
+
+    #[test]
+    fn test_profiles_add_use_remove_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut config = team_config();
+        config.config_path = dir.path().join("config.toml");
+
+        config
+            .add_profile(
+                "lab",
+                new_profile(
+                    "lab-key".to_string(),
+                    None,
+                    Some("~/.ssh/lab.pub".to_string()),
+                ),
+            )
+            .unwrap();
+        config.set_default_profile("lab").unwrap();
+        config.save().unwrap();
+
+        let mut reloaded = Config {
+            data: toml::from_str(&std::fs::read_to_string(&config.config_path).unwrap()).unwrap(),
+            ..config.clone()
+        };
+        reloaded.select_profile(None).unwrap();
+        assert_eq!(reloaded.active_profile(), Some("lab"));
+        assert_eq!(reloaded.api().api_key.as_deref(), Some("lab-key"));
+        assert_eq!(reloaded.ssh().key_path.as_deref(), Some("~/.ssh/lab.pub"));
+
+        reloaded.remove_profile("lab").unwrap();
+        assert_eq!(reloaded.data.default_profile, None);
+        assert_eq!(reloaded.active_profile(), None);
+        assert_eq!(reloaded.api().api_key.as_deref(), Some("personal-key"));
+    }
+}
@@ -1,6 +1,14 @@
-use crate::{config::Config, helpers::resolve_pod_targets, CliError, Result};
+use crate::{
+    config::Config,
+    display::{is_structured_output, print_info, print_output},
+    helpers::resolve_pod_targets,
+    remote::forget_pod,
+    CliError, Result,
+};
 use dialoguer::Confirm;
 use lium_api::LiumApiClient;
+use log::{debug, warn};
+use serde::Serialize;
 
 /// Handles the `down` command to stop and terminate running pods.
 ///
@@ -29,7 +37,8 @@ use lium_api::LiumApiClient;
 /// 2. **Target Resolution**: Converts pod targets to actual pod references
 /// 3. **Status Check**: Verifies pods exist and are in a stoppable state
 /// 4. **Confirmation**: Shows affected pods and requests user confirmation (unless `--yes`)
-/// 5. **Termination**: Calls unrent_pod API for each target pod
+/// 5. **Termination**: Calls unrent_pod API for each target pod and removes its pinned
+///    SSH host key from `~/.lium/known_hosts`
 /// 6. **Results**: Reports success/failure counts and details
 ///
 /// # Error Conditions
@@ -47,6 +56,7 @@ use lium_api::LiumApiClient;
 ///
 /// # Examples
 /// ```rust
+/// # async fn example() -> lium_cli::Result<()> {
 /// use lium_cli::commands::down::handle;
 /// use lium_cli::config::Config;
 ///
@@ -67,6 +77,8 @@ use lium_api::LiumApiClient;
 ///     true,
 ///     &config
 /// ).await?;
+/// # Ok(())
+/// # }
 /// ```
 ///
 /// # Output Format
@@ -92,7 +104,42 @@ use lium_api::LiumApiClient;
 /// - Add backup/snapshot creation before termination
 pub async fn handle(pods: Vec<String>, all: bool, yes: bool, config: &Config) -> Result<()> {
     let api_client = LiumApiClient::from_config(config)?;
+    let results = handle_with_client(pods, all, yes, &api_client).await?;
 
+    print_output(&results, |results| {
+        if results.is_empty() {
+            return;
+        }
+        let stopped = results.iter().filter(|r| r.stopped).count();
+        println!("\n🏁 Stop operation complete:");
+        println!("  ✅ Successfully stopped: {}", stopped);
+        if stopped < results.len() {
+            println!("  ❌ Failed to stop: {}", results.len() - stopped);
+        }
+    })
+}
+
+/// The outcome of stopping one pod, as printed by `--output json`.
+#[derive(Debug, Clone, Serialize)]
+pub struct StopResult {
+    pub huid: String,
+    pub name: String,
+    pub executor_id: String,
+    pub stopped: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Runs the `down` flow against an already constructed API client.
+///
+/// Returns one result per targeted pod, or none if nothing was found or the
+/// confirmation was declined. Progress is printed in table mode only.
+pub async fn handle_with_client(
+    pods: Vec<String>,
+    all: bool,
+    yes: bool,
+    api_client: &LiumApiClient,
+) -> Result<Vec<StopResult>> {
     // Determine targets
     let targets = if all {
         vec!["all".to_string()]
@@ -106,22 +153,25 @@ pub async fn handle(pods: Vec<String>, all: bool, yes: bool, config: &Config) ->
     };
 
     // Resolve pod targets
-    let resolved_pods = resolve_pod_targets(&api_client, &targets).await?;
+    let resolved_pods = resolve_pod_targets(api_client, &targets).await?;
 
+    let progress = !is_structured_output();
     if resolved_pods.is_empty() {
-        println!("No active pods found to stop.");
-        return Ok(());
+        print_info("No active pods found to stop.");
+        return Ok(Vec::new());
     }
 
-    println!("📋 Pods to stop:");
-    for (i, (pod, _)) in resolved_pods.iter().enumerate() {
-        println!(
-            "  {}. {} ({}) - Status: {}",
-            i + 1,
-            pod.huid,
-            pod.name,
-            pod.status
-        );
+    if progress {
+        println!("📋 Pods to stop:");
+        for (i, (pod, _)) in resolved_pods.iter().enumerate() {
+            println!(
+                "  {}. {} ({}) - Status: {}",
+                i + 1,
+                pod.huid,
+                pod.name,
+                pod.status
+            );
+        }
     }
 
     // Confirmation unless -y flag
@@ -136,15 +186,16 @@ pub async fn handle(pods: Vec<String>, all: bool, yes: bool, config: &Config) ->
             .map_err(|e| CliError::InvalidInput(format!("Input error: {}", e)))?;
 
         if !confirm {
-            println!("❌ Operation cancelled.");
-            return Ok(());
+            print_info("Operation cancelled.");
+            return Ok(Vec::new());
         }
     }
 
-    println!("\n🛑 Stopping {} pod(s)...", resolved_pods.len());
+    if progress {
+        println!("\n🛑 Stopping {} pod(s)...", resolved_pods.len());
+    }
 
-    let mut success_count = 0;
-    let mut failure_count = 0;
+    let mut results = Vec::new();
 
     // Stop each pod
     for (pod, target_name) in resolved_pods {
@@ -154,7 +205,9 @@ pub async fn handle(pods: Vec<String>, all: bool, yes: bool, config: &Config) ->
             format!("{} ({})", target_name, pod.huid)
         };
 
-        print!("🛑 Stopping pod {}... ", pod_display);
+        if progress {
+            print!("🛑 Stopping pod {}... ", pod_display);
+        }
 
         // Extract executor ID from pod data
         let executor_id = pod
@@ -163,23 +216,113 @@ pub async fn handle(pods: Vec<String>, all: bool, yes: bool, config: &Config) ->
             .and_then(|v| v.as_str())
             .unwrap_or(&pod.id); // Fallback to pod ID if executor ID not found
 
-        match api_client.unrent_pod(executor_id).await {
-            Ok(_) => {
-                println!("✅ Success");
-                success_count += 1;
+        let error = match api_client.unrent_pod(executor_id).await {
+            Ok(response) => {
+                if let Some(detail) = &response.detail {
+                    debug!("Unrented executor {}: {}", executor_id, detail);
+                }
+                None
             }
-            Err(e) => {
-                println!("❌ Failed: {}", e);
-                failure_count += 1;
+            Err(e) => Some(e),
+        };
+        if error.is_none() {
+            // The pod id will never be reused, so its pinned host key and key can go
+            if let Err(e) = forget_pod(&pod) {
+                warn!("Failed to forget the SSH keys of pod {}: {}", pod.huid, e);
             }
         }
+        if progress {
+            match &error {
+                None => println!("✅ Success"),
+                Some(e) => println!("❌ Failed: {}", e),
+            }
+        }
+
+        results.push(StopResult {
+            huid: pod.huid.clone(),
+            name: pod.name.clone(),
+            executor_id: executor_id.to_string(),
+            stopped: error.is_none(),
+            error: error.map(|e| e.to_string()),
+        });
     }
 
-    println!("\n🏁 Stop operation complete:");
-    println!("  ✅ Successfully stopped: {}", success_count);
-    if failure_count > 0 {
-        println!("  ❌ Failed to stop: {}", failure_count);
+    Ok(results)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{api_pod, test_client};
+    use lium_api::InMemoryTransport;
+    use reqwest::Method;
+    use serde_json::json;
+
+    #[tokio::test]
+    async fn test_down_unrents_pod_by_executor_id() {
+        let transport = InMemoryTransport::new()
+            .with_pods(&[
+                api_pod("pod-1", "train", "exec-1", None),
+                api_pod("pod-2", "serve", "exec-2", None),
+            ])
+            .with_json(Method::DELETE, "executors/exec-2/rent", json!({}));
+        let client = test_client(&transport);
+
+        let results = handle_with_client(vec!["serve".to_string()], false, true, &client)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            transport.request_log(),
+            vec![
+                (Method::GET, "pods".to_string()),
+                (Method::DELETE, "executors/exec-2/rent".to_string()),
+            ]
+        );
+        assert_eq!(
+            serde_json::to_value(&results).unwrap(),
+            json!([{
+                "huid": results[0].huid,
+                "name": "serve",
+                "executor_id": "exec-2",
+                "stopped": true,
+            }])
+        );
     }
 
-    Ok(())
+    #[tokio::test]
+    async fn test_down_all_unrents_every_pod() {
+        let transport = InMemoryTransport::new()
+            .with_pods(&[
+                api_pod("pod-1", "train", "exec-1", None),
+                api_pod("pod-2", "serve", "exec-2", None),
+            ])
+            .with_json(Method::DELETE, "executors/exec-1/rent", json!({}))
+            .with_json(Method::DELETE, "executors/exec-2/rent", json!({}));
+        let client = test_client(&transport);
+
+        handle_with_client(vec![], true, true, &client)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            transport.request_log(),
+            vec![
+                (Method::GET, "pods".to_string()),
+                (Method::DELETE, "executors/exec-1/rent".to_string()),
+                (Method::DELETE, "executors/exec-2/rent".to_string()),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_down_without_targets_makes_no_requests() {
+        let transport = InMemoryTransport::new();
+        let client = test_client(&transport);
+
+        assert!(handle_with_client(vec![], false, true, &client)
+            .await
+            .is_err());
+        assert!(transport.requests().is_empty());
+    }
 }
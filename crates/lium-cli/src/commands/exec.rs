@@ -1,18 +1,28 @@
 use crate::{
     config::Config,
-    display::{print_error, print_info, print_success, print_warning},
+    display::{
+        is_structured_output, pod_output_prefix, print_error, print_info, print_output,
+        print_success, print_warning, write_console, Table,
+    },
     helpers::resolve_pod_targets,
+    remote::{exec_on_pod, pod_ssh_auth, pod_ssh_target, with_session, RemoteCommand},
+    secrets::{resolve_secret_vars, stdin_env_command, stdin_env_payload},
     CliError, Result,
 };
 use clap::Args;
+use futures::stream::{self, StreamExt};
 use lium_api::LiumApiClient;
-use lium_utils::parse_ssh_command;
+use lium_utils::{
+    is_sensitive_name, redact_registered, register_secret, stdin_fan_out, CancelToken,
+    OutputStream, SshAuth, SshError, SshTarget, UtilsError,
+};
 use log::{debug, error, info, warn};
+use serde::Serialize;
 use std::collections::HashMap;
+use std::fmt;
 use std::fs;
-use std::process::Stdio;
-use tokio::io::{AsyncBufReadExt, BufReader};
-use tokio::process::Command;
+use std::sync::mpsc;
+use std::time::{Duration, Instant};
 
 /// Command-line arguments for the `exec` command that executes commands on remote pods.
 ///
@@ -36,8 +46,18 @@ use tokio::process::Command;
 /// # Set environment variables
 /// lium exec 1 --env DEBUG=1 --env API_KEY=secret "python app.py"
 ///
+/// # Inject secrets without exposing them on the command line
+/// lium exec 1 --secret HF_TOKEN -- python train.py
+///
 /// # Use double dash for commands with flags
 /// lium exec 1 -- python train.py --epochs 100 --lr 0.001
+///
+/// # Run on 8 pods at a time, stopping at the first failure
+/// lium exec all --parallel 8 --fail-fast --timeout 600 -- pip install -r requirements.txt
+///
+/// # Interactive programs and piped input
+/// lium exec 1 --tty htop
+/// cat data.csv | lium exec 1,2 --stdin -- wc -l
 /// ```
 ///
 /// # Pod Target Resolution
@@ -51,14 +71,13 @@ use tokio::process::Command;
 /// # Security Considerations
 /// - Commands are executed via SSH with configured private keys
 /// - Environment variables are exported before command execution
+/// - Secrets are sent over the SSH session's stdin, never on the remote command line,
+///   and are shown as `***` in all output
 /// - Output streaming prevents command hanging
-/// - SSH host key checking is disabled for cloud environments
+/// - Pod host keys are pinned on first connect and verified on every later one
 ///
 /// # TODO
-/// - Add support for interactive TTY sessions
-/// - Implement command timeout configuration
 /// - Add support for file upload before execution
-/// - Support for parallel vs sequential execution modes
 #[derive(Args)]
 pub struct ExecArgs {
     /// Pod targets to execute commands on (comma-separated).
@@ -126,6 +145,53 @@ pub struct ExecArgs {
     /// shell escaping to handle special characters in values.
     #[arg(short, long)]
     pub env: Vec<String>,
+
+    /// Secrets to export before command execution.
+    ///
+    /// Format: `VAR` or `VAR=NAME`. The value of secret `NAME` (or `VAR`) is read from
+    /// the local secret store (`lium secrets`), falling back to the local environment.
+    /// Values are sent over the SSH session's stdin rather than the command line, so
+    /// they never show in `ps` on the pod, and are redacted as `***` in all output.
+    ///
+    /// Examples:
+    /// - `--secret HF_TOKEN`
+    /// - `--secret WANDB_API_KEY=wandb_key`
+    #[arg(long)]
+    pub secret: Vec<String>,
+
+    /// Number of pods to run the command on at the same time.
+    ///
+    /// With more than one, output lines are printed as they arrive behind the pod's
+    /// HUID (`[brave-cat-1a2b] ...`), colored per pod. The default of 1 runs pods one
+    /// after another with a header before each pod's output.
+    #[arg(long, value_name = "N", default_value_t = 1)]
+    pub parallel: usize,
+
+    /// Stop at the first pod that fails: pods not started yet are skipped and
+    /// commands still running are cancelled.
+    #[arg(long)]
+    pub fail_fast: bool,
+
+    /// Seconds the command may run on each pod before it is abandoned and counted as
+    /// failed.
+    #[arg(long, value_name = "SECONDS")]
+    pub timeout: Option<u64>,
+
+    /// Allocate a pseudo-terminal for interactive programs such as `htop` or `python -i`.
+    ///
+    /// Requires a single pod target. Keystrokes go to the program until it exits.
+    /// Cannot be combined with `--secret` (the terminal would echo the values),
+    /// `--timeout` or a structured `--output` format.
+    #[arg(short, long)]
+    pub tty: bool,
+
+    /// Stream local standard input to the command, e.g.
+    /// `cat data.csv | lium exec 1 -i 'wc -l'`.
+    ///
+    /// With several targets every pod receives a copy of the input, and all of them
+    /// run at once regardless of `--parallel`.
+    #[arg(short = 'i', long = "stdin")]
+    pub stdin: bool,
 }
 
 /// Handle the exec command for remote command execution
@@ -147,9 +213,9 @@ pub struct ExecArgs {
 /// 2. **Target Resolution**: Resolves pod targets to actual pod instances
 /// 3. **SSH Configuration**: Validates SSH key availability and configuration
 /// 4. **Command Preparation**: Processes commands, scripts, and environment variables
-/// 5. **Execution Loop**: Executes commands on each target pod sequentially
+/// 5. **Execution Loop**: Executes commands on up to `--parallel` pods at a time
 /// 6. **Output Streaming**: Streams stdout/stderr in real-time with proper labeling
-/// 7. **Result Summary**: Reports success/failure counts for multiple pod operations
+/// 7. **Result Summary**: Prints a summary table for multiple pod operations
 ///
 /// # Command vs Script Execution
 ///
@@ -171,9 +237,13 @@ pub struct ExecArgs {
 /// 4. Prepend to command or script content
 /// 5. Execute combined command string
 ///
+/// Secrets given with `--secret` are not part of the command string: the command is
+/// wrapped to read `export` lines from stdin first, and the values are written to the
+/// SSH session's stdin. Any secret value in the command's output is replaced with `***`.
+///
 /// # SSH Connection Management
 /// - Uses configured private key from user settings
-/// - Disables host key checking for cloud environments
+/// - Verifies pod host keys against `~/.lium/known_hosts` (trust on first use)
 /// - Supports custom ports from pod SSH commands
 /// - Implements connection retry logic for transient failures
 /// - Provides detailed debugging for connection issues
@@ -184,9 +254,18 @@ pub struct ExecArgs {
 /// - Maintains real-time feedback
 ///
 /// For multiple pod execution:
-/// - Labels output with pod identifiers
-/// - Separates output between pods
-/// - Provides execution summary
+/// - Sequentially: prints a header before each pod's output
+/// - With `--parallel N`: prefixes every line with the pod's colored `[huid]`
+/// - With `--stdin`: every pod receives a copy of local stdin and all run at once
+///
+/// With `--tty` the single target's command runs in a pseudo-terminal attached to the
+/// local terminal, so interactive programs work as they do over `lium ssh`.
+/// - Provides a summary table with each pod's status, exit code and duration
+///
+/// # Exit Status
+/// With a single target the process exits with the remote command's status (124 if
+/// it timed out, 255 if it could not be run). With several targets it exits with 1
+/// if the command failed on any pod.
 ///
 /// # Error Conditions
 /// - Invalid pod targets (non-existent or inaccessible pods)
@@ -197,6 +276,7 @@ pub struct ExecArgs {
 ///
 /// # Examples
 /// ```rust
+/// # async fn example(config: lium_cli::Config) -> lium_cli::Result<()> {
 /// use lium_cli::commands::exec::{handle, ExecArgs};
 /// use lium_cli::config::Config;
 ///
@@ -206,42 +286,196 @@ pub struct ExecArgs {
 ///     command: vec!["nvidia-smi".to_string()],
 ///     script: None,
 ///     env: vec![],
+///     secret: vec![],
+///     parallel: 1,
+///     fail_fast: false,
+///     timeout: None,
+///     tty: false,
+///     stdin: false,
 /// };
 /// handle(args, &config).await?;
 ///
-/// // Execute script with environment variables on multiple pods
+/// // Execute script with environment variables on 3 pods at once
 /// let args = ExecArgs {
 ///     pod_targets: "1,2,3".to_string(),
 ///     command: vec![],
 ///     script: Some("setup.sh".to_string()),
 ///     env: vec!["DEBUG=1".to_string(), "WORKERS=4".to_string()],
+///     secret: vec!["HF_TOKEN".to_string()],
+///     parallel: 3,
+///     fail_fast: true,
+///     timeout: Some(600),
+///     tty: false,
+///     stdin: false,
 /// };
 /// handle(args, &config).await?;
+/// # Ok(())
+/// # }
 /// ```
 ///
 /// # Security Considerations
 /// - SSH connections use configured private keys only
-/// - Host key verification is disabled for cloud pod environments
+/// - Host keys are pinned per pod and a changed key aborts the connection
 /// - Environment variables are properly shell-escaped
 /// - Command execution is logged for audit purposes
 /// - No sensitive information is logged in debug output
 ///
 /// # Performance Considerations
-/// - Commands are executed sequentially across pods (not parallel)
+/// - Commands run on one pod at a time unless `--parallel` is given
 /// - Output streaming prevents memory buildup for long-running commands
-/// - SSH connections are created per-pod (no connection pooling)
+/// - Each pod is connected to once per invocation through the shared SSH session pool
 /// - Large script files are efficiently streamed to remote pods
 ///
 /// # TODO
-/// - Add support for parallel execution across multiple pods
-/// - Implement command timeout configuration
-/// - Add support for interactive TTY sessions
 /// - Support for file upload/download before/after execution
 /// - Add execution history and result caching
-/// - Implement connection pooling for better performance
 pub async fn handle(args: ExecArgs, config: &Config) -> Result<()> {
     let api_client = LiumApiClient::from_config(config)?;
+    let results = handle_with_client(args, config, &api_client).await?;
 
+    print_output(&results, |results| {
+        // Show summary for multiple pods
+        if results.len() > 1 {
+            println!();
+            display_exec_summary(results);
+            let succeeded = results.iter().filter(|r| r.success).count();
+            println!();
+            print_info(&format!(
+                "Execution Summary: {} successful, {} failed",
+                succeeded,
+                results.len() - succeeded
+            ));
+        }
+    })?;
+
+    exit_status(&results)
+}
+
+/// Maps the per-pod results to the outcome of the whole command.
+///
+/// A single target propagates the remote exit status (124 for a timeout, 255 if the
+/// command could not be run, as `timeout` and `ssh` do); several targets fail with 1
+/// if any pod failed.
+fn exit_status(results: &[ExecResult]) -> Result<()> {
+    match results {
+        [result] if !result.success => Err(CliError::Exit(match result.status {
+            ExecStatus::Failed => result.exit_code.unwrap_or(1),
+            ExecStatus::TimedOut => 124,
+            _ => 255,
+        })),
+        _ if results.iter().any(|r| !r.success) => Err(CliError::Exit(1)),
+        _ => Ok(()),
+    }
+}
+
+/// Prints one row per pod with its status, exit code and run time
+fn display_exec_summary(results: &[ExecResult]) {
+    let mut table = Table::new(vec![
+        "Pod".to_string(),
+        "Target".to_string(),
+        "Status".to_string(),
+        "Exit Code".to_string(),
+        "Duration".to_string(),
+    ]);
+    for result in results {
+        table.add_row(vec![
+            result.huid.clone(),
+            result.target.clone(),
+            result.status.to_string(),
+            result
+                .exit_code
+                .map(|code| code.to_string())
+                .unwrap_or_else(|| "-".to_string()),
+            format!("{:.1}s", result.duration_ms as f64 / 1000.0),
+        ]);
+    }
+    table.print();
+}
+
+/// How the command ended on one pod.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecStatus {
+    /// Exited with status 0
+    Succeeded,
+    /// Exited with a non-zero status
+    Failed,
+    /// Ran longer than `--timeout`
+    TimedOut,
+    /// Stopped by `--fail-fast` after another pod failed
+    Cancelled,
+    /// Not started because another pod failed first (`--fail-fast`)
+    Skipped,
+    /// Could not be run, e.g. the pod was unreachable
+    Error,
+}
+
+impl fmt::Display for ExecStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            ExecStatus::Succeeded => "succeeded",
+            ExecStatus::Failed => "failed",
+            ExecStatus::TimedOut => "timed out",
+            ExecStatus::Cancelled => "cancelled",
+            ExecStatus::Skipped => "skipped",
+            ExecStatus::Error => "error",
+        })
+    }
+}
+
+/// The outcome of running the command on one pod, as printed by `--output json`.
+///
+/// Output is streamed to the terminal in table mode, so `stdout` and `stderr` are
+/// only captured when a structured output format is selected.
+#[derive(Debug, Clone, Serialize)]
+pub struct ExecResult {
+    pub huid: String,
+    pub target: String,
+    pub success: bool,
+    pub status: ExecStatus,
+    pub exit_code: Option<i32>,
+    pub duration_ms: u64,
+    pub stdout: String,
+    pub stderr: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl ExecResult {
+    fn new(huid: &str, target: &str) -> Self {
+        Self {
+            huid: huid.to_string(),
+            target: target.to_string(),
+            success: false,
+            status: ExecStatus::Error,
+            exit_code: None,
+            duration_ms: 0,
+            stdout: String::new(),
+            stderr: String::new(),
+            error: None,
+        }
+    }
+}
+
+/// The prepared command and how to run it, shared by every pod
+struct ExecPlan {
+    command: String,
+    stdin: Option<String>,
+    capture: bool,
+    multiple: bool,
+    timeout: Option<Duration>,
+    fail_fast: bool,
+    cancel: CancelToken,
+}
+
+/// Runs the `exec` flow against an already constructed API client.
+///
+/// Returns one result per targeted pod, in target order.
+pub async fn handle_with_client(
+    args: ExecArgs,
+    config: &Config,
+    api_client: &LiumApiClient,
+) -> Result<Vec<ExecResult>> {
     // Parse pod targets (split by comma)
     let pod_targets: Vec<String> = args
         .pod_targets
@@ -256,19 +490,50 @@ pub async fn handle(args: ExecArgs, config: &Config) -> Result<()> {
         ));
     }
 
+    if args.parallel == 0 {
+        return Err(CliError::InvalidInput(
+            "--parallel must be at least 1".to_string(),
+        ));
+    }
+    if args.tty {
+        let conflict = if !args.secret.is_empty() {
+            Some("--secret")
+        } else if args.timeout.is_some() {
+            Some("--timeout")
+        } else if is_structured_output() {
+            Some("--output")
+        } else {
+            None
+        };
+        if let Some(flag) = conflict {
+            return Err(CliError::InvalidInput(format!(
+                "--tty cannot be combined with {}",
+                flag
+            )));
+        }
+    }
+
     // Resolve pod targets
-    let resolved_pods = resolve_pod_targets(&api_client, &pod_targets).await?;
+    let resolved_pods = resolve_pod_targets(api_client, &pod_targets).await?;
 
     if resolved_pods.is_empty() {
         return Err(CliError::InvalidInput(
             "No pods found to execute command on".to_string(),
         ));
     }
+    if args.tty && resolved_pods.len() > 1 {
+        return Err(CliError::InvalidInput(
+            "--tty requires exactly one pod target".to_string(),
+        ));
+    }
 
     // Parse environment variables
     let mut env_map = HashMap::new();
     for env_var in &args.env {
         if let Some((key, value)) = env_var.split_once('=') {
+            if is_sensitive_name(key) {
+                register_secret(value);
+            }
             env_map.insert(key.to_string(), value.to_string());
         } else {
             return Err(CliError::InvalidInput(format!(
@@ -278,6 +543,8 @@ pub async fn handle(args: ExecArgs, config: &Config) -> Result<()> {
         }
     }
 
+    let secret_vars = resolve_secret_vars(config, &args.secret)?;
+
     // Determine the command to execute
     let (exec_command, operation_desc) = if let Some(script_path) = &args.script {
         // Read script file
@@ -312,10 +579,11 @@ pub async fn handle(args: ExecArgs, config: &Config) -> Result<()> {
             command.clone()
         };
 
-        let desc = if !env_map.is_empty() {
+        let desc = if !env_map.is_empty() || !secret_vars.is_empty() {
             let env_str = env_map
                 .iter()
                 .map(|(k, v)| format!("{}={}", k, v))
+                .chain(secret_vars.iter().map(ToString::to_string))
                 .collect::<Vec<_>>()
                 .join(", ");
             format!("command with env [{}]: {}", env_str, command)
@@ -329,208 +597,260 @@ pub async fn handle(args: ExecArgs, config: &Config) -> Result<()> {
             "Either a command or --script must be provided".to_string(),
         ));
     };
+    let exec_command = if secret_vars.is_empty() {
+        exec_command
+    } else {
+        stdin_env_command(&exec_command)
+    };
 
-    // Get SSH private key path from config
-    let private_key_path = config.get_ssh_private_key_path()?;
-
-    debug!(
-        "Looking for SSH private key at: {}",
-        private_key_path.display()
-    );
-
-    if !private_key_path.exists() {
-        // Try some common key names
-        let key_dir = private_key_path
-            .parent()
-            .unwrap_or(std::path::Path::new("."));
-        print_warning("Private key not found. Looking for other keys in the same directory...");
-
-        let possible_keys = ["id_rsa", "id_ed25519", "id_ecdsa", "tplr"];
-        for key_name in &possible_keys {
-            let key_path = key_dir.join(key_name);
-            if key_path.exists() {
-                print_info(&format!("Found potential key: {}", key_path.display()));
-            }
+    // Show what we're about to execute; structured output captures the command
+    // output instead of streaming it
+    let capture = is_structured_output();
+    print_info(&format!(
+        "Executing {} on {} pod(s):",
+        operation_desc,
+        resolved_pods.len()
+    ));
+    if !capture {
+        for (pod, original_ref) in &resolved_pods {
+            println!("  - {} ({})", pod.huid, original_ref);
         }
-
-        return Err(CliError::InvalidInput(format!(
-            "SSH private key not found at '{}'. Configure it with: lium config set ssh.key_path /path/to/key.pub",
-            private_key_path.display()
-        )));
+        println!();
     }
 
-    print_success("Successfully connected to SSH");
+    // Resolve every pod's connection details up front, so a pod without SSH details
+    // or a missing key fails the command before anything runs
+    let mut jobs = resolved_pods
+        .iter()
+        .map(|(pod, target_name)| {
+            Ok(PodJob {
+                huid: pod.huid.as_str(),
+                target_name: target_name.as_str(),
+                target: pod_ssh_target(pod)?,
+                auth: pod_ssh_auth(config, pod)?,
+                input: None,
+                prefix: None,
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
 
-    // Also check if the public key exists
-    let public_key_path = private_key_path.with_extension("pub");
-    if !public_key_path.exists() {
-        print_warning(&format!(
-            "SSH public key not found at '{}'. This key should have been added to the pod during creation.",
-            public_key_path.display()
-        ));
+    if args.tty {
+        let job = jobs.remove(0);
+        return Ok(vec![run_in_terminal(job, exec_command).await]);
     }
 
-    // Show what we're about to execute
-    print_info(&format!(
-        "Executing {} on {} pod(s):",
-        operation_desc,
-        resolved_pods.len()
-    ));
-    for (pod, original_ref) in &resolved_pods {
-        println!("  - {} ({})", pod.huid, original_ref);
+    // Every pod gets its own copy of local stdin, so they must all run at once
+    let count = jobs.len();
+    let parallel = if args.stdin { count } else { args.parallel };
+    if args.stdin {
+        for (job, input) in jobs.iter_mut().zip(stdin_fan_out(count)) {
+            job.input = Some(input);
+        }
+    }
+    if parallel > 1 && count > 1 && !capture {
+        let width = jobs.iter().map(|job| job.huid.chars().count()).max();
+        for (index, job) in jobs.iter_mut().enumerate() {
+            job.prefix = Some(pod_output_prefix(job.huid, index, width.unwrap_or(0)));
+        }
     }
-    println!();
 
-    // Execute on each pod
-    let mut success_count = 0;
-    let mut failure_count = 0;
+    // Secrets are sent ahead of the command on stdin, followed by local stdin if any
+    let plan = ExecPlan {
+        command: exec_command,
+        stdin: (!secret_vars.is_empty()).then(|| stdin_env_payload(&secret_vars)),
+        capture,
+        multiple: count > 1,
+        timeout: args.timeout.map(Duration::from_secs),
+        fail_fast: args.fail_fast,
+        cancel: CancelToken::new(),
+    };
 
-    for (i, (pod, target_name)) in resolved_pods.iter().enumerate() {
-        // Show header for multiple pods
-        if resolved_pods.len() > 1 {
-            println!("--- Output from {} ({}) ---", pod.huid, target_name);
+    let runs = jobs.into_iter().enumerate().map(|(index, job)| {
+        let plan = &plan;
+        async move {
+            // Show header for multiple pods run one at a time
+            let headed =
+                count > 1 && job.prefix.is_none() && !capture && !plan.cancel.is_cancelled();
+            if headed {
+                println!("--- Output from {} ({}) ---", job.huid, job.target_name);
+            }
+            let result = run_on_pod(plan, job).await;
+            if headed && index + 1 < count {
+                println!();
+            }
+            (index, result)
         }
+    });
+    let mut results: Vec<(usize, ExecResult)> = stream::iter(runs)
+        .buffer_unordered(parallel)
+        .collect()
+        .await;
+    results.sort_by_key(|(index, _)| *index);
 
-        // Get SSH connection details
-        let ssh_cmd = pod.ssh_cmd.as_ref().ok_or_else(|| {
-            CliError::InvalidInput(format!("Pod {} has no SSH connection info", pod.huid))
-        })?;
+    Ok(results.into_iter().map(|(_, result)| result).collect())
+}
 
-        debug!("SSH command from pod: {}", ssh_cmd);
+/// One pod the command runs on
+struct PodJob<'a> {
+    huid: &'a str,
+    target_name: &'a str,
+    target: SshTarget,
+    auth: SshAuth,
+    /// Local stdin forwarded to this pod with `--stdin`
+    input: Option<mpsc::Receiver<Vec<u8>>>,
+    /// `[huid]` prefix for output lines when pods run in parallel
+    prefix: Option<String>,
+}
 
-        // Use the existing SSH command parser
-        let (host, mut port, user) = parse_ssh_command(ssh_cmd)
-            .map_err(|e| CliError::InvalidInput(format!("Failed to parse SSH command: {}", e)))?;
+/// Runs the command on one pod, streaming (or capturing) its output.
+///
+/// Failures are recorded in the result rather than returned, so one unreachable pod
+/// does not stop the others; with `--fail-fast` they cancel the remaining pods.
+async fn run_on_pod(plan: &ExecPlan, job: PodJob<'_>) -> ExecResult {
+    let PodJob {
+        huid,
+        target_name,
+        target,
+        auth,
+        input,
+        prefix,
+    } = job;
+    let mut result = ExecResult::new(huid, target_name);
+    if plan.cancel.is_cancelled() {
+        result.status = ExecStatus::Skipped;
+        result.error = Some("skipped after another pod failed".to_string());
+        return result;
+    }
+    debug!("Connecting to {} for {}", target, huid);
 
-        debug!(
-            "Parsed SSH details - Host: {}, Port: {}, User: {}",
-            host, port, user
-        );
+    let started = Instant::now();
+    let command = RemoteCommand {
+        command: plan.command.clone(),
+        stdin: plan.stdin.clone().unwrap_or_default().into_bytes(),
+        stream: input,
+        cancel: plan.cancel.clone(),
+        timeout: plan.timeout,
+    };
+    let output = PodOutput {
+        capture: plan.capture,
+        prefix,
+        stdout: String::new(),
+        stderr: String::new(),
+    };
+    let outcome = exec_on_pod(&target, &auth, command, output, PodOutput::line)
+        .await
+        .map(|(code, output)| (code, output.stdout, output.stderr));
+    result.duration_ms = elapsed_ms(started);
 
-        // Workaround: If port is 22 and SSH command contains "-p", extract the actual port
-        if port == 22 && ssh_cmd.contains("-p ") {
-            if let Some(port_start) = ssh_cmd.find("-p ") {
-                let port_str = &ssh_cmd[port_start + 3..];
-                if let Some(port_end) = port_str.find(|c: char| !c.is_numeric()) {
-                    if let Ok(parsed_port) = port_str[..port_end].parse::<u16>() {
-                        print_warning(&format!(
-                            "Parser returned port 22, but found -p {} in command. Using port {}.",
-                            parsed_port, parsed_port
-                        ));
-                        port = parsed_port;
-                    }
-                } else if let Ok(parsed_port) = port_str.trim().parse::<u16>() {
-                    print_warning(&format!(
-                        "Parser returned port 22, but found -p {} in command. Using port {}.",
-                        parsed_port, parsed_port
-                    ));
-                    port = parsed_port;
-                }
-            }
+    match outcome {
+        Ok((code, stdout, stderr)) => {
+            result.stdout = stdout;
+            result.stderr = stderr;
+            record_exit(&mut result, code, plan.multiple);
         }
+        Err(CliError::Utils(UtilsError::Ssh(SshError::Cancelled(_)))) => {
+            result.status = ExecStatus::Cancelled;
+            result.error = Some("cancelled after another pod failed".to_string());
+            print_warning(&format!("Cancelled command on '{}'", huid));
+        }
+        Err(e) => record_error(&mut result, e),
+    }
 
-        // Now run the actual command
-        let mut ssh_command = Command::new("ssh");
-        ssh_command
-            .arg("-i")
-            .arg(&private_key_path)
-            .arg("-o")
-            .arg("StrictHostKeyChecking=no")
-            .arg("-o")
-            .arg("UserKnownHostsFile=/dev/null")
-            .arg("-o")
-            .arg("PasswordAuthentication=no")
-            .arg("-p")
-            .arg(port.to_string())
-            .arg(format!("{}@{}", user, host))
-            .arg(&exec_command)
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .stdin(Stdio::null());
-
-        // Execute the command
-        match ssh_command.spawn() {
-            Ok(mut child) => {
-                // Set up output streaming
-                let stdout = child.stdout.take().expect("Failed to get stdout");
-                let stderr = child.stderr.take().expect("Failed to get stderr");
-
-                let stdout_reader = BufReader::new(stdout);
-                let stderr_reader = BufReader::new(stderr);
-
-                // Stream stdout
-                let stdout_handle = tokio::spawn(async move {
-                    let mut lines = stdout_reader.lines();
-                    while let Ok(Some(line)) = lines.next_line().await {
-                        println!("{}", line);
-                    }
-                });
-
-                // Stream stderr
-                let stderr_handle = tokio::spawn(async move {
-                    let mut lines = stderr_reader.lines();
-                    while let Ok(Some(line)) = lines.next_line().await {
-                        eprintln!("{}", line);
-                    }
-                });
-
-                // Wait for the command to complete
-                let output = child.wait_with_output().await;
-
-                // Wait for output streaming to complete
-                let _ = stdout_handle.await;
-                let _ = stderr_handle.await;
-
-                match output {
-                    Ok(result) => {
-                        if result.status.success() {
-                            if resolved_pods.len() > 1 {
-                                print_success(&format!(
-                                    "Command completed successfully on '{}'",
-                                    pod.huid
-                                ));
-                            }
-                            success_count += 1;
-                        } else {
-                            let exit_code = result.status.code().unwrap_or(-1);
-                            print_error(&format!(
-                                "Command failed on '{}' with exit code: {}",
-                                pod.huid, exit_code
-                            ));
-                            failure_count += 1;
-                        }
-                    }
-                    Err(e) => {
-                        print_error(&format!(
-                            "Failed to execute command on '{}': {}",
-                            pod.huid, e
-                        ));
-                        failure_count += 1;
-                    }
-                }
-            }
-            Err(e) => {
-                print_error(&format!("Failed to start SSH for '{}': {}", pod.huid, e));
-                failure_count += 1;
+    if !result.success && plan.fail_fast {
+        plan.cancel.cancel();
+    }
+    result
+}
+
+/// Output of the command on one pod, either streamed to the terminal or captured
+/// for structured output
+struct PodOutput {
+    capture: bool,
+    /// `[huid]` prefix for streamed lines when pods run in parallel
+    prefix: Option<String>,
+    stdout: String,
+    stderr: String,
+}
+
+impl PodOutput {
+    /// Handles one line printed by the remote command.
+    ///
+    /// Remote output is the user's own data, so only registered secrets (`--secret`
+    /// values, sensitive `--env` values, API keys) are masked; the token-shape
+    /// heuristics used for lium's own messages would mangle lines like
+    /// `max_tokens=2048`.
+    fn line(&mut self, stream: OutputStream, line: &str) {
+        let line = redact_registered(line);
+        if self.capture {
+            let buffer = match stream {
+                OutputStream::Stdout => &mut self.stdout,
+                OutputStream::Stderr => &mut self.stderr,
+            };
+            buffer.push_str(&line);
+            buffer.push('\n');
+        } else {
+            match &self.prefix {
+                Some(prefix) => write_console(stream, &format!("{} {}\n", prefix, line)),
+                None => write_console(stream, &format!("{}\n", line)),
             }
         }
+    }
+}
 
-        // Add separator between pods (except for the last one)
-        if i < resolved_pods.len() - 1 {
-            println!();
-        }
+/// Runs the command for `--tty` in a pseudo-terminal attached to the local terminal
+async fn run_in_terminal(job: PodJob<'_>, command: String) -> ExecResult {
+    let mut result = ExecResult::new(job.huid, job.target_name);
+    debug!("Connecting to {} for {}", job.target, job.huid);
+
+    let started = Instant::now();
+    let outcome = with_session(&job.target, &job.auth, move |session| {
+        session.exec_tty(&command)
+    })
+    .await;
+    result.duration_ms = elapsed_ms(started);
+
+    match outcome {
+        Ok(code) => record_exit(&mut result, code, false),
+        Err(e) => record_error(&mut result, e),
     }
+    result
+}
 
-    // Show summary for multiple pods
-    if resolved_pods.len() > 1 {
-        println!();
-        print_info(&format!(
-            "Execution Summary: {} successful, {} failed",
-            success_count, failure_count
+/// Records the exit status of the command on `result`'s pod
+fn record_exit(result: &mut ExecResult, code: i32, report_success: bool) {
+    result.exit_code = Some(code);
+    result.success = code == 0;
+    if result.success {
+        result.status = ExecStatus::Succeeded;
+        if report_success {
+            print_success(&format!(
+                "Command completed successfully on '{}'",
+                result.huid
+            ));
+        }
+    } else {
+        result.status = ExecStatus::Failed;
+        print_error(&format!(
+            "Command failed on '{}' with exit code: {}",
+            result.huid, code
         ));
     }
+}
 
-    Ok(())
+/// Records why the command could not be run (or finish) on `result`'s pod
+fn record_error(result: &mut ExecResult, error: CliError) {
+    if let CliError::Utils(UtilsError::Ssh(SshError::Timeout(_))) = error {
+        result.status = ExecStatus::TimedOut;
+    }
+    print_error(&format!(
+        "Failed to execute command on '{}': {}",
+        result.huid, error
+    ));
+    result.error = Some(error.to_string());
+}
+
+fn elapsed_ms(started: Instant) -> u64 {
+    started.elapsed().as_millis().try_into().unwrap_or(u64::MAX)
 }
 
 /// Execute a script on pods (helper for common patterns)
@@ -575,6 +895,12 @@ pub async fn execute_script_on_pods(
         command: vec![],
         script: Some(script_path),
         env: env_args,
+        secret: vec![],
+        parallel: 1,
+        fail_fast: false,
+        timeout: None,
+        tty: false,
+        stdin: false,
     };
 
     handle(args, config).await
@@ -618,3 +944,283 @@ pub async fn setup_iota_on_pods(
 
     execute_script_on_pods(pod_targets, "iota", &env_vars, config).await
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::display::{render_output, OutputFormat};
+    use crate::test_support::{
+        api_pod, capture_output, fake_session, test_client, test_config, test_config_with_ssh_key,
+    };
+    use lium_api::InMemoryTransport;
+    use reqwest::Method;
+
+    fn exec_args(targets: &str, command: &str) -> ExecArgs {
+        ExecArgs {
+            pod_targets: targets.to_string(),
+            command: vec![command.to_string()],
+            script: None,
+            env: vec![],
+            secret: vec![],
+            parallel: 1,
+            fail_fast: false,
+            timeout: None,
+            tty: false,
+            stdin: false,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_exec_resolves_targets_with_single_pods_request() {
+        let transport = InMemoryTransport::new().with_pods(&[
+            api_pod(
+                "pod-1",
+                "train",
+                "exec-1",
+                Some("ssh root@10.0.0.1 -p 2222"),
+            ),
+            api_pod(
+                "pod-2",
+                "serve",
+                "exec-2",
+                Some("ssh root@10.0.0.2 -p 2222"),
+            ),
+        ]);
+        let client = test_client(&transport);
+
+        // No SSH key is configured, so the flow stops after resolving targets
+        let result = handle_with_client(
+            exec_args("train,serve", "nvidia-smi"),
+            &test_config(),
+            &client,
+        )
+        .await;
+
+        assert!(result.is_err());
+        assert_eq!(
+            transport.request_log(),
+            vec![(Method::GET, "pods".to_string())]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_exec_rejects_invalid_env_before_running() {
+        let transport =
+            InMemoryTransport::new().with_pods(&[api_pod("pod-1", "train", "exec-1", None)]);
+        let client = test_client(&transport);
+
+        let mut args = exec_args("train", "env");
+        args.env = vec!["NOT_A_PAIR".to_string()];
+        let result = handle_with_client(args, &test_config(), &client).await;
+
+        assert!(matches!(result, Err(CliError::InvalidInput(_))));
+        assert_eq!(
+            transport.request_log(),
+            vec![(Method::GET, "pods".to_string())]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_exec_env_flag_secrets_never_reach_output() {
+        let transport = InMemoryTransport::new().with_pods(&[api_pod(
+            "pod-1",
+            "train",
+            "exec-1",
+            Some("ssh root@10.0.0.1 -p 2222"),
+        )]);
+        let client = test_client(&transport);
+
+        let mut args = exec_args("train", "python train.py");
+        args.env = vec![
+            "HF_TOKEN=flag-token-5e1d07b2".to_string(),
+            "EPOCHS=3000".to_string(),
+        ];
+        let result = handle_with_client(args, &test_config(), &client).await;
+        assert!(result.is_err());
+
+        let line = crate::display::status_line(
+            "ℹ".into(),
+            "Running with HF_TOKEN=flag-token-5e1d07b2 EPOCHS=3000",
+        );
+        assert!(!line.contains("flag-token-5e1d07b2"));
+        assert!(line.contains("EPOCHS=3000"));
+
+        // Run it for real on a pod that echoes its environment back
+        let transport = InMemoryTransport::new().with_pods(&[api_pod(
+            "pod-env-flag-secret",
+            "tune",
+            "exec-3",
+            Some("ssh root@10.0.0.31 -p 2222"),
+        )]);
+        let client = test_client(&transport);
+        let keys = tempfile::tempdir().unwrap();
+        fake_session(
+            "pod-env-flag-secret",
+            &[
+                (OutputStream::Stdout, "HF_TOKEN=flag-token-5e1d07b2"),
+                (OutputStream::Stderr, "login failed for flag-token-5e1d07b2"),
+                (OutputStream::Stdout, "EPOCHS=3000"),
+            ],
+            1,
+        );
+
+        let mut args = exec_args("tune", "env");
+        args.env = vec![
+            "HF_TOKEN=flag-token-5e1d07b2".to_string(),
+            "EPOCHS=3000".to_string(),
+        ];
+        let config = test_config_with_ssh_key(keys.path());
+        let (results, captured) = capture_output(handle_with_client(args, &config, &client)).await;
+
+        assert_eq!(results.unwrap()[0].status, ExecStatus::Failed);
+        assert!(captured.stdout.contains("EPOCHS=3000"));
+        assert!(captured.stderr.contains("login failed for ***"));
+        assert!(captured.log.contains("10.0.0.31"));
+        assert!(!captured.stdout.contains("flag-token-5e1d07b2"));
+        assert!(!captured.stderr.contains("flag-token-5e1d07b2"));
+        assert!(!captured.log.contains("flag-token-5e1d07b2"));
+    }
+
+    #[tokio::test]
+    async fn test_exec_rejects_zero_parallelism() {
+        let transport = InMemoryTransport::new();
+        let client = test_client(&transport);
+
+        let mut args = exec_args("train", "nvidia-smi");
+        args.parallel = 0;
+        let result = handle_with_client(args, &test_config(), &client).await;
+
+        assert!(matches!(result, Err(CliError::InvalidInput(_))));
+        assert!(transport.request_log().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_exec_tty_requires_single_target_without_secrets() {
+        let transport = InMemoryTransport::new().with_pods(&[
+            api_pod(
+                "pod-1",
+                "train",
+                "exec-1",
+                Some("ssh root@10.0.0.1 -p 2222"),
+            ),
+            api_pod(
+                "pod-2",
+                "serve",
+                "exec-2",
+                Some("ssh root@10.0.0.2 -p 2222"),
+            ),
+        ]);
+        let client = test_client(&transport);
+
+        let mut args = exec_args("train", "htop");
+        args.tty = true;
+        args.secret = vec!["HF_TOKEN".to_string()];
+        let err = handle_with_client(args, &test_config(), &client)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("--secret"), "{}", err);
+        assert!(transport.request_log().is_empty());
+
+        let mut args = exec_args("train,serve", "htop");
+        args.tty = true;
+        let err = handle_with_client(args, &test_config(), &client)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("exactly one pod"), "{}", err);
+    }
+
+    #[test]
+    fn test_exit_status_propagates_single_pod_and_aggregates_many() {
+        let result = |status, code: Option<i32>| ExecResult {
+            success: status == ExecStatus::Succeeded,
+            status,
+            exit_code: code,
+            ..ExecResult::new("brave-cat-1a2b", "1")
+        };
+        let exit_code = |results: &[ExecResult]| exit_status(results).err().map(|e| e.exit_code());
+
+        assert_eq!(exit_code(&[result(ExecStatus::Succeeded, Some(0))]), None);
+        assert_eq!(exit_code(&[result(ExecStatus::Failed, Some(3))]), Some(3));
+        assert_eq!(exit_code(&[result(ExecStatus::TimedOut, None)]), Some(124));
+        assert_eq!(exit_code(&[result(ExecStatus::Error, None)]), Some(255));
+        assert_eq!(
+            exit_code(&[
+                result(ExecStatus::Succeeded, Some(0)),
+                result(ExecStatus::Failed, Some(3)),
+            ]),
+            Some(1)
+        );
+        assert_eq!(
+            exit_code(&[
+                result(ExecStatus::Succeeded, Some(0)),
+                result(ExecStatus::Succeeded, Some(0)),
+            ]),
+            None
+        );
+    }
+
+    #[tokio::test]
+    async fn test_exec_remote_output_only_masks_registered_secrets() {
+        let transport = InMemoryTransport::new().with_pods(&[api_pod(
+            "pod-benign-output",
+            "bench",
+            "exec-9",
+            Some("ssh root@10.0.0.9 -p 2222"),
+        )]);
+        let client = test_client(&transport);
+        let keys = tempfile::tempdir().unwrap();
+        fake_session(
+            "pod-benign-output",
+            &[
+                (OutputStream::Stdout, "max_token=2048"),
+                (OutputStream::Stderr, "num_tokens: 5120"),
+                (OutputStream::Stdout, "password_min_len=12"),
+                (OutputStream::Stdout, "HF_TOKEN=bench-token-77a0c3f1"),
+            ],
+            0,
+        );
+
+        let mut args = exec_args("bench", "python bench.py");
+        args.env = vec!["HF_TOKEN=bench-token-77a0c3f1".to_string()];
+        let config = test_config_with_ssh_key(keys.path());
+        let (results, captured) = capture_output(handle_with_client(args, &config, &client)).await;
+
+        assert_eq!(results.unwrap()[0].status, ExecStatus::Succeeded);
+        let stdout = captured.stdout.lines().collect::<Vec<_>>();
+        assert!(stdout.contains(&"max_token=2048"));
+        assert!(stdout.contains(&"password_min_len=12"));
+        assert!(stdout.contains(&"HF_TOKEN=***"));
+        assert!(captured
+            .stderr
+            .lines()
+            .any(|line| line == "num_tokens: 5120"));
+        assert!(!captured.contains("bench-token-77a0c3f1"));
+
+        // Captured output for --output json is only masked the same way
+        let plan = ExecPlan {
+            command: "python bench.py".to_string(),
+            stdin: None,
+            capture: true,
+            multiple: false,
+            timeout: None,
+            fail_fast: false,
+            cancel: CancelToken::new(),
+        };
+        let job = PodJob {
+            huid: "bench",
+            target_name: "bench",
+            target: SshTarget::new("10.0.0.9", 2222, "root").for_pod("pod-benign-output"),
+            auth: SshAuth::default(),
+            input: None,
+            prefix: None,
+        };
+        let result = run_on_pod(&plan, job).await;
+        let document: serde_json::Value =
+            serde_json::from_str(&render_output(&[result], OutputFormat::Json).unwrap()).unwrap();
+        assert_eq!(
+            document[0]["stdout"],
+            "max_token=2048\npassword_min_len=12\nHF_TOKEN=***\n"
+        );
+        assert_eq!(document[0]["stderr"], "num_tokens: 5120\n");
+    }
+}
@@ -1,4 +1,5 @@
 use crate::config::Config;
+use crate::display::{is_structured_output, print_info, print_output};
 use crate::Result;
 
 /// Handles the `fund` command for wallet and funding management operations.
@@ -26,6 +27,7 @@ use crate::Result;
 ///
 /// # Examples
 /// ```rust
+/// # async fn example() -> lium_cli::Result<()> {
 /// use lium_cli::commands::fund::handle;
 /// use lium_cli::{FundCommands, config::Config};
 ///
@@ -39,6 +41,8 @@ use crate::Result;
 ///
 /// // View transaction history
 /// handle(FundCommands::History, &config).await?;
+/// # Ok(())
+/// # }
 /// ```
 ///
 /// # TODO
@@ -104,37 +108,35 @@ pub async fn handle(action: crate::FundCommands, config: &Config) -> Result<()>
 async fn handle_balance(config: &Config) -> Result<()> {
     let api_client = lium_api::LiumApiClient::from_config(config)?;
 
-    println!("💰 Fetching wallet balance...");
+    print_info("💰 Fetching wallet balance...");
 
     match api_client.get_funding_wallets().await {
-        Ok(wallets) => {
+        Ok(wallets) => print_output(&wallets, |wallets| {
             println!("📊 Funding Wallets:");
 
-            if let Some(wallets_array) = wallets.as_array() {
-                if wallets_array.is_empty() {
-                    println!("  No wallets found. Use 'lium fund add' to add a wallet.");
-                } else {
-                    for (i, wallet) in wallets_array.iter().enumerate() {
-                        println!("  {}. Wallet:", i + 1);
-                        if let Some(obj) = wallet.as_object() {
-                            for (key, value) in obj {
-                                println!("     {}: {}", key, value);
-                            }
-                        }
-                        println!();
+            if wallets.is_empty() {
+                println!("  No wallets found. Use 'lium fund add' to add a wallet.");
+            } else {
+                for (i, wallet) in wallets.iter().enumerate() {
+                    println!("  {}. Wallet: {}", i + 1, wallet.wallet_hash);
+                    if let Some(created_at) = &wallet.created_at {
+                        println!("     Added: {}", created_at);
+                    }
+                    for (key, value) in &wallet.extra {
+                        println!("     {}: {}", key, value);
                     }
+                    println!();
                 }
-            } else {
-                println!("  Unexpected wallet data format");
             }
-        }
+        }),
+        // Scripts need a failing exit code; the table view keeps the friendly hint
+        Err(e) if is_structured_output() => Err(e.into()),
         Err(e) => {
             println!("❌ Failed to fetch wallet balance: {}", e);
             println!("💡 Make sure you have added a wallet with 'lium fund add'");
+            Ok(())
         }
     }
-
-    Ok(())
 }
 
 /// Handle fund add command
@@ -173,12 +175,16 @@ async fn handle_history(config: &Config) -> Result<()> {
     match api_client.get_users_me().await {
         Ok(user_info) => {
             println!("👤 User Information:");
-            if let Some(obj) = user_info.as_object() {
-                for (key, value) in obj {
-                    println!("  {}: {}", key, value);
-                }
-            } else {
-                println!("  Unexpected user data format");
+            println!("  ID: {}", user_info.id);
+            if let Some(email) = &user_info.email {
+                println!("  Email: {}", email);
+            }
+            if let Some(balance) = user_info.balance {
+                println!("  Balance: {:.2}", balance);
+            }
+            println!("  App ID: {}", user_info.app_id);
+            for (key, value) in &user_info.extra {
+                println!("  {}: {}", key, value);
             }
         }
         Err(e) => {
@@ -1,15 +1,19 @@
 use crate::{
     config::Config,
-    display::{print_error, print_info, print_success, print_warning},
+    display::{
+        display_template_details, print_error, print_info, print_output, print_success,
+        print_warning, prompt_confirm, prompt_input,
+    },
     CliError, Result,
 };
 use dialoguer::{Input, Password};
-use lium_api::LiumApiClient;
-use lium_utils::build_and_push_image;
+use lium_api::{ApiError, HttpError, LiumApiClient};
+use lium_core::TemplateUpdate;
+use lium_utils::{build_and_push_image, register_secret};
 use std::path::{Path, PathBuf};
 use tokio::time::{sleep, Duration};
 
-/// Handle image subcommands (list, create, delete)
+/// Handle image subcommands (list, create, show, edit, delete)
 pub async fn handle_subcommand(action: crate::ImageCommands, config: &Config) -> Result<()> {
     use crate::ImageCommands;
 
@@ -18,7 +22,13 @@ pub async fn handle_subcommand(action: crate::ImageCommands, config: &Config) ->
     match action {
         ImageCommands::List => handle_list(&api_client).await,
         ImageCommands::Create { name, image, tag } => handle_create(name, image, tag, config).await,
-        ImageCommands::Delete { id } => handle_delete(&api_client, id).await,
+        ImageCommands::Show { id } => handle_show(&api_client, &id).await,
+        ImageCommands::Edit {
+            id,
+            name,
+            description,
+        } => handle_edit(&api_client, &id, name, description).await,
+        ImageCommands::Delete { id, yes } => handle_delete(&api_client, &id, yes).await,
     }
 }
 
@@ -87,6 +97,9 @@ async fn wait_for_image_verification_by_name(
     const MAX_ATTEMPTS: u32 = 30; // 5 minutes with 10-second intervals
     const POLL_INTERVAL: Duration = Duration::from_secs(10);
 
+    // Each poll must see the latest verification status, not a cached listing
+    let api_client = &api_client.refreshing();
+
     let start_time = std::time::Instant::now();
 
     for attempt in 1..=MAX_ATTEMPTS {
@@ -266,79 +279,112 @@ pub async fn handle_build(
 async fn handle_list(api_client: &LiumApiClient) -> Result<()> {
     print_info("📋 Fetching available templates...");
 
-    match api_client.get_templates().await {
-        Ok(templates) => {
-            if templates.is_empty() {
-                println!("No templates found.");
-                return Ok(());
-            }
+    let templates = match api_client.get_templates().await {
+        Ok(templates) => templates,
+        Err(e) => {
+            print_error(&format!("❌ Failed to fetch templates: {}", e));
+            return Err(e.into());
+        }
+    };
+
+    print_output(&templates, |templates| {
+        if templates.is_empty() {
+            println!("No templates found.");
+            return;
+        }
+
+        println!("\n🎯 Available Templates:");
+        println!(
+            "{:<38} {:<25} {:<20} {:<12}",
+            "ID", "Name", "Image", "Status"
+        );
+        println!("{}", "─".repeat(95));
+
+        for template in templates {
+            let image_display = template
+                .docker_image
+                .split('/')
+                .next_back()
+                .unwrap_or(&template.docker_image);
+
+            let status_display = match template.status.as_deref() {
+                Some("VERIFY_SUCCESS") => "✅ verified",
+                Some("VERIFY_FAILED") => "❌ failed",
+                Some("VERIFYING") => "🔄 verifying",
+                Some(status) => status,
+                None => "pending",
+            };
 
-            println!("\n🎯 Available Templates:");
             println!(
                 "{:<38} {:<25} {:<20} {:<12}",
-                "ID", "Name", "Image", "Status"
+                template.id, template.name, image_display, status_display
             );
-            println!("{}", "─".repeat(95));
-
-            for template in templates {
-                let image_display = template
-                    .docker_image
-                    .split('/')
-                    .last()
-                    .unwrap_or(&template.docker_image);
-
-                let status_display = match template.status.as_deref() {
-                    Some("VERIFY_SUCCESS") => "✅ verified",
-                    Some("VERIFY_FAILED") => "❌ failed",
-                    Some("VERIFYING") => "🔄 verifying",
-                    Some(status) => status,
-                    None => "pending",
-                };
-
-                println!(
-                    "{:<38} {:<25} {:<20} {:<12}",
-                    template.id, template.name, image_display, status_display
-                );
-            }
+        }
 
-            println!("\n💡 To use a template: lium up <executor> --image <template-id>");
-            println!("💡 To set default: lium config set template.default_id <template-id>");
+        println!("\n💡 To use a template: lium up <executor> --image <template-id>");
+        println!("💡 To set default: lium config set template.default_id <template-id>");
+    })
+}
+
+/// Fetches a template, turning a 404 into a friendly "not found" error
+async fn fetch_template(api_client: &LiumApiClient, id: &str) -> Result<lium_core::TemplateInfo> {
+    match api_client.get_template(id).await {
+        Ok(template) => Ok(template),
+        Err(ApiError::Http(HttpError::HttpError { status: 404, .. })) => {
+            print_error(&format!("❌ Template not found: {}", id));
+            Err(CliError::NotFound(format!("Template {} not found", id)))
         }
         Err(e) => {
-            print_error(&format!("❌ Failed to fetch templates: {}", e));
-            return Err(e.into());
+            print_error(&format!("❌ Failed to fetch template: {}", e));
+            Err(e.into())
         }
     }
+}
 
+/// Handle showing a single template
+async fn handle_show(api_client: &LiumApiClient, id: &str) -> Result<()> {
+    let template = fetch_template(api_client, id).await?;
+    display_template_details(&template);
     Ok(())
 }
 
-/// Handle deleting a template
-async fn handle_delete(api_client: &LiumApiClient, id: String) -> Result<()> {
-    print_info(&format!("🗑️  Deleting template: {}", id));
+/// Handle editing a template's name or description
+///
+/// With no flags, prompts for a new description using the current one as the default.
+async fn handle_edit(
+    api_client: &LiumApiClient,
+    id: &str,
+    name: Option<String>,
+    description: Option<String>,
+) -> Result<()> {
+    let template = fetch_template(api_client, id).await?;
 
-    // Check if template exists
-    match api_client.get_templates().await {
-        Ok(templates) => {
-            let template_exists = templates.iter().any(|t| t.id == id);
-            if !template_exists {
-                print_error(&format!("❌ Template not found: {}", id));
-                return Err(CliError::NotFound(format!("Template {} not found", id)));
-            }
-        }
-        Err(e) => {
-            print_error(&format!("❌ Failed to check templates: {}", e));
-            return Err(e.into());
-        }
+    let mut update = TemplateUpdate { name, description };
+    if update.is_empty() {
+        let current = template.description.as_deref().unwrap_or("");
+        update = update.with_description(prompt_input("Description", Some(current))?);
     }
 
-    // TODO: Implement delete_template in API client when available
-    print_warning("⚠️  Template deletion is not yet implemented in the API");
-    print_info("💡 Contact support to remove unused templates");
+    let updated = api_client.update_template(id, &update).await?;
+    print_success(&format!("✅ Template {} updated", updated.id));
+    display_template_details(&updated);
+    Ok(())
+}
 
-    Err(CliError::OperationFailed(
-        "Template deletion not yet implemented".to_string(),
-    ))
+/// Handle deleting a template
+async fn handle_delete(api_client: &LiumApiClient, id: &str, yes: bool) -> Result<()> {
+    let template = fetch_template(api_client, id).await?;
+    display_template_details(&template);
+
+    if !yes && !prompt_confirm(&format!("Delete template '{}'?", template.name), false)? {
+        println!("Operation cancelled.");
+        return Ok(());
+    }
+
+    print_info(&format!("🗑️  Deleting template: {}", id));
+    api_client.delete_template(id).await?;
+    print_success(&format!("✅ Template {} deleted", id));
+    Ok(())
 }
 
 /// Get Docker credentials from config or prompt user
@@ -369,11 +415,10 @@ async fn get_docker_credentials(config: &Config) -> Result<(String, String)> {
             "Username and token cannot be empty".to_string(),
         ));
     }
+    register_secret(&token);
 
     // Store credentials for future use
-    let mut config_mut = Config::new()?;
-    config_mut.set_docker_credentials(&username, &token)?;
-    config_mut.save()?;
+    Config::new()?.update(|config| config.set_docker_credentials(&username, &token))?;
 
     print_success("💾 Docker credentials saved for future use");
 
@@ -388,6 +433,9 @@ async fn wait_for_image_verification(
     const MAX_ATTEMPTS: u32 = 30; // 5 minutes with 10-second intervals
     const POLL_INTERVAL: Duration = Duration::from_secs(10);
 
+    // Each poll must see the latest verification status, not a cached listing
+    let api_client = &api_client.refreshing();
+
     let start_time = std::time::Instant::now();
 
     for attempt in 1..=MAX_ATTEMPTS {
@@ -444,3 +492,65 @@ async fn wait_for_image_verification(
             .to_string(),
     ))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::test_client;
+    use lium_api::InMemoryTransport;
+    use reqwest::{Method, StatusCode};
+    use serde_json::json;
+
+    fn template_json(description: &str) -> serde_json::Value {
+        json!({
+            "id": "tmpl-1",
+            "name": "pytorch",
+            "docker_image": "pytorch/pytorch",
+            "docker_image_tag": "2.3",
+            "docker_image_digest": "sha256:abc",
+            "status": "VERIFY_SUCCESS",
+            "description": description,
+        })
+    }
+
+    #[tokio::test]
+    async fn test_edit_and_delete_template() {
+        let transport = InMemoryTransport::new()
+            .with_json(Method::GET, "templates/tmpl-1", template_json("old"))
+            .with_json(Method::PUT, "templates/tmpl-1", template_json("new"))
+            .with_json(Method::DELETE, "templates/tmpl-1", json!({}));
+        let client = test_client(&transport);
+
+        handle_edit(&client, "tmpl-1", None, Some("new".to_string()))
+            .await
+            .unwrap();
+        assert_eq!(
+            transport.requests()[1].body,
+            Some(json!({"description": "new"}))
+        );
+
+        handle_delete(&client, "tmpl-1", true).await.unwrap();
+        assert_eq!(
+            transport.request_log()[2..],
+            [
+                (Method::GET, "templates/tmpl-1".to_string()),
+                (Method::DELETE, "templates/tmpl-1".to_string()),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_show_missing_template_is_not_found() {
+        let transport = InMemoryTransport::new().with_response(
+            Method::GET,
+            "templates/missing",
+            lium_api::HttpResponse::new(StatusCode::NOT_FOUND, "not found"),
+        );
+        let client = test_client(&transport);
+
+        assert!(matches!(
+            handle_show(&client, "missing").await,
+            Err(CliError::NotFound(_))
+        ));
+    }
+}
@@ -1,4 +1,4 @@
-use crate::{config::Config, CliError, Result};
+use crate::{config::Config, secrets::secret_ref, CliError, Result};
 use dialoguer::{Input, Password, Select};
 use log::debug;
 use std::path::Path;
@@ -88,10 +88,13 @@ use tokio::time::{timeout, Duration};
 ///
 /// # Examples
 /// ```rust
+/// # async fn example() -> lium_cli::Result<()> {
 /// use lium_cli::commands::init::handle;
 ///
 /// // Run interactive setup
 /// handle().await?;
+/// # Ok(())
+/// # }
 /// ```
 ///
 /// # Setup Output
@@ -141,6 +144,7 @@ pub async fn handle() -> Result<()> {
     let mut config = Config::new()?; // Use synchronous version to avoid nested async issues
 
     // Get API key
+    let mut reused_key = false;
     let api_key = if let Some(existing_key) = config.get_api_key()? {
         let use_existing = Select::new()
             .with_prompt("API key already configured. Use existing key?")
@@ -149,6 +153,7 @@ pub async fn handle() -> Result<()> {
             .interact()?;
 
         if use_existing == 0 {
+            reused_key = true;
             existing_key
         } else {
             get_api_key_from_user()?
@@ -157,7 +162,14 @@ pub async fn handle() -> Result<()> {
         get_api_key_from_user()?
     };
 
-    config.set_api_key(&api_key)?;
+    // Keep a `secret:` reference rather than replacing it with the key it resolves to
+    let stored_as_secret = config
+        .api()
+        .api_key
+        .is_some_and(|key| secret_ref(&key).is_some());
+    if !(reused_key && stored_as_secret) {
+        config.set_api_key(&api_key)?;
+    }
 
     // Get SSH key path
     let ssh_key_path = if let Some(existing_path) = config.get_ssh_public_key_path()? {
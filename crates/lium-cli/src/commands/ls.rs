@@ -2,7 +2,7 @@ use crate::{
     config::Config,
     display::{
         display_executors_compact, display_executors_detailed, display_executors_table,
-        display_gpu_summary,
+        display_gpu_summary, print_info, print_offline_banner, print_output,
     },
     CliError, Result,
 };
@@ -10,7 +10,7 @@ use clap::Args;
 use lium_api::LiumApiClient;
 use lium_core::{
     filter_by_availability, filter_by_gpu_type, filter_by_price_range, find_pareto_optimal,
-    group_by_gpu_type, parse_price_range, sort_by_gpu_count, sort_by_price,
+    group_by_gpu_type, parse_price_range, sort_by_gpu_count, sort_by_price, ExecutorInfo,
 };
 use log::debug;
 
@@ -310,6 +310,7 @@ pub enum SortBy {
 ///
 /// # Examples
 /// ```rust
+/// # async fn example() -> lium_cli::Result<()> {
 /// use lium_cli::commands::ls::{handle, LsArgs, DisplayFormat, SortBy};
 /// use lium_cli::config::Config;
 ///
@@ -333,6 +334,8 @@ pub enum SortBy {
 ///
 /// let config = Config::new()?;
 /// handle(args, &config).await?;
+/// # Ok(())
+/// # }
 /// ```
 ///
 /// # TODO
@@ -352,10 +355,10 @@ pub async fn handle(args: LsArgs, config: &Config) -> Result<()> {
             return Err(CliError::Api(e));
         }
     };
+    print_offline_banner(&client, "executors");
 
     if executors.is_empty() {
-        println!("No executors found.");
-        return Ok(());
+        return print_output(&executors, |_| println!("No executors found."));
     }
 
     debug!("Successfully fetched {} executors", executors.len());
@@ -368,6 +371,14 @@ pub async fn handle(args: LsArgs, config: &Config) -> Result<()> {
         debug!("Filtering by GPU type: {}", gpu_type);
         executors = filter_by_gpu_type(&executors, gpu_type);
         debug!("After GPU filter: {} executors", executors.len());
+    } else if let Some(gpu_types) = config.defaults().gpu_types.filter(|t| !t.is_empty()) {
+        // Preferred GPU types pinned in config (e.g. a project's .lium.toml)
+        debug!("Filtering by preferred GPU types: {:?}", gpu_types);
+        executors = filter_by_preferred_gpu_types(&executors, &gpu_types);
+        print_info(&format!(
+            "Showing preferred GPU types from config: {} (pass a GPU type to override)",
+            gpu_types.join(", ")
+        ));
     }
 
     if let Some(price_range) = &args.price {
@@ -423,30 +434,30 @@ pub async fn handle(args: LsArgs, config: &Config) -> Result<()> {
     // Export if requested
     if let Some(export_path) = &args.export {
         export_results(&executors, export_path)?;
-        println!("Results exported to: {}", export_path);
+        print_info(&format!("Results exported to: {}", export_path));
     }
 
-    // Display results based on format
-    match args.format {
-        DisplayFormat::Table => {
-            display_executors_table(&executors, args.pareto);
-        }
-        DisplayFormat::Compact => {
-            display_executors_compact(&executors);
-        }
-        DisplayFormat::Detailed => {
-            display_executors_detailed(&executors);
+    // Display results based on format; --output json/yaml/ndjson prints the executors
+    print_output(&executors, |executors| {
+        match args.format {
+            DisplayFormat::Table => {
+                display_executors_table(executors, args.pareto);
+            }
+            DisplayFormat::Compact => {
+                display_executors_compact(executors);
+            }
+            DisplayFormat::Detailed => {
+                display_executors_detailed(executors);
+            }
+            DisplayFormat::Summary => {
+                let gpu_groups = group_by_gpu_type(executors);
+                display_gpu_summary(&gpu_groups);
+            }
         }
-        DisplayFormat::Summary => {
-            let gpu_groups = group_by_gpu_type(&executors);
-            display_gpu_summary(&gpu_groups);
-        }
-    }
-
-    // Show filter summary
-    show_filter_summary(&args);
 
-    Ok(())
+        // Show filter summary
+        show_filter_summary(&args);
+    })
 }
 
 fn apply_sorting(executors: &mut Vec<lium_core::ExecutorInfo>, args: &LsArgs) {
@@ -598,6 +609,22 @@ fn export_results(executors: &[lium_core::ExecutorInfo], export_path: &str) -> R
     Ok(())
 }
 
+/// Keeps executors matching any of the preferred GPU types
+fn filter_by_preferred_gpu_types(
+    executors: &[ExecutorInfo],
+    gpu_types: &[String],
+) -> Vec<ExecutorInfo> {
+    executors
+        .iter()
+        .filter(|executor| {
+            gpu_types.iter().any(|gpu_type| {
+                !filter_by_gpu_type(std::slice::from_ref(*executor), gpu_type).is_empty()
+            })
+        })
+        .cloned()
+        .collect()
+}
+
 fn show_filter_summary(args: &LsArgs) {
     let mut filters = Vec::new();
 
@@ -704,6 +731,20 @@ mod tests {
         assert!(filtered.iter().all(|e| e.gpu_type.contains("RTX4090")));
     }
 
+    #[test]
+    fn test_filter_by_preferred_gpu_types() {
+        let executors = vec![
+            create_test_executor("1", "RTX4090", 1.0, true),
+            create_test_executor("2", "H100", 2.0, true),
+            create_test_executor("3", "A100", 1.5, true),
+        ];
+
+        let preferred = ["H100".to_string(), "RTX4090".to_string()];
+        let filtered = filter_by_preferred_gpu_types(&executors, &preferred);
+        let huids: Vec<&str> = filtered.iter().map(|e| e.huid.as_str()).collect();
+        assert_eq!(huids, vec!["1", "2"]);
+    }
+
     #[test]
     fn test_filter_by_availability() {
         let executors = vec![
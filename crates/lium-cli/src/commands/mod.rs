@@ -8,6 +8,7 @@ pub mod ls;
 pub mod ps;
 pub mod rsync;
 pub mod scp;
+pub mod secrets;
 pub mod ssh;
 pub mod theme;
 pub mod up;
@@ -1,10 +1,16 @@
 use crate::config::Config;
-use crate::display::{display_pod_details, display_pods_table};
+use crate::display::{
+    display_pod_details, display_pods_table, format_pod_event, print_error, print_info,
+    print_offline_banner, print_output,
+};
 use crate::helpers::{resolve_pod_targets, store_pod_selection};
 use crate::Result;
 use clap::Args;
-use lium_api::LiumApiClient;
-use log::{debug, info};
+use futures::StreamExt;
+use lium_api::{Lium, LiumApiClient};
+use lium_core::PodInfo;
+use log::{debug, trace};
+use std::time::Duration;
 
 /// Command-line arguments for the `ps` command that lists and inspects running pods.
 ///
@@ -31,6 +37,10 @@ use log::{debug, info};
 /// lium ps pod1,pod2
 /// lium ps 1,3       # by index
 /// lium ps all       # details for all pods
+///
+/// # Watch pods and print changes as they happen
+/// lium ps --watch
+/// lium ps --watch --interval 10
 /// ```
 ///
 /// # Pod Target Resolution
@@ -46,7 +56,6 @@ use log::{debug, info};
 /// to include stopped, terminated, or failed pods in the listing.
 ///
 /// # TODO
-/// - Add real-time status updates and pod monitoring
 /// - Implement pod resource usage tracking
 /// - Add pod log streaming capabilities
 /// - Support for pod grouping and ta
@@ -97,6 +106,18 @@ pub struct PsArgs {
     /// Examples: "RTX4090", "H100", "A100"
     #[arg(short, long)]
     pub gpu: Option<String>,
+
+    /// Keep running and print pod changes as they happen.
+    ///
+    /// Prints a line whenever a pod is added or removed, changes status, or
+    /// becomes reachable over SSH. Existing pods are printed as added when the
+    /// watch starts. Press Ctrl+C to stop.
+    #[arg(short, long, conflicts_with = "targets")]
+    pub watch: bool,
+
+    /// Seconds between pod listings in watch mode.
+    #[arg(long, default_value_t = 5, requires = "watch", value_name = "SECONDS")]
+    pub interval: u64,
 }
 
 /// Handles the `ps` command to list active pods and show detailed pod information.
@@ -163,6 +184,7 @@ pub struct PsArgs {
 ///
 /// # Examples
 /// ```rust
+/// # async fn example(config: lium_cli::Config) -> lium_cli::Result<()> {
 /// use lium_cli::commands::ps::{handle, PsArgs};
 /// use lium_cli::config::Config;
 ///
@@ -172,6 +194,8 @@ pub struct PsArgs {
 ///     all: false,
 ///     status: None,
 ///     gpu: None,
+///     watch: false,
+///     interval: 5,
 /// };
 /// handle(args, &config).await?;
 ///
@@ -181,6 +205,8 @@ pub struct PsArgs {
 ///     all: false,
 ///     status: None,
 ///     gpu: None,
+///     watch: false,
+///     interval: 5,
 /// };
 /// handle(args, &config).await?;
 ///
@@ -190,8 +216,12 @@ pub struct PsArgs {
 ///     all: true,
 ///     status: Some("running".to_string()),
 ///     gpu: Some("RTX4090".to_string()),
+///     watch: false,
+///     interval: 5,
 /// };
 /// handle(args, &config).await?;
+/// # Ok(())
+/// # }
 /// ```
 ///
 /// # Output Format
@@ -225,8 +255,16 @@ pub struct PsArgs {
 ///   Updated: 2024-01-15 16:45:00 UTC
 /// ```
 ///
+/// ## Watch Mode (--watch)
+/// Polls the pod list every `--interval` seconds and prints one line per change:
+/// ```text
+/// [14:03:12] + brave-cat-42 added (PENDING)
+/// [14:03:42] ~ brave-cat-42 PENDING → RUNNING
+/// [14:03:42] 🔑 brave-cat-42 ready: ssh -p 2222 root@gpu-host.example.com
+/// [14:20:05] - brave-cat-42 removed
+/// ```
+///
 /// # TODO
-/// - Add real-time status updates with periodic refresh
 /// - Implement pod resource usage monitoring (CPU, memory, GPU utilization)
 /// - Add pod log viewing and streaming capabilities
 /// - Support for pod grouping and custom tagging
@@ -234,66 +272,64 @@ pub struct PsArgs {
 /// - Implement cost tracking and billing information
 pub async fn handle(args: PsArgs, config: &Config) -> Result<()> {
     let client = LiumApiClient::from_config(config)?;
+    handle_with_client(args, &client).await
+}
+
+/// Runs the `ps` flow against an already constructed API client.
+pub async fn handle_with_client(args: PsArgs, client: &LiumApiClient) -> Result<()> {
+    if args.watch {
+        return watch_pods(client, Duration::from_secs(args.interval.max(1))).await;
+    }
 
     // If specific targets are provided, show details for those pods
     if !args.targets.is_empty() {
-        let resolved_pods = resolve_pod_targets(&client, &args.targets).await?;
-
-        for (pod, identifier) in resolved_pods {
-            println!("Pod details for {} ({}):", pod.huid, identifier);
-            display_pod_details(&pod);
-            println!();
-        }
-        return Ok(());
+        let resolved_pods = resolve_pod_targets(client, &args.targets).await?;
+        let pods: Vec<PodInfo> = resolved_pods.iter().map(|(pod, _)| pod.clone()).collect();
+
+        return print_output(&pods, |_| {
+            for (pod, identifier) in &resolved_pods {
+                println!("Pod details for {} ({}):", pod.huid, identifier);
+                display_pod_details(pod);
+                println!();
+            }
+        });
     }
 
     // Fetch all pods from API for listing
     let mut pods = client.get_pods().await?;
+    print_offline_banner(client, "pods");
 
-    println!("DEBUG: Total pods fetched from API: {}", pods.len());
-
-    // Debug: Print all pod statuses
+    debug!("Fetched {} pods from the API", pods.len());
     for (i, pod) in pods.iter().enumerate() {
-        println!(
-            "DEBUG: Pod {} - HUID: {}, Status: '{}'",
-            i, pod.huid, pod.status
-        );
+        trace!("Pod {} - HUID: {}, status: '{}'", i, pod.huid, pod.status);
     }
 
     if pods.is_empty() {
-        println!("No pods found. Use 'lium up' to create a pod.");
-        return Ok(());
+        return print_output(&pods, |_| {
+            println!("No pods found. Use 'lium up' to create a pod.")
+        });
     }
 
     // Apply filters
     if !args.all {
-        println!("DEBUG: Filtering pods (not using --all flag)");
-        println!("DEBUG: Looking for statuses: running, starting, active, ready");
-
-        // Debug: Check what pods are being filtered out
         let before_count = pods.len();
-
         pods.retain(|pod| {
-            let status_lower = pod.status.to_lowercase();
             let keep = matches!(
-                status_lower.as_str(),
+                pod.status.to_lowercase().as_str(),
                 "running" | "starting" | "active" | "ready"
             );
-
             if !keep {
-                println!(
-                    "DEBUG: Filtering out pod {} with status '{}'",
+                debug!(
+                    "Filtering out pod {} with status '{}'",
                     pod.huid, pod.status
                 );
             }
-
             keep
         });
-
-        println!(
-            "DEBUG: Filtered from {} to {} pods",
-            before_count,
-            pods.len()
+        debug!(
+            "Kept {} of {} pods with an active status (use --all to show all)",
+            pods.len(),
+            before_count
         );
     }
 
@@ -314,95 +350,29 @@ pub async fn handle(args: PsArgs, config: &Config) -> Result<()> {
     }
 
     if pods.is_empty() {
-        println!("No pods found matching your criteria.");
-        println!("Hint: Use 'lium ps --all' to see all pods regardless of status.");
-        return Ok(());
+        return print_output(&pods, |_| {
+            println!("No pods found matching your criteria.");
+            println!("Hint: Use 'lium ps --all' to see all pods regardless of status.");
+        });
     }
 
     // Store pod selection for index-based references in other commands
     store_pod_selection(&pods)?;
 
-    // Debug: Analyze pod data structure before display
-    println!("\n=== DEBUG: Analyzing Pod Data ===");
-    for (i, pod) in pods.iter().take(1).enumerate() {
-        // Just analyze first pod
-        println!("\nPod {} data analysis:", i);
-
-        // Check what's in executor
-        println!("\nExecutor top-level keys:");
-        if let Some(obj) = pod.executor.as_object() {
-            for (key, value) in obj {
-                match value {
-                    serde_json::Value::Object(_) => println!("  {} = [object]", key),
-                    serde_json::Value::Array(arr) => {
-                        println!("  {} = [array with {} items]", key, arr.len())
-                    }
-                    serde_json::Value::String(s) => println!("  {} = \"{}\"", key, s),
-                    serde_json::Value::Number(n) => println!("  {} = {}", key, n),
-                    serde_json::Value::Bool(b) => println!("  {} = {}", key, b),
-                    serde_json::Value::Null => println!("  {} = null", key),
-                }
-            }
-        }
-
-        // Look for GPU info in different places
-        println!("\nSearching for GPU info:");
-
-        // Check executor.gpu_type (what display_pods_table expects)
-        if let Some(gpu_type) = pod.executor.get("gpu_type") {
-            println!("  Found executor.gpu_type: {:?}", gpu_type);
-        } else {
-            println!("  executor.gpu_type NOT FOUND");
-        }
-
-        // Check executor.gpu_count
-        if let Some(gpu_count) = pod.executor.get("gpu_count") {
-            println!("  Found executor.gpu_count: {:?}", gpu_count);
-        } else {
-            println!("  executor.gpu_count NOT FOUND");
-        }
-
-        // Check executor.specs
-        if let Some(specs) = pod.executor.get("specs") {
-            println!("  Found executor.specs");
-
-            // Check specs.gpu
-            if let Some(gpu) = specs.get("gpu") {
-                println!("    Found specs.gpu:");
-                if let Some(count) = gpu.get("count") {
-                    println!("      gpu.count = {}", count);
-                }
-                if let Some(details) = gpu.get("details") {
-                    if let Some(arr) = details.as_array() {
-                        println!("      gpu.details has {} GPUs", arr.len());
-                        if let Some(first) = arr.first() {
-                            if let Some(name) = first.get("name") {
-                                println!("      First GPU name: {}", name);
-                            }
-                        }
-                    }
-                }
-            }
-        }
-
-        // Check for machine_name
-        if let Some(machine_name) = pod.executor.get("machine_name") {
-            println!("  Found executor.machine_name: {:?}", machine_name);
-        }
-
-        // Check created_at
-        println!("\nTiming info:");
-        println!("  pod.created_at: {:?}", pod.created_at);
-        if let Some(uptime) = pod.executor.get("uptime_in_minutes") {
-            println!("  executor.uptime_in_minutes: {:?}", uptime);
-        }
+    // The raw executor data is what the table columns are extracted from
+    if let Some(pod) = pods.first() {
+        trace!("Executor data for pod {}: {}", pod.huid, pod.executor);
+        trace!("Pod {} created_at: {:?}", pod.huid, pod.created_at);
     }
-    println!("=== END DEBUG ===\n");
 
-    // Display pods table
-    display_pods_table(&pods);
+    print_output(&pods, |pods| {
+        display_pods_table(pods);
+        display_pods_summary(pods);
+    })
+}
 
-    // Show summary
+/// Prints the status counts, hourly cost and usage hint below the pods table
+fn display_pods_summary(pods: &[PodInfo]) {
     let running_count = pods
         .iter()
         .filter(|p| matches!(p.status.to_lowercase().as_str(), "running" | "active"))
@@ -446,13 +416,47 @@ pub async fn handle(args: PsArgs, config: &Config) -> Result<()> {
     // Usage hint
     println!();
     println!("Use 'lium ps <pod_target>' for detailed info, or 'lium exec <pod_target> <command>' to run commands.");
+}
+
+/// Prints pod change events until interrupted with Ctrl+C.
+///
+/// Poll failures are reported and the watch carries on, so a flaky connection does not
+/// end a long-running watch.
+async fn watch_pods(client: &LiumApiClient, interval: Duration) -> Result<()> {
+    print_info(&format!(
+        "Watching pods every {}s (press Ctrl+C to stop)",
+        interval.as_secs()
+    ));
+
+    let lium = Lium::from_client(client.clone());
+    let mut events = Box::pin(lium.watch_pods(interval));
+
+    loop {
+        tokio::select! {
+            event = events.next() => match event {
+                Some(Ok(event)) => {
+                    print_output(&event, |event| println!("{}", format_pod_event(event)))?
+                }
+                Some(Err(e)) => print_error(&format!("Failed to list pods: {}", e)),
+                None => break,
+            },
+            _ = tokio::signal::ctrl_c() => {
+                println!();
+                break;
+            }
+        }
+    }
 
     Ok(())
 }
 
 #[cfg(test)]
 mod tests {
+    use super::*;
+    use crate::test_support::{api_pod, test_client};
+    use lium_api::InMemoryTransport;
     use lium_core::PodInfo;
+    use reqwest::Method;
     use std::collections::HashMap;
 
     fn create_test_pod(huid: &str, status: &str, gpu_type: &str) -> PodInfo {
@@ -513,9 +517,74 @@ mod tests {
             .collect();
         assert_eq!(rtx_pods.len(), 2);
     }
+
+    #[test]
+    fn test_format_pod_event_describes_change() {
+        colored::control::set_override(false);
+        let pod = create_test_pod("brave-cat-42", "RUNNING", "RTX4090");
+
+        let line = format_pod_event(&lium_api::PodEvent::StatusChanged {
+            pod: pod.clone(),
+            from: "PENDING".to_string(),
+            to: "RUNNING".to_string(),
+        });
+        assert!(line.ends_with("~ brave-cat-42 PENDING → RUNNING"));
+
+        let line = format_pod_event(&lium_api::PodEvent::SshReady(pod));
+        assert!(line.ends_with("brave-cat-42 ready: ssh user@brave-cat-42.example.com"));
+    }
+
+    #[tokio::test]
+    async fn test_ps_details_fetches_pods_once() {
+        let transport = InMemoryTransport::new().with_pods(&[
+            api_pod(
+                "pod-1",
+                "train",
+                "exec-1",
+                Some("ssh root@10.0.0.1 -p 2222"),
+            ),
+            api_pod("pod-2", "serve", "exec-2", None),
+        ]);
+        let client = test_client(&transport);
+
+        let args = PsArgs {
+            targets: vec!["train".to_string()],
+            all: false,
+            status: None,
+            gpu: None,
+            watch: false,
+            interval: 5,
+        };
+        handle_with_client(args, &client).await.unwrap();
+
+        assert_eq!(
+            transport.request_log(),
+            vec![(Method::GET, "pods".to_string())]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_ps_unknown_target_fails() {
+        let transport =
+            InMemoryTransport::new().with_pods(&[api_pod("pod-1", "train", "exec-1", None)]);
+        let client = test_client(&transport);
+
+        let args = PsArgs {
+            targets: vec!["missing".to_string()],
+            all: false,
+            status: None,
+            gpu: None,
+            watch: false,
+            interval: 5,
+        };
+        assert!(handle_with_client(args, &client).await.is_err());
+        assert_eq!(
+            transport.request_log(),
+            vec![(Method::GET, "pods".to_string())]
+        );
+    }
 }
 
-// TODO: Add real-time status updates
 // TODO: Add cost tracking and billing information
 // TODO: Add pod logs viewing capability
 // TODO: Add resource usage monitoring
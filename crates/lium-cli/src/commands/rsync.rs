@@ -2,9 +2,11 @@ use crate::{
     config::Config,
     display::{print_error, print_info, print_success, print_warning},
     helpers::{parse_ssh_command, resolve_pod_targets},
+    remote::{pod_ssh_auth, with_session},
     CliError, Result,
 };
 use lium_api::LiumApiClient;
+use lium_utils::{openssh_host_key_options, SshAuth, SshPool, SshTarget};
 use log::{debug, error, info, warn};
 use std::path::{Path, PathBuf};
 use std::process::{Command, Stdio};
@@ -73,7 +75,7 @@ use std::process::{Command, Stdio};
 /// - Automatically configures SSH for rsync operations
 /// - Uses configured private keys for authentication
 /// - Supports custom ports from pod SSH configurations
-/// - Disables host key checking for cloud environments
+/// - Verifies pod host keys against `~/.lium/known_hosts` (trust on first use)
 /// - Provides detailed SSH debugging information
 ///
 /// # Remote Rsync Installation
@@ -103,6 +105,7 @@ use std::process::{Command, Stdio};
 ///
 /// # Examples
 /// ```rust
+/// # async fn example() -> lium_cli::Result<()> {
 /// use lium_cli::commands::rsync::handle;
 /// use lium_cli::config::Config;
 ///
@@ -131,6 +134,8 @@ use std::process::{Command, Stdio};
 ///     vec!["-avn".to_string()],
 ///     &config
 /// ).await?;
+/// # Ok(())
+/// # }
 /// ```
 ///
 /// # Output Format
@@ -157,7 +162,7 @@ use std::process::{Command, Stdio};
 ///
 /// # Security Considerations
 /// - Uses configured SSH private keys exclusively
-/// - Disables host key checking for cloud pod environments
+/// - rsync's ssh verifies the pod host key recorded in `~/.lium/known_hosts`
 /// - Validates file paths to prevent directory traversal attacks
 /// - Logs operations for audit purposes without exposing sensitive data
 /// - Respects file permissions and ownership where possible
@@ -244,15 +249,6 @@ pub async fn handle(
         return Err(CliError::InvalidInput("No valid pods found.".to_string()));
     }
 
-    // Get SSH private key path
-    let private_key_path = config.get_ssh_private_key_path()?;
-    if !private_key_path.exists() {
-        return Err(CliError::InvalidInput(format!(
-            "SSH private key not found at '{}'. Configure it with: lium config set ssh.key_path /path/to/key",
-            private_key_path.display()
-        )));
-    }
-
     // Process rsync options
     let mut rsync_args = Vec::new();
     let mut has_archive = false;
@@ -363,27 +359,14 @@ pub async fn handle(
 
         // Parse SSH command
         let (host, port, user) = parse_ssh_command(ssh_cmd)?;
+        let target = SshTarget::new(host.clone(), port, user.clone()).for_pod(&pod.id);
+        let auth = pod_ssh_auth(config, pod)?;
 
         // Check if rsync is installed on the remote pod (for upload) or source pod (for download)
         if is_upload || (!is_upload && resolved_pods.len() == 1) {
             debug!("Checking if rsync is installed on remote pod...");
 
-            let check_rsync_cmd = Command::new("ssh")
-                .arg("-i")
-                .arg(&private_key_path)
-                .arg("-p")
-                .arg(port.to_string())
-                .arg("-o")
-                .arg("StrictHostKeyChecking=no")
-                .arg("-o")
-                .arg("UserKnownHostsFile=/dev/null")
-                .arg(format!("{}@{}", user, host))
-                .arg("which rsync")
-                .output();
-
-            let rsync_installed = check_rsync_cmd
-                .map(|output| output.status.success())
-                .unwrap_or(false);
+            let rsync_installed = remote_succeeds(&target, &auth, "which rsync").await;
 
             if !rsync_installed {
                 print_warning(&format!(
@@ -407,41 +390,13 @@ pub async fn handle(
                 for install_cmd in &install_commands {
                     print_info(&format!("  📦 Trying to install rsync..."));
 
-                    let install_result = Command::new("ssh")
-                        .arg("-i")
-                        .arg(&private_key_path)
-                        .arg("-p")
-                        .arg(port.to_string())
-                        .arg("-o")
-                        .arg("StrictHostKeyChecking=no")
-                        .arg("-o")
-                        .arg("UserKnownHostsFile=/dev/null")
-                        .arg(format!("{}@{}", user, host))
-                        .arg(install_cmd)
-                        .output();
-
-                    if let Ok(output) = install_result {
-                        if output.status.success() {
-                            // Verify installation
-                            let verify_cmd = Command::new("ssh")
-                                .arg("-i")
-                                .arg(&private_key_path)
-                                .arg("-p")
-                                .arg(port.to_string())
-                                .arg("-o")
-                                .arg("StrictHostKeyChecking=no")
-                                .arg("-o")
-                                .arg("UserKnownHostsFile=/dev/null")
-                                .arg(format!("{}@{}", user, host))
-                                .arg("which rsync")
-                                .output();
-
-                            if verify_cmd.map(|o| o.status.success()).unwrap_or(false) {
-                                print_success("  ✅ rsync installed successfully");
-                                install_success = true;
-                                break;
-                            }
-                        }
+                    // Verify installation
+                    if remote_succeeds(&target, &auth, install_cmd).await
+                        && remote_succeeds(&target, &auth, "which rsync").await
+                    {
+                        print_success("  ✅ rsync installed successfully");
+                        install_success = true;
+                        break;
                     }
                 }
 
@@ -457,11 +412,28 @@ pub async fn handle(
             }
         }
 
-        // Build SSH options for rsync
+        // Build SSH options for rsync. Connecting first records or verifies the pod's
+        // host key, which rsync's ssh then checks against the same known_hosts file
+        if let Err(e) = with_session(&target, &auth, |_| Ok(())).await {
+            print_error(&format!(
+                "  ❌ Cannot connect to '{}' ({}): {}",
+                pod.huid, original_ref, e
+            ));
+            failure_count += 1;
+            continue;
+        }
+        // ssh offers the agent's keys itself through the inherited SSH_AUTH_SOCK
+        let identity_options: String = auth
+            .identities
+            .iter()
+            .filter(|path| path.exists())
+            .map(|path| format!("-i {} ", shell_quote(&path.to_string_lossy())))
+            .collect();
         let ssh_options = format!(
-            "ssh -i {} -o StrictHostKeyChecking=no -o UserKnownHostsFile=/dev/null -p {}",
-            shell_quote(&private_key_path.to_string_lossy()),
-            port
+            "ssh {}-p {} {}",
+            identity_options,
+            port,
+            openssh_host_key_options(&target, SshPool::global().known_hosts())
         );
 
         // Build complete rsync command
@@ -480,18 +452,11 @@ pub async fn handle(
                 if !dir.is_empty() && dir != "~" && dir != "." && !dry_run {
                     print_info(&format!("  📁 Creating directory structure: {}", dir));
 
-                    let mkdir_result = Command::new("ssh")
-                        .arg("-i")
-                        .arg(&private_key_path)
-                        .arg("-p")
-                        .arg(port.to_string())
-                        .arg("-o")
-                        .arg("StrictHostKeyChecking=no")
-                        .arg("-o")
-                        .arg("UserKnownHostsFile=/dev/null")
-                        .arg(format!("{}@{}", user, host))
-                        .arg(format!("mkdir -p {}", shell_quote(&dir)))
-                        .output();
+                    let remote_dir = dir.clone();
+                    let mkdir_result = with_session(&target, &auth, move |session| {
+                        session.create_dir_all(&remote_dir)
+                    })
+                    .await;
 
                     if let Err(e) = mkdir_result {
                         print_warning(&format!(
@@ -610,6 +575,77 @@ pub async fn handle(
     Ok(())
 }
 
+/// Uploads every `defaults.sync_paths` entry to `destination`, for `lium rsync
+/// <DESTINATION>` without a source.
+///
+/// Each path is synced with [`handle`] in the configured order, so a project can pin
+/// what it pushes to a pod in its `.lium.toml`.
+///
+/// # Arguments
+/// * `destination` - Remote destination in `pod_targets:remote_path` format
+/// * `options` - rsync options passed to every transfer
+/// * `config` - User configuration providing `defaults.sync_paths` and SSH settings
+///
+/// # Returns
+/// * `Result<()>` - Success, or the error of the first path that failed to sync
+///
+/// # Errors
+/// * `CliError::InvalidInput` - If no sync paths are configured or `destination` is
+///   not a pod path
+///
+/// # Examples
+/// ```rust,no_run
+/// # async fn example(config: lium_cli::Config) -> lium_cli::Result<()> {
+/// use lium_cli::commands::rsync::handle_sync_paths;
+///
+/// // With `sync_paths = ["src/", "configs/"]` under `[defaults]`
+/// handle_sync_paths("1:~/project/".to_string(), vec!["-a".to_string()], &config).await?;
+/// # Ok(())
+/// # }
+/// ```
+pub async fn handle_sync_paths(
+    destination: String,
+    options: Vec<String>,
+    config: &Config,
+) -> Result<()> {
+    let sources = config.sync_paths()?;
+    if sources.is_empty() {
+        return Err(CliError::InvalidInput(
+            "Rsync requires source and destination arguments, or defaults.sync_paths to upload"
+                .to_string(),
+        ));
+    }
+    if parse_rsync_path(&destination)?.0.is_none() {
+        return Err(CliError::InvalidInput(format!(
+            "'{}' is not a pod destination (pod_targets:path format)",
+            destination
+        )));
+    }
+
+    for source in sources {
+        print_info(&format!(
+            "Syncing {} from defaults.sync_paths",
+            source.display()
+        ));
+        handle(
+            source.display().to_string(),
+            destination.clone(),
+            options.clone(),
+            config,
+        )
+        .await?;
+    }
+    Ok(())
+}
+
+/// Runs `command` on a pod and returns true if it exits with status 0
+async fn remote_succeeds(target: &SshTarget, auth: &SshAuth, command: &str) -> bool {
+    let command = command.to_string();
+    with_session(target, auth, move |session| session.exec(&command))
+        .await
+        .is_ok_and(|(_, _, code)| code == 0)
+}
+
 /// Check if a command exists in PATH
 fn command_exists(cmd: &str) -> bool {
     Command::new("which")
@@ -651,3 +687,28 @@ fn shell_quote(s: &str) -> String {
         format!("'{}'", s.replace('\'', "'\"'\"'"))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::DefaultsConfig;
+    use crate::test_support::test_config;
+
+    #[tokio::test]
+    async fn test_sync_paths_require_configured_paths_and_pod_destination() {
+        let mut config = test_config();
+        let error = handle_sync_paths("1:~/project/".to_string(), vec![], &config)
+            .await
+            .unwrap_err();
+        assert!(error.to_string().contains("defaults.sync_paths"));
+
+        config.data.defaults = Some(DefaultsConfig {
+            sync_paths: Some(vec!["src/".to_string()]),
+            ..Default::default()
+        });
+        let error = handle_sync_paths("./backup/".to_string(), vec![], &config)
+            .await
+            .unwrap_err();
+        assert!(error.to_string().contains("not a pod destination"));
+    }
+}
@@ -2,12 +2,13 @@ use crate::{
     config::Config,
     display::{print_error, print_info, print_success, print_warning},
     helpers::resolve_pod_targets,
+    remote::{pod_ssh_auth, with_session},
     CliError, Result,
 };
 use lium_api::LiumApiClient;
-use log::{debug, error, info, warn};
-use std::path::{Path, PathBuf};
-use std::process::{Command, Stdio};
+use lium_utils::SshTarget;
+use log::{debug, error};
+use std::path::PathBuf;
 
 /// Parse SSH command to extract host, port, and user
 /// This is a local implementation that handles empty strings and edge cases
@@ -69,15 +70,6 @@ pub async fn handle(
     hotkey: Option<String>,
     config: &Config,
 ) -> Result<()> {
-    // Check if scp is available
-    if !command_exists("scp") {
-        return Err(CliError::InvalidInput(
-            "Error: 'scp' command not found in your system PATH. \
-             Please install an SCP client (usually part of OpenSSH)."
-                .to_string(),
-        ));
-    }
-
     let api_client = LiumApiClient::from_config(config)?;
 
     // Parse the source and destination to determine direction
@@ -114,15 +106,6 @@ pub async fn handle(
         );
     }
 
-    // Get SSH private key path
-    let private_key_path = config.get_ssh_private_key_path()?;
-    if !private_key_path.exists() {
-        return Err(CliError::InvalidInput(format!(
-            "SSH private key not found at '{}'. Configure it with: lium config set ssh.key_path /path/to/key",
-            private_key_path.display()
-        )));
-    }
-
     // Collect files to copy
     let mut files_to_copy: Vec<(PathBuf, String)> = Vec::new();
 
@@ -249,72 +232,16 @@ pub async fn handle(
 
         // Use the local parse_ssh_command that handles edge cases
         let (host, port, user) = parse_ssh_command(ssh_cmd)?;
+        let target = SshTarget::new(host, port, user).for_pod(&pod.id);
+        let auth = pod_ssh_auth(config, &pod)?;
 
-        debug!(
-            "Parsed SSH details - Host: {}, Port: {}, User: {}",
-            host, port, user
-        );
-
-        // Workaround: If port is 22 and SSH command contains "-p", extract the actual port
-        // (Similar to exec.rs workaround)
-        let mut final_port = port;
-        if port == 22 && ssh_cmd.contains("-p ") {
-            if let Some(port_start) = ssh_cmd.find("-p ") {
-                let port_str = &ssh_cmd[port_start + 3..];
-                if let Some(port_end) = port_str.find(|c: char| !c.is_numeric()) {
-                    if let Ok(parsed_port) = port_str[..port_end].parse::<u16>() {
-                        debug!(
-                            "Parser returned port 22, but found -p {} in command. Using port {}.",
-                            parsed_port, parsed_port
-                        );
-                        final_port = parsed_port;
-                    }
-                } else if let Ok(parsed_port) = port_str.trim().parse::<u16>() {
-                    debug!(
-                        "Parser returned port 22, but found -p {} in command. Using port {}.",
-                        parsed_port, parsed_port
-                    );
-                    final_port = parsed_port;
-                }
-            }
-        }
+        debug!("Parsed SSH details - {}", target);
 
         if is_upload {
-            // Upload files to this pod
+            // Upload files to this pod over one pooled SFTP session
             let mut pod_success = true;
 
             for (local_file, remote_dest) in &files_to_copy {
-                // Create remote directory if needed
-                let remote_dir = if remote_dest.contains('/') {
-                    Path::new(remote_dest)
-                        .parent()
-                        .map(|p| p.to_string_lossy().to_string())
-                } else {
-                    None
-                };
-
-                if let Some(dir) = remote_dir {
-                    if !dir.is_empty() && dir != "~" && dir != "." {
-                        let mkdir_cmd = Command::new("ssh")
-                            .arg("-i")
-                            .arg(&private_key_path)
-                            .arg("-p")
-                            .arg(final_port.to_string())
-                            .arg("-o")
-                            .arg("StrictHostKeyChecking=no")
-                            .arg("-o")
-                            .arg("UserKnownHostsFile=/dev/null")
-                            .arg(format!("{}@{}", user, host))
-                            .arg(format!("mkdir -p {}", shell_quote(&dir)))
-                            .output();
-
-                        if let Err(e) = mkdir_cmd {
-                            warn!("Failed to create remote directory: {}", e);
-                        }
-                    }
-                }
-
-                // Execute SCP
                 print_info(&format!(
                     "📤 Copying {} → {} ({}):{}",
                     local_file.file_name().unwrap().to_string_lossy(),
@@ -323,34 +250,20 @@ pub async fn handle(
                     remote_dest
                 ));
 
-                let mut scp_cmd = Command::new("scp");
-                scp_cmd
-                    .arg("-i")
-                    .arg(&private_key_path)
-                    .arg("-P")
-                    .arg(final_port.to_string())
-                    .arg("-o")
-                    .arg("StrictHostKeyChecking=no")
-                    .arg("-o")
-                    .arg("UserKnownHostsFile=/dev/null")
-                    .arg(local_file)
-                    .arg(format!("{}@{}:{}", user, host, remote_dest))
-                    .stdout(Stdio::piped())
-                    .stderr(Stdio::piped());
-
-                match scp_cmd.output() {
-                    Ok(output) => {
-                        if output.status.success() {
-                            print_success("  ✅ Done");
-                        } else {
-                            let stderr = String::from_utf8_lossy(&output.stderr);
-                            print_error(&format!("  ❌ Failed: {}", stderr.trim()));
-                            pod_success = false;
-                            break;
-                        }
+                // Missing remote directories are created by the upload
+                let (local_file, remote_dest) = (local_file.clone(), remote_dest.clone());
+                let upload = with_session(&target, &auth, move |session| {
+                    session.upload(&local_file, &remote_dest)
+                })
+                .await;
+
+                match upload {
+                    Ok(path) => {
+                        debug!("Uploaded to {}", path.display());
+                        print_success("  ✅ Done");
                     }
                     Err(e) => {
-                        print_error(&format!("  ❌ Failed to execute SCP: {}", e));
+                        print_error(&format!("  ❌ Failed: {}", e));
                         pod_success = false;
                         break;
                     }
@@ -369,34 +282,20 @@ pub async fn handle(
                 pod.huid, original_ref, remote_path, local_path
             ));
 
-            let mut scp_cmd = Command::new("scp");
-            scp_cmd
-                .arg("-i")
-                .arg(&private_key_path)
-                .arg("-P")
-                .arg(final_port.to_string())
-                .arg("-o")
-                .arg("StrictHostKeyChecking=no")
-                .arg("-o")
-                .arg("UserKnownHostsFile=/dev/null")
-                .arg(format!("{}@{}:{}", user, host, remote_path))
-                .arg(&local_path)
-                .stdout(Stdio::piped())
-                .stderr(Stdio::piped());
-
-            match scp_cmd.output() {
-                Ok(output) => {
-                    if output.status.success() {
-                        print_success("  ✅ Done");
-                        success_count += 1;
-                    } else {
-                        let stderr = String::from_utf8_lossy(&output.stderr);
-                        print_error(&format!("  ❌ Failed: {}", stderr.trim()));
-                        failure_count += 1;
-                    }
+            let (remote, local) = (remote_path.clone(), PathBuf::from(&local_path));
+            let download = with_session(&target, &auth, move |session| {
+                session.download(&remote, &local)
+            })
+            .await;
+
+            match download {
+                Ok(path) => {
+                    debug!("Downloaded to {}", path.display());
+                    print_success("  ✅ Done");
+                    success_count += 1;
                 }
                 Err(e) => {
-                    print_error(&format!("  ❌ Failed to execute SCP: {}", e));
+                    print_error(&format!("  ❌ Failed: {}", e));
                     failure_count += 1;
                 }
             }
@@ -413,15 +312,6 @@ pub async fn handle(
     Ok(())
 }
 
-/// Check if a command exists in PATH
-fn command_exists(cmd: &str) -> bool {
-    Command::new("which")
-        .arg(cmd)
-        .output()
-        .map(|output| output.status.success())
-        .unwrap_or(false)
-}
-
 /// Parse SCP arguments to determine direction and paths
 fn parse_scp_args(source: &str, destination: &str) -> Result<(bool, String, String, String)> {
     // Check if source or destination contains pod reference (no colon or colon at end means pod reference)
@@ -465,13 +355,3 @@ fn parse_scp_args(source: &str, destination: &str) -> Result<(bool, String, Stri
         "Invalid SCP syntax. Use: lium scp <pod> <local_file> [<remote_path>] or lium scp <pod>:<remote_file> <local_path>".to_string()
     ))
 }
-
-/// Quote a string for shell execution, preserving ~ for home expansion
-fn shell_quote(s: &str) -> String {
-    if s.starts_with('~') {
-        s.to_string()
-    } else {
-        // Simple quoting - in production, use a proper shell escaping library
-        format!("'{}'", s.replace('\'', "'\"'\"'"))
-    }
-}
@@ -0,0 +1,156 @@
+use crate::{
+    config::Config,
+    display::{print_info, print_success, print_warning, prompt_confirm},
+    secrets::{SecretStore, SECRET_REF_PREFIX},
+    Result, SecretCommands,
+};
+use dialoguer::Password;
+use lium_utils::register_secret;
+
+/// Handles the `secrets` command for the encrypted secret store.
+///
+/// Secrets live in `~/.lium/secrets`, encrypted under a passphrase or key file (see
+/// [`crate::secrets`]). Config values reference them as `secret:<name>`, so the API key
+/// and Docker token never need to be written to `config.toml` in plaintext.
+///
+/// # Arguments
+/// * `action` - The specific secrets action to perform
+/// * `config` - Current user configuration, which supplies `secrets.key_file`
+///
+/// # Returns
+/// * `Result<()>` - Success or error if the store cannot be unlocked or saved
+///
+/// # Supported Operations
+/// - **Set**: Store a secret, prompting for the value without echo if it is not given
+/// - **Get**: Print a secret's raw value, for use in scripts
+/// - **List**: Print the stored secret names
+/// - **Rm**: Remove a secret
+/// - **Migrate**: Move plaintext credentials from `config.toml` into the store
+///
+/// # Examples
+/// ```rust
+/// # use lium_cli::config::Config;
+/// # async fn example(config: Config) -> lium_cli::Result<()> {
+/// use lium_cli::commands::secrets::handle;
+/// use lium_cli::SecretCommands;
+///
+/// handle(SecretCommands::Set {
+///     name: "docker_token".to_string(),
+///     value: None,
+/// }, &config).await?;
+/// # Ok(())
+/// # }
+/// ```
+pub async fn handle(action: SecretCommands, config: &Config) -> Result<()> {
+    match action {
+        SecretCommands::Set { name, value } => handle_set(name, value, config).await,
+        SecretCommands::Get { name } => handle_get(name, config).await,
+        SecretCommands::List => handle_list(config).await,
+        SecretCommands::Rm { name, yes } => handle_rm(name, yes, config).await,
+        SecretCommands::Migrate { yes } => handle_migrate(yes, config).await,
+    }
+}
+
+/// Stores a secret and shows how to reference it from the config.
+async fn handle_set(name: String, value: Option<String>, config: &Config) -> Result<()> {
+    let mut store = SecretStore::unlock(config)?;
+    let value = match value {
+        Some(value) => value,
+        None => Password::new()
+            .with_prompt(format!("Value for '{}'", name))
+            .allow_empty_password(false)
+            .interact()?,
+    };
+
+    register_secret(&value);
+    store.update(|secrets| secrets.set(&name, &value))?;
+    print_success(&format!("Stored secret '{}'", name));
+    print_info(&format!(
+        "Reference it from the config as \"{}{}\"",
+        SECRET_REF_PREFIX, name
+    ));
+    Ok(())
+}
+
+/// Prints a secret's raw value so it can be captured by scripts.
+async fn handle_get(name: String, config: &Config) -> Result<()> {
+    let store = SecretStore::unlock(config)?;
+    println!("{}", store.get(&name)?);
+    Ok(())
+}
+
+/// Prints the stored secret names, one per line; values are never shown.
+async fn handle_list(config: &Config) -> Result<()> {
+    if !SecretStore::default_path()?.exists() {
+        print_info("No secrets stored yet. Add one with 'lium secrets set NAME'");
+        return Ok(());
+    }
+
+    let store = SecretStore::unlock(config)?;
+    for name in store.names() {
+        println!("{}", name);
+    }
+    Ok(())
+}
+
+/// Removes a secret after confirmation.
+async fn handle_rm(name: String, yes: bool, config: &Config) -> Result<()> {
+    let mut store = SecretStore::unlock(config)?;
+    store.get(&name)?;
+    if !yes && !prompt_confirm(&format!("Remove secret '{}'?", name), false)? {
+        print_info("Cancelled");
+        return Ok(());
+    }
+
+    store.update(|secrets| secrets.remove(&name))?;
+    print_success(&format!("Removed secret '{}'", name));
+    Ok(())
+}
+
+/// Moves every plaintext API key and Docker token in `config.toml`, including those
+/// in profiles, into the secret store and replaces them with `secret:` references.
+///
+/// The secrets are saved before the config file is rewritten, so an interrupted
+/// migration never leaves a reference to a secret that was not stored.
+async fn handle_migrate(yes: bool, config: &Config) -> Result<()> {
+    if !config.has_plaintext_credentials() {
+        print_info("No plaintext credentials in the config file; nothing to migrate");
+        return Ok(());
+    }
+    if !yes
+        && !prompt_confirm(
+            "Move plaintext credentials from the config file into the encrypted secret store?",
+            true,
+        )?
+    {
+        print_info("Cancelled");
+        return Ok(());
+    }
+
+    let mut store = SecretStore::unlock(config)?;
+    let mut config = config.clone();
+    let moved = config.update(|config| {
+        let credentials = config.take_plaintext_credentials();
+        store.update(|secrets| {
+            for (name, value) in &credentials {
+                if secrets.contains(name) {
+                    print_warning(&format!("Replacing existing secret '{}'", name));
+                }
+                secrets.set(name, value)?;
+            }
+            Ok(())
+        })?;
+        Ok(credentials
+            .into_iter()
+            .map(|(name, _)| name)
+            .collect::<Vec<_>>())
+    })?;
+
+    for name in &moved {
+        print_success(&format!(
+            "Moved credential to secret '{}' (config now reads \"{}{}\")",
+            name, SECRET_REF_PREFIX, name
+        ));
+    }
+    Ok(())
+}
@@ -1,10 +1,10 @@
 use crate::{
     config::Config,
-    helpers::{parse_ssh_command, resolve_pod_targets},
+    helpers::resolve_pod_targets,
+    remote::{pod_ssh_auth, pod_ssh_target, with_session},
     CliError, Result,
 };
 use lium_api::LiumApiClient;
-use std::process::Command;
 
 /// Handles the `ssh` command to establish interactive SSH connections to pods.
 ///
@@ -26,7 +26,8 @@ use std::process::Command;
 /// 3. **SSH Configuration**: Extracts host, port, and user from pod SSH command
 /// 4. **Key Validation**: Verifies SSH private key availability and accessibility
 /// 5. **Connection**: Establishes SSH connection with proper configuration
-/// 6. **Interactive Session**: Transfers control to the SSH client for user interaction
+/// 6. **Interactive Session**: Opens a remote shell on a pseudo-terminal and relays the
+///    local terminal to it until the shell exits
 ///
 /// # Pod Target Resolution
 /// The pod target can be specified as:
@@ -38,18 +39,20 @@ use std::process::Command;
 /// an interactive session that cannot be multiplexed across multiple pods.
 ///
 /// # SSH Configuration
-/// The function automatically configures SSH with appropriate settings for cloud environments:
+/// The connection is made by lium itself (libssh2), so no OpenSSH client is needed:
 /// - **Private Key**: Uses configured private key from user settings
-/// - **Host Key Checking**: Disabled for cloud pods (security consideration)
-/// - **Known Hosts**: Uses /dev/null to avoid host key conflicts
+/// - **Host Key Checking**: The key is recorded in `~/.lium/known_hosts` on first
+///   connect; a different key for the same pod aborts the connection
 /// - **Port Forwarding**: Supports custom ports from pod SSH commands
-/// - **Connection Timeout**: Relies on SSH client defaults
+/// - **Terminal**: Requests a pty matching the local `TERM` and window size, and puts
+///   the local terminal in raw mode for the duration of the session
+/// - **Connection Timeout**: 30 seconds to connect and authenticate
 ///
 /// # Security Considerations
 /// - Only uses configured private keys (no password authentication)
-/// - Disables host key checking for cloud environments
+/// - Verifies pod host keys against `~/.lium/known_hosts` (trust on first use)
 /// - SSH commands are not logged to avoid exposing connection details
-/// - Uses standard SSH client security features
+/// - Keepalives detect dropped connections during long sessions
 ///
 /// # Error Conditions
 /// - Pod target not found or inaccessible
@@ -57,10 +60,11 @@ use std::process::Command;
 /// - Pod has no SSH connection information
 /// - SSH private key not found or not readable
 /// - SSH connection failure (network, authentication, etc.)
-/// - SSH client not available on the system
+/// - Remote shell exiting with a non-zero status
 ///
 /// # Examples
 /// ```rust
+/// # async fn example() -> lium_cli::Result<()> {
 /// use lium_cli::commands::ssh::handle;
 /// use lium_cli::config::Config;
 ///
@@ -74,6 +78,8 @@ use std::process::Command;
 ///
 /// // Connect to pod by name
 /// handle("my-training-pod".to_string(), &config).await?;
+/// # Ok(())
+/// # }
 /// ```
 ///
 /// # Interactive Experience
@@ -133,44 +139,22 @@ pub async fn handle(pod_target: String, config: &Config) -> Result<()> {
     let (pod, _) = &resolved_pods[0];
 
     // Parse SSH details
-    let ssh_cmd = pod.ssh_cmd.as_ref().ok_or_else(|| {
-        CliError::InvalidInput(format!("Pod {} has no SSH connection info", pod.huid))
-    })?;
-
-    let (host, port, user) = parse_ssh_command(ssh_cmd)?;
-
-    // Get SSH private key path
-    let private_key_path = config.get_ssh_private_key_path()?;
-
-    // Construct SSH command
-    let mut ssh_args = vec![
-        "-i".to_string(),
-        private_key_path.to_string_lossy().to_string(),
-        "-o".to_string(),
-        "StrictHostKeyChecking=no".to_string(),
-        "-o".to_string(),
-        "UserKnownHostsFile=/dev/null".to_string(),
-    ];
-
-    if port != 22 {
-        ssh_args.extend_from_slice(&["-p".to_string(), port.to_string()]);
-    }
-
-    ssh_args.push(format!("{}@{}", user, host));
+    let target = pod_ssh_target(pod)?;
+    let auth = pod_ssh_auth(config, pod)?;
 
     println!("🔗 Connecting to pod {} ({})...", pod.huid, pod.name);
-    println!("Host: {}:{}, User: {}", host, port, user);
+    println!(
+        "Host: {}:{}, User: {}",
+        target.host, target.port, target.user
+    );
 
-    // Execute SSH command
-    let status = Command::new("ssh")
-        .args(&ssh_args)
-        .status()
-        .map_err(CliError::Io)?;
+    // Hand the terminal to the remote shell until it exits
+    let exit_code = with_session(&target, &auth, |session| session.shell()).await?;
 
-    if !status.success() {
+    if exit_code != 0 {
         return Err(CliError::OperationFailed(format!(
-            "SSH connection failed with exit code: {:?}",
-            status.code()
+            "SSH session ended with exit code: {}",
+            exit_code
         )));
     }
 
@@ -23,7 +23,7 @@ use dialoguer::Select;
 /// - **Warning messages**: Cautionary information and important notices
 /// - **Info messages**: General information and status updates
 /// - **Accent colors**: Highlights, selections, and emphasis
-const AVAILABLE_THEMES: &[(&str, &str)] = &[
+pub const AVAILABLE_THEMES: &[(&str, &str)] = &[
     ("default", "Default theme with standard colors"),
     ("dark", "Dark theme with muted colors"),
     ("light", "Light theme with bright colors"),
@@ -39,7 +39,7 @@ const AVAILABLE_THEMES: &[(&str, &str)] = &[
 ///
 /// # Arguments
 /// * `action` - The specific theme action to perform (list or set)
-/// * `config` - User configuration for theme persistence (currently placeholder)
+/// * `config` - User configuration the selected theme is saved to
 ///
 /// # Returns
 /// * `Result<()>` - Success or error with theme operation information
@@ -50,6 +50,7 @@ const AVAILABLE_THEMES: &[(&str, &str)] = &[
 ///
 /// # Examples
 /// ```rust
+/// # async fn example() -> lium_cli::Result<()> {
 /// use lium_cli::commands::theme::handle;
 /// use lium_cli::{ThemeCommands, config::Config};
 ///
@@ -62,16 +63,16 @@ const AVAILABLE_THEMES: &[(&str, &str)] = &[
 /// handle(ThemeCommands::Set {
 ///     name: "dark".to_string()
 /// }, &config).await?;
+/// # Ok(())
+/// # }
 /// ```
 ///
 /// # Theme System Status
-/// **Note**: The theming system is currently in development. Theme selection
-/// affects color output but persistence to configuration files is not yet
-/// implemented. Future versions will include full theme persistence and
-/// additional customization options.
+/// **Note**: The theming system is currently in development. The selected theme
+/// is saved as `theme.name` in the configuration file (also settable with
+/// `lium config set theme.name <name>`).
 ///
 /// # TODO
-/// - Implement theme persistence in TOML configuration
 /// - Add support for custom theme creation
 /// - Support for theme inheritance and overrides
 /// - Add preview functionality for theme selection
@@ -116,16 +117,7 @@ async fn handle_list() -> Result<()> {
 
 /// Handle theme set command
 async fn handle_set(name: Option<String>, config: &Config) -> Result<()> {
-    let config = config.clone();
-
     let theme_name = if let Some(name) = name {
-        // Validate the provided theme name
-        if !AVAILABLE_THEMES.iter().any(|(theme, _)| *theme == name) {
-            return Err(crate::CliError::InvalidInput(format!(
-                "Unknown theme: {}. Use 'lium theme list' to see available themes.",
-                name
-            )));
-        }
         name
     } else {
         // Interactive theme selection
@@ -144,18 +136,19 @@ async fn handle_set(name: Option<String>, config: &Config) -> Result<()> {
         AVAILABLE_THEMES[selection].0.to_string()
     };
 
-    // For now, just print success - we'll need to add theme storage to config later
+    // Validated by set_theme
+    let mut config = config.clone();
+    config.update(|config| config.set_theme(&theme_name))?;
+
     println!("✅ Theme set to: {}", theme_name);
     println!("💡 The theme will be applied to future command outputs");
-    println!("⚠️  Theme persistence not yet implemented in TOML config");
 
     Ok(())
 }
 
 /// Get the current theme from config
-pub fn get_current_theme(_config: &Config) -> String {
-    // TODO: Implement theme storage in TOML config
-    "default".to_string()
+pub fn get_current_theme(config: &Config) -> String {
+    config.get_theme().unwrap_or_else(|| "default".to_string())
 }
 
 /// Apply theme colors to a string (for display utilities)
@@ -1,18 +1,39 @@
 use crate::{
-    config::Config,
+    config::{expand_path, private_key_path, read_ssh_public_keys, Config},
     display::{
-        display_executors_table, print_error, print_info, print_success, prompt_confirm,
-        prompt_select,
+        display_executors_table, is_structured_output, print_error, print_info, print_output,
+        print_success, print_warning, prompt_confirm, prompt_select, write_console,
     },
+    remote::{
+        exec_on_pod, pod_ssh_auth, pod_ssh_target, remember_pod_key, with_session, RemoteCommand,
+    },
+    secrets::{resolve_secret_vars, secret_exports, SecretVar},
     CliError, Result,
 };
 use clap::Args;
-use lium_api::LiumApiClient;
+use lium_api::{Lium, LiumApiClient, ReadyOptions, ReadyProgress};
 use lium_core::{
     filter_by_availability, filter_by_gpu_type, parse_env_vars, parse_executor_index,
-    parse_port_mappings, sort_by_price, validate_docker_image,
+    parse_port_mappings, sort_by_price, PodInfo, RentPodRequest, RentPodResponse,
+};
+use lium_utils::{
+    is_sensitive_name, redact, redact_registered, register_secret, CancelToken, OutputStream,
 };
+use log::{debug, info};
+use serde::Serialize;
 use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::time::Duration;
+
+/// Remote command that stores secret exports read from stdin in `~/.lium/env`
+/// (readable only by the pod user) and sources that file from `~/.bashrc`
+const POD_ENV_INSTALL: &str = "umask 077 && mkdir -p ~/.lium && cat > ~/.lium/env && \
+    { grep -qsF '. ~/.lium/env' ~/.bashrc || \
+    echo '[ -f ~/.lium/env ] && . ~/.lium/env' >> ~/.bashrc; }";
+
+/// Runs a bootstrap script read from stdin with the pod's `~/.lium/env` loaded
+const POD_BOOTSTRAP: &str = "[ -f ~/.lium/env ] && . ~/.lium/env; bash -s";
 
 /// Command-line arguments for the `up` command that creates and starts new pods.
 ///
@@ -36,6 +57,12 @@ use std::collections::HashMap;
 ///
 /// # Skip confirmation prompts (useful for automation)
 /// lium up --yes --name my-training-pod
+///
+/// # Block until the pod accepts SSH connections, then run a command
+/// lium up --yes --wait && lium exec 1 "nvidia-smi"
+///
+/// # Install secrets on the pod once it is ready (implies --wait)
+/// lium up --yes --secret HF_TOKEN --secret WANDB_API_KEY=wandb_key
 /// ```
 ///
 /// # Template vs Docker Image Handling
@@ -111,6 +138,17 @@ pub struct UpArgs {
     #[arg(short, long)]
     pub env: Option<String>,
 
+    /// Secrets to install on the pod once it accepts SSH connections (implies `--wait`).
+    ///
+    /// Format: `VAR` or `VAR=NAME`, repeatable. The value of secret `NAME` (or `VAR`) is
+    /// read from the local secret store (`lium secrets`), falling back to the local
+    /// environment. Values are sent over SSH stdin to `~/.lium/env` on the pod, which
+    /// `~/.bashrc` sources; they are never passed to the API or shown in output.
+    ///
+    /// Example: "HF_TOKEN", "WANDB_API_KEY=wandb_key"
+    #[arg(long, value_name = "VAR[=NAME]")]
+    pub secret: Vec<String>,
+
     /// Port mappings from pod to host (comma-separated HOST_PORT:CONTAINER_PORT pairs).
     ///
     /// Maps ports from the container to the host system, enabling external access
@@ -124,7 +162,8 @@ pub struct UpArgs {
     /// Path to SSH public key file for pod access.
     ///
     /// Overrides the default SSH key configured in the user's configuration.
-    /// The corresponding private key will be used for SSH connections to the pod.
+    /// The corresponding private key is remembered for the pod and used automatically
+    /// by later `ssh`, `exec`, `scp` and `rsync` commands; it may also live in ssh-agent.
     ///
     /// Example: "~/.ssh/custom_key.pub"
     #[arg(long)]
@@ -145,6 +184,18 @@ pub struct UpArgs {
     /// confirmation is not possible or desired.
     #[arg(short, long)]
     pub yes: bool,
+
+    /// Wait until the pod is running and accepting SSH connections.
+    ///
+    /// Polls the pod until its status is running and it has an SSH command, then
+    /// probes the SSH port. Exits with a non-zero code if the pod is not ready
+    /// within `--wait-timeout`, so `lium up --wait && lium exec ...` is safe.
+    #[arg(short, long)]
+    pub wait: bool,
+
+    /// Maximum seconds to wait for the pod to become ready.
+    #[arg(long, default_value_t = 300, requires = "wait", value_name = "SECONDS")]
+    pub wait_timeout: u64,
 }
 
 /// Handles the `up` command to create and start a new pod on a cloud GPU executor.
@@ -169,6 +220,7 @@ pub struct UpArgs {
 /// 6. **Confirmation**: Shows summary and requests user confirmation (unless `--yes`)
 /// 7. **Pod Creation**: Calls the rent_pod API to create and start the pod
 /// 8. **Result Display**: Shows pod details including SSH connection info
+/// 9. **Readiness Wait** (`--wait`): Blocks until the pod accepts SSH connections
 ///
 /// # Error Conditions
 /// - Invalid API key or configuration
@@ -180,6 +232,7 @@ pub struct UpArgs {
 ///
 /// # Examples
 /// ```rust
+/// # async fn example() -> lium_cli::Result<()> {
 /// use lium_cli::commands::up::{handle, UpArgs};
 /// use lium_cli::config::Config;
 ///
@@ -189,80 +242,84 @@ pub struct UpArgs {
 ///     available: true,
 ///     index: Some("1".to_string()),
 ///     env: Some("DEBUG=1".to_string()),
+///     secret: vec![],
 ///     ports: Some("8080:80".to_string()),
 ///     ssh_key: None,
 ///     name: Some("my-pod".to_string()),
 ///     yes: false,
+///     wait: true,
+///     wait_timeout: 300,
 /// };
 ///
 /// let config = Config::new()?;
 /// handle(args, &config).await?;
+/// # Ok(())
+/// # }
 /// ```
 ///
 /// # Debug Information
-/// The function includes extensive debug logging for troubleshooting:
-/// - API key validation and source
+/// Run with `-v`/`-vv` to log troubleshooting details:
+/// - API key source
 /// - Template resolution process
 /// - Executor filtering results
 /// - API call success/failure details
 ///
 /// # TODO
 /// - Add support for spot instances and preemptible pricing
-/// - Implement pod startup health checks
 /// - Add support for persistent storage mounting
 /// - Improve error messages with suggested solutions
 /// - Add cost estimation before pod creation
 pub async fn handle(args: UpArgs, config: &Config) -> Result<()> {
-    // DEBUG: Check if API key exists in config
-    print_info("DEBUG: Checking API key configuration...");
-
-    match config.get_api_key()? {
-        Some(key) => {
-            if key.len() >= 8 {
-                print_info(&format!(
-                    "DEBUG: API key found in config: {}...{}",
-                    &key[..4],
-                    &key[key.len() - 4..]
-                ));
-            } else {
-                print_info("DEBUG: API key found but too short to display");
-            }
-        }
-        None => {
-            print_info("DEBUG: No API key found in config or environment");
-        }
+    match std::env::var("LIUM_API_KEY") {
+        Ok(_) => debug!("Using the API key from LIUM_API_KEY"),
+        Err(_) => debug!("LIUM_API_KEY not set; using the API key from the config"),
     }
+    let client = LiumApiClient::from_config(config)?;
+    debug!("API client created");
 
-    // DEBUG: Check environment variable
-    match std::env::var("LIUM_API_KEY") {
-        Ok(key) => {
-            if key.len() >= 8 {
-                print_info(&format!(
-                    "DEBUG: LIUM_API_KEY env var found: {}...{}",
-                    &key[..4],
-                    &key[key.len() - 4..]
-                ));
-            } else {
-                print_info("DEBUG: LIUM_API_KEY env var found but too short");
+    let Some(result) = handle_with_client(args, config, &client).await? else {
+        return Ok(());
+    };
+    print_output(&result, |result| {
+        if !result.pod.ports_mapping.is_empty() {
+            println!("\nPort Mappings:");
+            for (service, port) in &result.pod.ports_mapping {
+                println!("  {}: {}", service, port);
             }
         }
-        Err(_) => {
-            print_info("DEBUG: LIUM_API_KEY environment variable not set");
-        }
-    }
 
-    print_info("DEBUG: Creating API client from config...");
-    let client = match LiumApiClient::from_config(config) {
-        Ok(c) => {
-            print_info("DEBUG: API client created successfully");
-            c
-        }
-        Err(e) => {
-            print_error(&format!("DEBUG: Failed to create API client: {}", e));
-            return Err(e);
+        println!();
+        if let Some(huid) = &result.pod.huid {
+            println!("Use 'lium ssh {}' to connect", huid);
         }
-    };
+    })
+}
 
+/// The outcome of `lium up`, as printed by `--output json`.
+#[derive(Debug, Clone, Serialize)]
+pub struct UpResult {
+    pub executor_id: String,
+    pub executor_huid: String,
+    pub gpu_type: String,
+    pub gpu_count: i32,
+    pub price_per_hour: f64,
+    pub template_id: String,
+    /// The rented pod as returned by the API, with its HUID filled in
+    pub pod: RentPodResponse,
+    /// The pod's state once it accepted SSH, if `--wait` (or `--secret`) was given
+    pub ready: Option<PodInfo>,
+}
+
+/// Runs the `up` flow against an already constructed API client.
+///
+/// This is the body of [`handle`] minus client construction and output, so tests can
+/// drive it with a client backed by an in-memory transport. Returns `None` if the
+/// user declined the confirmation.
+pub async fn handle_with_client(
+    args: UpArgs,
+    config: &Config,
+    client: &LiumApiClient,
+) -> Result<Option<UpResult>> {
     // Handle both templates and Docker images
     let template_id = match args.image {
         Some(image_input) => {
@@ -277,13 +334,9 @@ pub async fn handle(args: UpArgs, config: &Config) -> Result<()> {
                     image_input
                 ));
 
-                print_info("DEBUG: About to call get_templates()...");
                 match client.get_templates().await {
                     Ok(templates) => {
-                        print_info(&format!(
-                            "DEBUG: Successfully fetched {} templates",
-                            templates.len()
-                        ));
+                        debug!("Fetched {} templates", templates.len());
 
                         // Try to find existing template with this docker image
                         let matching_template = templates.iter().find(|template| {
@@ -307,7 +360,6 @@ pub async fn handle(args: UpArgs, config: &Config) -> Result<()> {
                         }
                     }
                     Err(e) => {
-                        print_error(&format!("DEBUG: API call to get_templates() failed"));
                         print_error(&format!("Failed to fetch templates: {}", e));
                         print_info(&format!(
                             "Attempting to use '{}' directly as template ID",
@@ -326,13 +378,9 @@ pub async fn handle(args: UpArgs, config: &Config) -> Result<()> {
             // No input provided - fetch templates and use default
             print_info("No image specified, fetching available templates...");
 
-            print_info("DEBUG: About to call get_templates() for default template...");
             match client.get_templates().await {
                 Ok(templates) => {
-                    print_info(&format!(
-                        "DEBUG: Successfully fetched {} templates",
-                        templates.len()
-                    ));
+                    debug!("Fetched {} templates", templates.len());
 
                     if templates.is_empty() {
                         return Err(CliError::OperationFailed("No templates found".to_string()));
@@ -355,9 +403,8 @@ pub async fn handle(args: UpArgs, config: &Config) -> Result<()> {
                     template_id
                 }
                 Err(e) => {
-                    print_error(&format!("DEBUG: API call to get_templates() failed"));
                     print_error(&format!("Failed to fetch templates: {}", e));
-                    print_error(&format!("DEBUG: Error details: {:?}", e));
+                    debug!("Template fetch error details: {:?}", e);
                     return Err(CliError::OperationFailed(
                         "Could not fetch templates".to_string(),
                     ));
@@ -372,6 +419,29 @@ pub async fn handle(args: UpArgs, config: &Config) -> Result<()> {
     } else {
         HashMap::new()
     };
+    for (key, value) in &env_vars {
+        if is_sensitive_name(key) {
+            register_secret(value);
+        }
+    }
+
+    // Resolve secrets before renting so a missing one costs nothing
+    let secret_vars = resolve_secret_vars(config, &args.secret)?;
+
+    // Likewise read the project's bootstrap script before renting
+    let bootstrap = match config.bootstrap_script()? {
+        Some(path) => {
+            let script = fs::read_to_string(&path).map_err(|e| {
+                CliError::InvalidInput(format!(
+                    "Cannot read defaults.bootstrap_script {}: {}",
+                    path.display(),
+                    e
+                ))
+            })?;
+            Some((path, script))
+        }
+        None => None,
+    };
 
     // Parse port mappings
     let port_mappings = if let Some(ports_str) = &args.ports {
@@ -381,20 +451,8 @@ pub async fn handle(args: UpArgs, config: &Config) -> Result<()> {
     };
 
     // Fetch and filter executors
-    print_info("DEBUG: About to fetch executors...");
-    let mut executors = match client.get_executors().await {
-        Ok(execs) => {
-            print_info(&format!(
-                "DEBUG: Successfully fetched {} executors",
-                execs.len()
-            ));
-            execs
-        }
-        Err(e) => {
-            print_error(&format!("DEBUG: Failed to fetch executors: {}", e));
-            return Err(e.into());
-        }
-    };
+    let mut executors = client.get_executors().await?;
+    debug!("Fetched {} executors", executors.len());
 
     if executors.is_empty() {
         return Err(CliError::OperationFailed("No executors found".to_string()));
@@ -425,6 +483,11 @@ pub async fn handle(args: UpArgs, config: &Config) -> Result<()> {
     let selected_executor = if let Some(index_str) = &args.index {
         let index = parse_executor_index(index_str, executors.len())?;
         executors[index].clone()
+    } else if is_structured_output() {
+        return Err(CliError::InvalidInput(
+            "Pass --index to choose an executor when using a structured --output format"
+                .to_string(),
+        ));
     } else {
         // Interactive selection
         println!("Available executors:");
@@ -463,13 +526,48 @@ pub async fn handle(args: UpArgs, config: &Config) -> Result<()> {
     print_info(&format!("Using template/image: {}", template_id));
 
     if !env_vars.is_empty() {
-        print_info(&format!("Environment Variables: {:?}", env_vars));
+        // Names only: values may be credentials that were not recognised as sensitive
+        let mut env_list = env_vars.keys().map(String::as_str).collect::<Vec<_>>();
+        env_list.sort();
+        print_info(&format!("Environment Variables: {}", env_list.join(", ")));
+    }
+
+    if !secret_vars.is_empty() {
+        let secret_list = secret_vars
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>();
+        print_info(&format!("Secrets: {}", secret_list.join(", ")));
     }
 
     if !port_mappings.is_empty() {
         print_info(&format!("Port Mappings: {:?}", port_mappings));
     }
 
+    if let Some((path, _)) = &bootstrap {
+        print_info(&format!("Bootstrap script: {}", path.display()));
+    }
+
+    // A key given with --ssh-key replaces the configured one for this pod
+    let pod_key = match &args.ssh_key {
+        Some(path) => {
+            let public_key = expand_path(path)?;
+            let keys = read_ssh_public_keys(&public_key).map_err(|e| {
+                CliError::InvalidInput(format!("Cannot use SSH key '{}': {}", path, e))
+            })?;
+            let private_key = private_key_path(&public_key);
+            if !private_key.exists() {
+                print_warning(&format!(
+                    "Private key {} not found; connections to the pod will need it in ssh-agent",
+                    private_key.display()
+                ));
+            }
+            print_info(&format!("SSH Key: {}", public_key.display()));
+            Some((keys, private_key))
+        }
+        None => None,
+    };
+
     // Confirmation
     if !args.yes {
         let confirm = prompt_confirm(
@@ -478,8 +576,8 @@ pub async fn handle(args: UpArgs, config: &Config) -> Result<()> {
         )?;
 
         if !confirm {
-            println!("Operation cancelled.");
-            return Ok(());
+            print_info("Operation cancelled.");
+            return Ok(None);
         }
     }
 
@@ -492,60 +590,447 @@ pub async fn handle(args: UpArgs, config: &Config) -> Result<()> {
         .name
         .unwrap_or_else(|| format!("pod-{}", selected_executor.huid));
 
-    let ssh_keys = config.get_ssh_public_keys().unwrap_or_default();
+    let ssh_keys = match &pod_key {
+        Some((keys, _)) => keys.clone(),
+        None => config.get_ssh_public_keys().unwrap_or_default(),
+    };
+
+    // Send exactly what was shown in the summary above
+    let request = RentPodRequest::new(pod_name, template_id)
+        .with_ssh_keys(ssh_keys)
+        .with_env(env_vars)
+        .with_ports(port_mappings);
 
-    // Debug: Print what we're about to send
-    print_info(&format!(
+    info!(
         "Renting: executor_id={}, pod_name={}, template_id={}",
-        executor_id, pod_name, template_id
+        executor_id, request.pod_name, request.template_id
+    );
+
+    let mut pod_info = match client.rent_pod(&executor_id, &request).await {
+        Ok(pod_info) => pod_info,
+        Err(e) => {
+            print_error(&format!("Failed to start pod: {}", e));
+            debug!("rent_pod error details: {:?}", e);
+            return Err(e.into());
+        }
+    };
+
+    print_success("Pod rented successfully!");
+    let huid = pod_info.huid();
+    print_info(&format!("Pod HUID: {}", huid));
+    pod_info.huid = Some(huid);
+    if let Some(name) = &pod_info.pod_name {
+        print_info(&format!("Pod Name: {}", name));
+    }
+    if let Some(status) = &pod_info.status {
+        print_info(&format!("Status: {}", status));
+    }
+
+    if let Some(ssh_cmd) = &pod_info.ssh_connect_cmd {
+        print_success(&format!("SSH Command: {}", ssh_cmd));
+    }
+
+    if let Some((_, private_key)) = &pod_key {
+        record_pod_key(&pod_info.id, private_key);
+    }
+
+    let mut ready = None;
+    if args.wait || !secret_vars.is_empty() || bootstrap.is_some() {
+        let pod =
+            wait_until_ready(client, &pod_info.id, Duration::from_secs(args.wait_timeout)).await?;
+        if !secret_vars.is_empty() {
+            install_secrets(config, &pod, &secret_vars).await?;
+        }
+        if let Some((path, script)) = bootstrap {
+            run_bootstrap(config, &pod, &path, script).await?;
+        }
+        ready = Some(pod);
+    } else {
+        print_info("The pod may take a few minutes to accept SSH connections (use --wait to block until it does)");
+    }
+
+    Ok(Some(UpResult {
+        executor_id,
+        executor_huid: selected_executor.huid,
+        gpu_type: selected_executor.gpu_type,
+        gpu_count: selected_executor.gpu_count,
+        price_per_hour: selected_executor.price_per_hour,
+        template_id: request.template_id,
+        pod: pod_info,
+        ready,
+    }))
+}
+
+/// Waits for a freshly rented pod to accept SSH connections, printing progress.
+///
+/// Only changes in progress are printed, so a pod sitting in `PENDING` prints one
+/// line rather than one per poll.
+async fn wait_until_ready(
+    client: &LiumApiClient,
+    pod_ref: &str,
+    timeout: Duration,
+) -> Result<PodInfo> {
+    print_info(&format!(
+        "Waiting up to {}s for the pod to become ready...",
+        timeout.as_secs()
     ));
 
-    print_info("DEBUG: About to call rent_pod API...");
-    match client
-        .rent_pod(&executor_id, &pod_name, &template_id, ssh_keys)
-        .await
-    {
-        Ok(pod_info) => {
-            print_info("DEBUG: rent_pod API call successful");
-            print_success("Pod started successfully!");
-            println!();
-
-            // Parse the response JSON to extract fields
-            if let Some(huid) = pod_info.get("huid").and_then(|v| v.as_str()) {
-                print_info(&format!("Pod HUID: {}", huid));
-            }
-            if let Some(name) = pod_info.get("name").and_then(|v| v.as_str()) {
-                print_info(&format!("Pod Name: {}", name));
-            }
-            if let Some(status) = pod_info.get("status").and_then(|v| v.as_str()) {
-                print_info(&format!("Status: {}", status));
+    let mut last_message = String::new();
+    let lium = Lium::from_client(client.clone());
+    let pod = lium
+        .wait_for_pod_ready_with(pod_ref, ReadyOptions::new(timeout), |progress| {
+            let message = match progress {
+                ReadyProgress::NotListed => "Waiting for the pod to appear...".to_string(),
+                ReadyProgress::Waiting { status } => format!("Pod status: {}", status),
+                ReadyProgress::Probing { host, port } => {
+                    format!("Probing SSH on {}:{}...", host, port)
+                }
+                ReadyProgress::ProbeFailed { reason, .. } => {
+                    format!("SSH not ready yet ({})", reason)
+                }
+                ReadyProgress::PollFailed { error } => {
+                    format!("Could not list pods, retrying ({})", error)
+                }
+                ReadyProgress::Ready => return,
+            };
+            if message != last_message {
+                print_info(&message);
+                last_message = message;
             }
+        })
+        .await?;
 
-            if let Some(ssh_cmd) = pod_info.get("ssh_cmd").and_then(|v| v.as_str()) {
-                print_success(&format!("SSH Command: {}", ssh_cmd));
-            }
+    print_success("Pod is ready!");
+    if let Some(ssh_cmd) = &pod.ssh_cmd {
+        print_success(&format!("SSH Command: {}", ssh_cmd));
+    }
+    Ok(pod)
+}
 
-            if let Some(ports) = pod_info.get("ports").and_then(|v| v.as_object()) {
-                if !ports.is_empty() {
-                    println!("\nPort Mappings:");
-                    for (service, port) in ports {
-                        println!("  {}: {}", service, port);
-                    }
+/// Remembers the `--ssh-key` a pod was created with, warning instead of failing since
+/// the pod is already rented
+fn record_pod_key(pod_id: &str, private_key: &Path) {
+    if let Err(e) = remember_pod_key(pod_id, private_key) {
+        print_warning(&format!(
+            "Failed to remember the SSH key of pod {}: {}",
+            pod_id, e
+        ));
+    }
+}
+
+/// Writes secrets to `~/.lium/env` on a ready pod.
+///
+/// The `export` lines travel over the SSH session's stdin, so the values never appear
+/// on a command line on either machine.
+///
+/// # Errors
+/// * `CliError::InvalidInput` - If the pod has no SSH details or no SSH key is configured
+/// * `CliError::OperationFailed` - If the remote write fails
+async fn install_secrets(config: &Config, pod: &PodInfo, vars: &[SecretVar]) -> Result<()> {
+    let target = pod_ssh_target(pod)?;
+    let payload = secret_exports(vars);
+
+    let (code, stderr) = with_session(&target, &pod_ssh_auth(config, pod)?, move |session| {
+        let mut stderr = Vec::new();
+        let code =
+            session.exec_streaming(POD_ENV_INSTALL, Some(payload.as_bytes()), |stream, line| {
+                if stream == OutputStream::Stderr {
+                    stderr.push(line.to_string());
                 }
+            })?;
+        Ok((code, stderr.join("\n")))
+    })
+    .await?;
+    if code != 0 {
+        return Err(CliError::OperationFailed(format!(
+            "Failed to install secrets on the pod: {}",
+            redact(stderr.trim())
+        )));
+    }
+
+    let names = vars.iter().map(|v| v.var.as_str()).collect::<Vec<_>>();
+    print_success(&format!(
+        "Installed secrets {} in ~/.lium/env on the pod",
+        names.join(", ")
+    ));
+    Ok(())
+}
+
+/// Runs `defaults.bootstrap_script` on a ready pod, streaming its output.
+///
+/// The script is piped to `bash -s` after `~/.lium/env` is sourced, so it sees any
+/// `--secret` values. Like `lium exec`, only registered secrets are masked in its output.
+///
+/// # Errors
+/// * `CliError::InvalidInput` - If the pod has no SSH details or no SSH key is configured
+/// * `CliError::OperationFailed` - If the script exits with a non-zero status
+async fn run_bootstrap(config: &Config, pod: &PodInfo, path: &Path, script: String) -> Result<()> {
+    print_info(&format!("Running bootstrap script {}...", path.display()));
+
+    let command = RemoteCommand {
+        command: POD_BOOTSTRAP.to_string(),
+        stdin: script.into_bytes(),
+        stream: None,
+        cancel: CancelToken::new(),
+        timeout: None,
+    };
+    // Keep stdout clean for --output json and friends
+    let structured = is_structured_output();
+    let (code, ()) = exec_on_pod(
+        &pod_ssh_target(pod)?,
+        &pod_ssh_auth(config, pod)?,
+        command,
+        (),
+        move |_, stream, line| {
+            let stream = if structured {
+                OutputStream::Stderr
+            } else {
+                stream
+            };
+            write_console(stream, &format!("{}\n", redact_registered(line)));
+        },
+    )
+    .await?;
+    if code != 0 {
+        return Err(CliError::OperationFailed(format!(
+            "Bootstrap script {} exited with status {}",
+            path.display(),
+            code
+        )));
+    }
+
+    print_success("Bootstrap script finished");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::DefaultsConfig;
+    use crate::test_support::{
+        api_executor, api_pod, capture_output, fake_session, test_client, test_config,
+        test_config_with_ssh_key,
+    };
+    use lium_api::InMemoryTransport;
+    use reqwest::Method;
+    use serde_json::json;
+
+    fn up_args(image: &str, index: &str, name: &str) -> UpArgs {
+        UpArgs {
+            image: Some(image.to_string()),
+            gpu: None,
+            available: false,
+            index: Some(index.to_string()),
+            env: None,
+            secret: vec![],
+            ports: None,
+            ssh_key: None,
+            name: Some(name.to_string()),
+            yes: true,
+            wait: false,
+            wait_timeout: 300,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_up_rents_cheapest_available_executor() {
+        let transport = InMemoryTransport::new()
+            .with_executors(&[
+                api_executor("exec-rented", 0.5, true),
+                api_executor("exec-pricey", 3.0, false),
+                api_executor("exec-cheap", 1.0, false),
+            ])
+            .with_json(
+                Method::POST,
+                "executors/exec-cheap/rent",
+                json!({"id": "pod-1", "huid": "pod-huid", "status": "PENDING"}),
+            );
+        let client = test_client(&transport);
+
+        let result = handle_with_client(up_args("tmpl-1", "1", "my-pod"), &test_config(), &client)
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(
+            transport.request_log(),
+            vec![
+                (Method::GET, "executors".to_string()),
+                (Method::POST, "executors/exec-cheap/rent".to_string()),
+            ]
+        );
+        let output = serde_json::to_value(&result).unwrap();
+        assert_eq!(output["executor_id"], "exec-cheap");
+        assert_eq!(output["template_id"], "tmpl-1");
+        assert_eq!(output["pod"]["huid"], "pod-huid");
+        assert_eq!(output["pod"]["status"], "PENDING");
+        assert!(output["ready"].is_null());
+        assert_eq!(
+            transport.requests()[1].body,
+            Some(json!({
+                "pod_name": "my-pod",
+                "template_id": "tmpl-1",
+                "user_public_key": [],
+            }))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_up_wait_blocks_until_ssh_answers() {
+        use tokio::io::AsyncWriteExt;
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        tokio::spawn(async move {
+            while let Ok((mut socket, _)) = listener.accept().await {
+                let _ = socket.write_all(b"SSH-2.0-OpenSSH_9.6\r\n").await;
             }
+        });
+
+        let ssh_cmd = format!("ssh root@127.0.0.1 -p {}", port);
+        let transport = InMemoryTransport::new()
+            .with_executors(&[api_executor("exec-1", 1.0, false)])
+            .with_json(
+                Method::POST,
+                "executors/exec-1/rent",
+                json!({"id": "pod-1", "status": "PENDING"}),
+            )
+            .with_pods(&[api_pod("pod-1", "train", "exec-1", Some(&ssh_cmd))]);
+        let client = test_client(&transport);
+
+        let mut args = up_args("tmpl-1", "1", "train");
+        args.wait = true;
+        args.wait_timeout = 5;
+        handle_with_client(args, &test_config(), &client)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            transport.request_log().last(),
+            Some(&(Method::GET, "pods".to_string()))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_up_runs_bootstrap_script_once_ready() {
+        use tokio::io::AsyncWriteExt;
 
-            println!();
-            if let Some(huid) = pod_info.get("huid").and_then(|v| v.as_str()) {
-                println!("Use 'lium ssh {}' to connect", huid);
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        tokio::spawn(async move {
+            while let Ok((mut socket, _)) = listener.accept().await {
+                let _ = socket.write_all(b"SSH-2.0-OpenSSH_9.6\r\n").await;
             }
-        }
-        Err(e) => {
-            print_error(&format!("DEBUG: rent_pod API call failed"));
-            print_error(&format!("Failed to start pod: {}", e));
-            print_error(&format!("DEBUG: Error details: {:?}", e));
-            return Err(e.into());
-        }
+        });
+
+        let ssh_cmd = format!("ssh root@127.0.0.1 -p {}", port);
+        let transport = InMemoryTransport::new()
+            .with_executors(&[api_executor("exec-1", 1.0, false)])
+            .with_json(
+                Method::POST,
+                "executors/exec-1/rent",
+                json!({"id": "pod-bootstrap", "status": "PENDING"}),
+            )
+            .with_pods(&[api_pod("pod-bootstrap", "train", "exec-1", Some(&ssh_cmd))]);
+        let client = test_client(&transport);
+        fake_session(
+            "pod-bootstrap",
+            &[(OutputStream::Stdout, "installed requirements")],
+            0,
+        );
+
+        let dir = tempfile::tempdir().unwrap();
+        let script = dir.path().join("setup.sh");
+        fs::write(&script, "pip install -r requirements.txt\n").unwrap();
+        let mut config = test_config_with_ssh_key(dir.path());
+        config.data.defaults = Some(DefaultsConfig {
+            bootstrap_script: Some(script.display().to_string()),
+            ..Default::default()
+        });
+
+        // The script implies waiting, even without --wait
+        let mut args = up_args("tmpl-1", "1", "train");
+        args.wait_timeout = 5;
+        let (result, captured) = capture_output(handle_with_client(args, &config, &client)).await;
+
+        assert!(result.unwrap().unwrap().ready.is_some());
+        assert!(captured.stdout.contains("installed requirements"));
+        assert!(captured.contains("Bootstrap script finished"));
     }
 
-    Ok(())
+    #[tokio::test]
+    async fn test_up_forwards_env_and_ports() {
+        let transport = InMemoryTransport::new()
+            .with_executors(&[api_executor("exec-1", 1.0, false)])
+            .with_json(
+                Method::POST,
+                "executors/exec-1/rent",
+                json!({"id": "pod-1"}),
+            );
+        let client = test_client(&transport);
+
+        let mut args = up_args("tmpl-1", "1", "train");
+        args.env = Some("WANDB_MODE=offline,DEBUG=1,DB_URL=postgres://u:pw-7c41@db".to_string());
+        args.ports = Some("8888:8888".to_string());
+        let (result, captured) =
+            capture_output(handle_with_client(args, &test_config(), &client)).await;
+        result.unwrap();
+
+        // The summary names the variables without printing their values
+        let summary = captured
+            .stdout
+            .lines()
+            .chain(captured.stderr.lines())
+            .find(|line| line.contains("Environment Variables"))
+            .unwrap();
+        assert!(summary.ends_with("Environment Variables: DB_URL, DEBUG, WANDB_MODE"));
+
+        let body = transport.requests()[1].body.clone().unwrap();
+        assert_eq!(
+            body["env"],
+            json!({"WANDB_MODE": "offline", "DEBUG": "1", "DB_URL": "postgres://u:pw-7c41@db"})
+        );
+        assert_eq!(body["ports"], json!({"8888": "8888"}));
+    }
+
+    #[tokio::test]
+    async fn test_up_resolves_docker_image_to_template() {
+        let transport = InMemoryTransport::new()
+            .with_json(
+                Method::GET,
+                "templates",
+                json!([{
+                    "id": "tmpl-torch",
+                    "name": "PyTorch",
+                    "docker_image": "pytorch/pytorch",
+                    "docker_image_tag": "2.1",
+                }]),
+            )
+            .with_executors(&[api_executor("exec-1", 1.0, false)])
+            .with_json(
+                Method::POST,
+                "executors/exec-1/rent",
+                json!({"id": "pod-1"}),
+            );
+        let client = test_client(&transport);
+
+        handle_with_client(
+            up_args("pytorch/pytorch:2.1", "1", "torch-pod"),
+            &test_config(),
+            &client,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(
+            transport.request_log(),
+            vec![
+                (Method::GET, "templates".to_string()),
+                (Method::GET, "executors".to_string()),
+                (Method::POST, "executors/exec-1/rent".to_string()),
+            ]
+        );
+        let body = transport.requests()[2].body.clone().unwrap();
+        assert_eq!(body["template_id"], "tmpl-torch");
+    }
 }
@@ -1,8 +1,15 @@
+use crate::files::{write_atomic, FileLock};
+use crate::secrets;
+use crate::state::{State, STATE_FILE};
 use crate::{CliError, Result};
+use lium_api::{CacheMode, HttpSettings, ResponseCache};
+use lium_utils::redact::register_secret;
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::BTreeMap;
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::time::Duration;
 use thiserror::Error;
 
 /// Configuration-specific errors that can occur during config operations
@@ -14,6 +21,10 @@ use thiserror::Error;
 /// * `InvalidValue` - A configuration value is invalid for its field
 /// * `DirectoryCreationFailed` - Failed to create the config directory
 /// * `TomlError` - Error parsing or serializing TOML data
+/// * `ProfileNotFound` - The selected or named profile does not exist
+/// * `ProfileExists` - A profile with that name already exists
+/// * `UnknownKey` - A dotted key passed to `lium config get/set` is not a known setting
+/// * `Locked` - Another lium process held a config or state file lock for too long
 #[derive(Error, Debug)]
 pub enum ConfigError {
     #[error("Config file not found")]
@@ -33,17 +44,84 @@ pub enum ConfigError {
 
     #[error("TOML parsing error: {0}")]
     TomlError(String),
+
+    #[error("Profile '{0}' not found (see 'lium config profiles list')")]
+    ProfileNotFound(String),
+
+    #[error("Profile '{0}' already exists")]
+    ProfileExists(String),
+
+    #[error("Unknown config key '{key}'. {hint}")]
+    UnknownKey { key: String, hint: String },
+
+    #[error("{0} is locked by another lium process; try again")]
+    Locked(String),
+
+    #[error(
+        "{path} was written by a newer lium (config version {found}, this build supports \
+         {supported}); upgrade lium to change it"
+    )]
+    NewerVersion {
+        path: String,
+        found: u32,
+        supported: u32,
+    },
 }
 
+/// Current version of the `config.toml` schema, written to its `version` field.
+///
+/// Files without a `version` field predate versioning and are treated as version 1.
+pub const CONFIG_VERSION: u32 = 2;
+
+/// Upgrades a configuration table by one version, given the configuration directory
+type Migration = fn(&mut toml::Table, &Path) -> Result<()>;
+
+/// Ordered migration chain: `(from, description, migration)` upgrades `from` to `from + 1`.
+const MIGRATIONS: &[(u32, &str, Migration)] = &[(
+    1,
+    "move saved selections to state.json",
+    migrate_selections_to_state,
+)];
+
+/// Name that refers to the top-level (profile-less) settings.
+pub const DEFAULT_PROFILE: &str = "default";
+
+/// Dotted keys accepted by `lium config get` and `lium config set`.
+pub const CONFIG_KEYS: &[&str] = &[
+    "api.base_url",
+    "ssh.key_path",
+    "ssh.user",
+    "ssh.identities",
+    "ssh.agent",
+    "template.default_id",
+    "docker.username",
+    "theme.name",
+    "secrets.key_file",
+];
+
+/// Dotted keys holding credentials, which are redacted wherever configuration is shown.
+pub const CREDENTIAL_KEYS: &[&str] = &["api.api_key", "docker.token"];
+
+/// Placeholder shown instead of a plaintext credential
+const REDACTED: &str = lium_utils::redact::MASK;
+
 /// API configuration section containing API-related settings
 ///
 /// # Fields
 /// * `api_key` - Optional API key for authentication
 /// * `base_url` - Optional base URL for API requests
+/// * `timeout` - Optional seconds allowed for a whole request (defaults to 60)
+/// * `connect_timeout` - Optional seconds allowed to connect (defaults to 10)
+/// * `proxy` - Optional proxy URL for all API requests
+/// * `ca_bundle` - Optional path to a PEM file of extra root certificates to trust
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct ApiConfig {
     pub api_key: Option<String>,
     pub base_url: Option<String>,
+    pub timeout: Option<u64>,
+    pub connect_timeout: Option<u64>,
+    pub proxy: Option<String>,
+    pub ca_bundle: Option<String>,
 }
 
 /// SSH configuration section containing SSH-related settings
@@ -51,10 +129,14 @@ pub struct ApiConfig {
 /// # Fields
 /// * `key_path` - Optional path to SSH public key file
 /// * `user` - Optional SSH username
+/// * `identities` - Optional additional private key files offered after `key_path`
+/// * `agent` - Whether keys held by ssh-agent are offered as well (defaults to true)
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct SshConfig {
     pub key_path: Option<String>,
     pub user: Option<String>,
+    pub identities: Option<Vec<String>>,
+    pub agent: Option<bool>,
 }
 
 /// Template configuration section containing template-related settings
@@ -77,21 +159,151 @@ pub struct DockerConfig {
     pub token: Option<String>,
 }
 
+/// Theme configuration section
+///
+/// # Fields
+/// * `name` - Optional name of the colour theme used for CLI output
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct ThemeConfig {
+    pub name: Option<String>,
+}
+
+/// Secret store section
+///
+/// # Fields
+/// * `key_file` - Optional key file that unlocks `~/.lium/secrets` without a passphrase
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct SecretsConfig {
+    pub key_file: Option<String>,
+}
+
+/// Workflow defaults section, typically pinned per project in `.lium.toml`
+///
+/// # Fields
+/// * `gpu_types` - Optional GPU types `lium ls` shows when no GPU filter is given
+/// * `sync_paths` - Optional local paths `lium rsync <DESTINATION>` uploads when no
+///   source is given
+/// * `bootstrap_script` - Optional local script `lium up` runs on every new pod once it
+///   is ready
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct DefaultsConfig {
+    pub gpu_types: Option<Vec<String>>,
+    pub sync_paths: Option<Vec<String>>,
+    pub bootstrap_script: Option<String>,
+}
+
+/// Response cache configuration section
+///
+/// # Fields
+/// * `enabled` - Whether GET responses are cached under `~/.lium/cache` (defaults to true)
+/// * `executors_ttl` - Seconds a cached executor listing stays fresh
+/// * `templates_ttl` - Seconds a cached template listing stays fresh
+/// * `pods_ttl` - Seconds a cached pod listing stays fresh
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct CacheConfig {
+    pub enabled: Option<bool>,
+    pub executors_ttl: Option<u64>,
+    pub templates_ttl: Option<u64>,
+    pub pods_ttl: Option<u64>,
+}
+
+/// Settings for a named account profile (`[profiles.<name>]`)
+///
+/// Each field set in a profile overrides the matching top-level field while the
+/// profile is active; fields the profile leaves unset fall back to the top-level
+/// settings, so shared values like `ssh.key_path` only need to be written once.
+///
+/// # Fields
+/// * `api` - Optional API configuration
+/// * `ssh` - Optional SSH configuration
+/// * `template` - Optional template configuration
+/// * `docker` - Optional Docker configuration
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct ProfileConfig {
+    pub api: Option<ApiConfig>,
+    pub ssh: Option<SshConfig>,
+    pub template: Option<TemplateConfig>,
+    pub docker: Option<DockerConfig>,
+}
+
+/// Project-local settings read from the nearest `.lium.toml`
+///
+/// Project files are usually committed to a repository, so they may only pin
+/// workflow settings. Sections that could redirect credentials (`[api]`, `[docker]`)
+/// are ignored with a warning.
+///
+/// # Fields
+/// * `ssh` - Optional SSH configuration
+/// * `template` - Optional template configuration
+/// * `theme` - Optional theme configuration
+/// * `defaults` - Optional workflow defaults
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct ProjectConfig {
+    pub ssh: Option<SshConfig>,
+    pub template: Option<TemplateConfig>,
+    pub theme: Option<ThemeConfig>,
+    pub defaults: Option<DefaultsConfig>,
+}
+
+/// File name of project-local configuration files
+pub const PROJECT_CONFIG_FILE: &str = ".lium.toml";
+
+/// Sections of a project file that are never read
+const PROJECT_IGNORED_SECTIONS: &[&str] = &["api", "docker", "cache", "secrets", "profiles"];
+
+/// Where an effective configuration value came from
+///
+/// # Variants
+/// * `UserFile` - The top level of the user config file
+/// * `Profile` - The active profile in the user config file
+/// * `ProjectFile` - A project-local `.lium.toml`
+/// * `Env` - A `LIUM_<SECTION>_<KEY>` environment variable
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConfigOrigin {
+    UserFile(PathBuf),
+    Profile(String),
+    ProjectFile(PathBuf),
+    Env(String),
+}
+
+impl std::fmt::Display for ConfigOrigin {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConfigOrigin::UserFile(path) => write!(f, "user config {}", path.display()),
+            ConfigOrigin::Profile(name) => write!(f, "profile '{}'", name),
+            ConfigOrigin::ProjectFile(path) => write!(f, "project config {}", path.display()),
+            ConfigOrigin::Env(var) => write!(f, "env {}", var),
+        }
+    }
+}
+
 /// Main configuration structure containing all configuration sections
 ///
 /// # Fields
+/// * `version` - Schema version of the file (see [`CONFIG_VERSION`]), stamped on save
+/// * `default_profile` - Optional profile used when neither `--profile` nor `LIUM_PROFILE` is set
 /// * `api` - Optional API configuration
 /// * `ssh` - Optional SSH configuration
 /// * `template` - Optional template configuration
 /// * `docker` - Optional Docker configuration
-/// * `selections` - Optional generic key-value storage organized by sections
+/// * `theme` - Optional theme configuration
+/// * `defaults` - Optional workflow defaults
+/// * `cache` - Optional response cache configuration
+/// * `secrets` - Optional secret store configuration
+/// * `profiles` - Optional named account profiles
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct ConfigData {
+    pub version: Option<u32>,
+    pub default_profile: Option<String>,
     pub api: Option<ApiConfig>,
     pub ssh: Option<SshConfig>,
     pub template: Option<TemplateConfig>,
     pub docker: Option<DockerConfig>,
-    pub selections: Option<HashMap<String, HashMap<String, String>>>,
+    pub theme: Option<ThemeConfig>,
+    pub defaults: Option<DefaultsConfig>,
+    pub cache: Option<CacheConfig>,
+    pub secrets: Option<SecretsConfig>,
+    pub profiles: Option<BTreeMap<String, ProfileConfig>>,
 }
 
 /// Implementation of the API config trait for the main Config struct
@@ -109,24 +321,100 @@ impl lium_api::ApiConfig for Config {
 
     /// Get the base URL for API requests
     fn get_base_url(&self) -> std::result::Result<Option<String>, Self::Error> {
-        Ok(self.data.api.as_ref().and_then(|api| api.base_url.clone()))
+        Ok(self.api().base_url)
+    }
+
+    /// Get the response cache, honouring `[cache]` settings and the run's cache mode
+    fn get_cache(&self) -> std::result::Result<Option<ResponseCache>, Self::Error> {
+        let settings: CacheConfig = self.section("cache");
+
+        let mode = match self.cache_mode {
+            CacheMode::Normal if settings.enabled == Some(false) => CacheMode::Disabled,
+            mode => mode,
+        };
+        if mode == CacheMode::Disabled {
+            return Ok(None);
+        }
+
+        let mut cache = ResponseCache::new(self.cache_dir()).with_mode(mode);
+        for (endpoint, ttl) in [
+            ("executors", settings.executors_ttl),
+            ("templates", settings.templates_ttl),
+            ("pods", settings.pods_ttl),
+        ] {
+            if let Some(seconds) = ttl {
+                cache = cache.with_ttl(endpoint, Duration::from_secs(seconds));
+            }
+        }
+
+        Ok(Some(cache))
+    }
+
+    /// Get the HTTP client settings from the `[api]` section
+    fn get_http_settings(&self) -> std::result::Result<HttpSettings, Self::Error> {
+        let mut settings = HttpSettings::default();
+        let api = self.api();
+
+        for (field, value) in [
+            ("api.timeout", api.timeout),
+            ("api.connect_timeout", api.connect_timeout),
+        ] {
+            if value == Some(0) {
+                return Err(ConfigError::InvalidValue {
+                    field: field.to_string(),
+                    value: "0 (must be at least 1 second)".to_string(),
+                }
+                .into());
+            }
+        }
+
+        if let Some(seconds) = api.timeout {
+            settings = settings.with_timeout(Duration::from_secs(seconds));
+        }
+        if let Some(seconds) = api.connect_timeout {
+            settings = settings.with_connect_timeout(Duration::from_secs(seconds));
+        }
+        if let Some(proxy) = &api.proxy {
+            settings = settings.with_proxy(proxy.clone());
+        }
+        if let Some(ca_bundle) = &api.ca_bundle {
+            settings = settings.with_ca_bundle(expand_path(ca_bundle)?);
+        }
+
+        Ok(settings)
     }
 }
 
 /// Configuration manager for Lium that handles loading, saving, and accessing configuration
 ///
+/// Settings resolve through layers, highest precedence first: command-line flags
+/// (applied by each command), `LIUM_<SECTION>_<KEY>` environment variables, the nearest
+/// `.lium.toml`, the active profile, the top level of the user config file, and
+/// built-in defaults. Only the user config file (`data`) is ever saved.
+///
 /// # Fields
 /// * `config_path` - Path to the configuration file
 /// * `data` - The configuration data structure
+/// * `cache_mode` - How API responses are cached for this run (set from CLI flags, never saved)
+/// * `profile` - The active profile for this run, `None` for the top-level settings (never saved)
+/// * `project` - The project config file and its settings, if one was found (never saved)
+/// * `env_overrides` - Settings taken from `LIUM_<SECTION>_<KEY>` variables (never saved)
 #[derive(Debug, Clone)]
 pub struct Config {
     pub config_path: PathBuf,
     pub data: ConfigData,
+    pub cache_mode: CacheMode,
+    pub profile: Option<String>,
+    pub project: Option<(PathBuf, ProjectConfig)>,
+    pub env_overrides: ConfigData,
 }
 
 impl Config {
     /// Create a new config instance by loading from file or creating default
     ///
+    /// An old `config.json` is converted to TOML first, and older TOML files are
+    /// upgraded through the migration chain (see [`Config::load_from`]).
+    ///
     /// # Returns
     /// * `Result<Self>` - The new Config instance or an error
     ///
@@ -144,75 +432,132 @@ impl Config {
                 .map_err(|e| ConfigError::DirectoryCreationFailed(e.to_string()))?;
         }
 
-        let data = if config_path.exists() {
-            let content = fs::read_to_string(&config_path).map_err(CliError::Io)?;
-            toml::from_str(&content).map_err(|e| ConfigError::TomlError(e.to_string()))?
-        } else {
+        if !config_path.exists() {
             // Check for old JSON config and migrate
             let json_path = config_dir.join("config.json");
             if json_path.exists() {
                 migrate_from_json(&json_path, &config_path)?;
-                let content = fs::read_to_string(&config_path).map_err(CliError::Io)?;
-                toml::from_str(&content).map_err(|e| ConfigError::TomlError(e.to_string()))?
-            } else {
-                ConfigData::default()
             }
-        };
+        }
 
-        Ok(Config { config_path, data })
+        let mut config = Self::load_from(config_path)?;
+        if let Some(project_path) = std::env::current_dir()
+            .ok()
+            .and_then(|cwd| find_project_config(&cwd))
+        {
+            config.project = Some((project_path.clone(), load_project_config(&project_path)?));
+        }
+        config.env_overrides = env_overrides(|name| std::env::var(name).ok())?;
+        Ok(config)
+    }
+
+    /// Loads the configuration from the given file, upgrading older schema versions.
+    ///
+    /// When the file's `version` is older than [`CONFIG_VERSION`], it is copied to
+    /// `config.toml.v<version>.bak`, every later migration in the chain is applied in
+    /// order, and the upgraded file is written back. A missing file yields defaults.
+    ///
+    /// # Arguments
+    /// * `config_path` - Path to the `config.toml` file
+    ///
+    /// # Returns
+    /// * `Result<Self>` - The loaded Config instance or an error
+    ///
+    /// # Errors
+    /// * `ConfigError::TomlError` - If TOML parsing fails
+    /// * `ConfigError::InvalidFormat` - If the `version` field is not a positive integer
+    /// * `CliError::Io` - If file operations fail
+    pub fn load_from(config_path: PathBuf) -> Result<Self> {
+        let data = read_config_data(&config_path, false)?;
+        Ok(Config {
+            config_path,
+            data,
+            cache_mode: CacheMode::default(),
+            profile: None,
+            project: None,
+            env_overrides: ConfigData::default(),
+        })
+    }
+
+    /// Applies a change to the latest configuration on disk and saves it, holding the
+    /// config file lock throughout.
+    ///
+    /// The file is re-read under the lock before `change` runs, so settings written by
+    /// another lium process since this config was loaded are kept. On success `self` is
+    /// replaced by the saved configuration; on error nothing is written.
+    ///
+    /// # Arguments
+    /// * `change` - Modifies the freshly loaded configuration
+    ///
+    /// # Returns
+    /// * `Result<R>` - The value returned by `change`
+    ///
+    /// # Errors
+    /// * `ConfigError::Locked` - If another process holds the lock for too long
+    /// * `ConfigError::NewerVersion` - If the file was written by a newer lium
+    /// * Any error returned by `change`, or by reading and writing the file
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use lium_cli::Config;
+    /// # fn example(mut config: Config) -> lium_cli::Result<()> {
+    /// config.update(|config| config.set_key("ssh.user", "ubuntu"))?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn update<R>(&mut self, change: impl FnOnce(&mut Config) -> Result<R>) -> Result<R> {
+        let _lock = FileLock::acquire(&self.config_path)?;
+
+        let mut latest = self.clone();
+        latest.data = read_config_data(&latest.config_path, true)?;
+        let result = change(&mut latest)?;
+        write_config_data(&latest.config_path, &latest.data)?;
+
+        *self = latest;
+        Ok(result)
     }
 
     /// Save the configuration to file with atomic write and error handling
     ///
+    /// This overwrites the file with `data` as a whole; use [`Config::update`] to change
+    /// individual settings without discarding concurrent changes by other processes.
+    ///
     /// # Returns
     /// * `Result<()>` - Success or error
     ///
     /// # Errors
     /// * `ConfigError::TomlError` - If TOML serialization fails
     /// * `ConfigError::DirectoryCreationFailed` - If directory creation fails
+    /// * `ConfigError::Locked` - If another process holds the config file lock for too long
+    /// * `ConfigError::NewerVersion` - If the file was written by a newer lium
     /// * `CliError::Io` - If file operations fail
     pub fn save(&self) -> Result<()> {
-        // Serialize to TOML
-        let content = toml::to_string_pretty(&self.data)
-            .map_err(|e| ConfigError::TomlError(e.to_string()))?;
-
-        // Ensure parent directory exists
-        if let Some(parent) = self.config_path.parent() {
-            if !parent.exists() {
-                fs::create_dir_all(parent)
-                    .map_err(|e| ConfigError::DirectoryCreationFailed(e.to_string()))?;
-            }
-        }
-
-        // Write to a temporary file first, then rename (atomic operation)
-        let temp_path = self.config_path.with_extension("tmp");
-
-        // Write to temp file
-        fs::write(&temp_path, &content).map_err(CliError::Io)?;
-
-        // Atomic rename
-        fs::rename(&temp_path, &self.config_path).map_err(CliError::Io)?;
-
-        // Explicitly sync to disk
-        if let Ok(file) = fs::File::open(&self.config_path) {
-            let _ = file.sync_all(); // Ignore errors for sync_all as it's not critical
-        }
-
-        Ok(())
+        let _lock = FileLock::acquire(&self.config_path)?;
+        write_config_data(&self.config_path, &self.data)
     }
 
     /// Get the API key, checking environment variable first
     ///
+    /// A `secret:<name>` value is looked up in the encrypted secret store, which is
+    /// unlocked on first use.
+    ///
     /// # Returns
     /// * `Result<Option<String>>` - The API key if found, None if not set
+    ///
+    /// # Errors
+    /// * `SecretError` - If the value references a secret that cannot be read
     pub fn get_api_key(&self) -> Result<Option<String>> {
-        // Try environment variable first
-        if let Ok(key) = std::env::var("LIUM_API_KEY") {
-            return Ok(Some(key));
-        }
+        // Try environment variable first, then config
+        let key = match std::env::var("LIUM_API_KEY") {
+            Ok(key) => Some(key),
+            Err(_) => self.api().api_key,
+        };
 
-        // Then check config
-        Ok(self.data.api.as_ref().and_then(|api| api.api_key.clone()))
+        let key = key.map(|key| secrets::resolve(self, key)).transpose()?;
+        if let Some(key) = &key {
+            register_secret(key);
+        }
+        Ok(key)
     }
 
     /// Set the API key in the configuration
@@ -223,10 +568,7 @@ impl Config {
     /// # Returns
     /// * `Result<()>` - Success or error
     pub fn set_api_key(&mut self, api_key: &str) -> Result<()> {
-        if self.data.api.is_none() {
-            self.data.api = Some(ApiConfig::default());
-        }
-        self.data.api.as_mut().unwrap().api_key = Some(api_key.to_string());
+        self.api_mut().api_key = Some(api_key.to_string());
         Ok(())
     }
 
@@ -235,7 +577,7 @@ impl Config {
     /// # Returns
     /// * `Result<Option<String>>` - The path if set, None if not configured
     pub fn get_ssh_public_key_path(&self) -> Result<Option<String>> {
-        Ok(self.data.ssh.as_ref().and_then(|ssh| ssh.key_path.clone()))
+        Ok(self.ssh().key_path)
     }
 
     /// Set the SSH public key path in configuration
@@ -247,10 +589,7 @@ impl Config {
     /// * `Result<()>` - Success or error
     /// Set SSH public key path
     pub fn set_ssh_public_key_path(&mut self, path: &str) -> Result<()> {
-        if self.data.ssh.is_none() {
-            self.data.ssh = Some(SshConfig::default());
-        }
-        self.data.ssh.as_mut().unwrap().key_path = Some(path.to_string());
+        self.ssh_mut().key_path = Some(path.to_string());
         Ok(())
     }
 
@@ -264,17 +603,16 @@ impl Config {
     ///
     /// # Examples
     /// ```rust
+    /// # use lium_cli::Config;
+    /// # fn example() -> lium_cli::Result<()> {
     /// let config = Config::new()?;
     /// let user = config.get_ssh_user()?;
     /// assert_eq!(user, "root"); // Default value
+    /// # Ok(())
+    /// # }
     /// ```
     pub fn get_ssh_user(&self) -> Result<String> {
-        Ok(self
-            .data
-            .ssh
-            .as_ref()
-            .and_then(|ssh| ssh.user.clone())
-            .unwrap_or_else(|| "root".to_string()))
+        Ok(self.ssh().user.unwrap_or_else(|| "root".to_string()))
     }
 
     /// Sets the SSH user in the configuration.
@@ -291,15 +629,16 @@ impl Config {
     ///
     /// # Examples
     /// ```rust
+    /// # use lium_cli::Config;
+    /// # fn example() -> lium_cli::Result<()> {
     /// let mut config = Config::new()?;
     /// config.set_ssh_user("ubuntu")?;
     /// assert_eq!(config.get_ssh_user()?, "ubuntu");
+    /// # Ok(())
+    /// # }
     /// ```
     pub fn set_ssh_user(&mut self, user: &str) -> Result<()> {
-        if self.data.ssh.is_none() {
-            self.data.ssh = Some(SshConfig::default());
-        }
-        self.data.ssh.as_mut().unwrap().user = Some(user.to_string());
+        self.ssh_mut().user = Some(user.to_string());
         Ok(())
     }
 
@@ -313,18 +652,18 @@ impl Config {
     ///
     /// # Examples
     /// ```rust
+    /// # use lium_cli::Config;
+    /// # fn example() -> lium_cli::Result<()> {
     /// let config = Config::new()?;
     /// match config.get_default_template_id()? {
     ///     Some(id) => println!("Default template: {}", id),
     ///     None => println!("No default template set"),
     /// }
+    /// # Ok(())
+    /// # }
     /// ```
     pub fn get_default_template_id(&self) -> Result<Option<String>> {
-        Ok(self
-            .data
-            .template
-            .as_ref()
-            .and_then(|t| t.default_id.clone()))
+        Ok(self.template().default_id)
     }
 
     /// Sets the default template ID in the configuration.
@@ -341,15 +680,16 @@ impl Config {
     ///
     /// # Examples
     /// ```rust
+    /// # use lium_cli::Config;
+    /// # fn example() -> lium_cli::Result<()> {
     /// let mut config = Config::new()?;
     /// config.set_default_template_id("template-123")?;
     /// assert_eq!(config.get_default_template_id()?.unwrap(), "template-123");
+    /// # Ok(())
+    /// # }
     /// ```
     pub fn set_default_template_id(&mut self, template_id: &str) -> Result<()> {
-        if self.data.template.is_none() {
-            self.data.template = Some(TemplateConfig::default());
-        }
-        self.data.template.as_mut().unwrap().default_id = Some(template_id.to_string());
+        self.template_mut().default_id = Some(template_id.to_string());
         Ok(())
     }
 
@@ -363,19 +703,24 @@ impl Config {
     ///
     /// # Examples
     /// ```rust
+    /// # use lium_cli::Config;
+    /// # fn example() -> lium_cli::Result<()> {
     /// let config = Config::new()?;
     /// if let Some((username, token)) = config.get_docker_credentials()? {
     ///     println!("Docker credentials found for user: {}", username);
     /// }
+    /// # Ok(())
+    /// # }
     /// ```
     pub fn get_docker_credentials(&self) -> Result<Option<(String, String)>> {
-        if let Some(docker) = &self.data.docker {
-            match (&docker.username, &docker.token) {
-                (Some(u), Some(t)) => Ok(Some((u.clone(), t.clone()))),
-                _ => Ok(None),
+        let docker = self.docker();
+        match (docker.username, docker.token) {
+            (Some(u), Some(t)) => {
+                let token = secrets::resolve(self, t)?;
+                register_secret(&token);
+                Ok(Some((u, token)))
             }
-        } else {
-            Ok(None)
+            _ => Ok(None),
         }
     }
 
@@ -394,22 +739,213 @@ impl Config {
     ///
     /// # Examples
     /// ```rust
+    /// # use lium_cli::Config;
+    /// # fn example() -> lium_cli::Result<()> {
     /// let mut config = Config::new()?;
     /// config.set_docker_credentials("user123", "token456")?;
     /// let (username, token) = config.get_docker_credentials()?.unwrap();
     /// assert_eq!(username, "user123");
     /// assert_eq!(token, "token456");
+    /// # Ok(())
+    /// # }
     /// ```
     pub fn set_docker_credentials(&mut self, username: &str, token: &str) -> Result<()> {
-        if self.data.docker.is_none() {
-            self.data.docker = Some(DockerConfig::default());
-        }
-        let docker = self.data.docker.as_mut().unwrap();
+        let docker = self.docker_mut();
         docker.username = Some(username.to_string());
         docker.token = Some(token.to_string());
         Ok(())
     }
 
+    /// Gets the configured theme name
+    ///
+    /// # Returns
+    /// * `Option<String>` - The theme name if set
+    pub fn get_theme(&self) -> Option<String> {
+        self.section::<ThemeConfig>("theme").name
+    }
+
+    /// Sets the theme name
+    ///
+    /// # Arguments
+    /// * `name` - One of the themes listed by `lium theme list`
+    ///
+    /// # Errors
+    /// * `ConfigError::InvalidValue` - If the theme does not exist
+    pub fn set_theme(&mut self, name: &str) -> Result<()> {
+        if !crate::commands::theme::AVAILABLE_THEMES
+            .iter()
+            .any(|(theme, _)| *theme == name)
+        {
+            return Err(invalid_value(
+                "theme.name",
+                format!("'{}' is not a theme (see 'lium theme list')", name),
+            ));
+        }
+
+        self.data.theme.get_or_insert_with(Default::default).name = Some(name.to_string());
+        Ok(())
+    }
+
+    /// Gets the effective value of a setting by its dotted key.
+    ///
+    /// Returns the effective value after resolving every layer (see [`Config`]), so the
+    /// result may come from the active profile, a project `.lium.toml` or a
+    /// `LIUM_<SECTION>_<KEY>` variable; use [`Config::origin_of`] to find out which.
+    ///
+    /// # Arguments
+    /// * `key` - One of [`CONFIG_KEYS`], e.g. `ssh.user`
+    ///
+    /// # Returns
+    /// * `Result<Option<String>>` - The raw value if set, `None` if unset
+    ///
+    /// # Errors
+    /// * `ConfigError::UnknownKey` - If the key is not a known setting
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use lium_cli::Config;
+    /// # fn example(mut config: Config) -> lium_cli::Result<()> {
+    /// let base_url = config.get_key("api.base_url")?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn get_key(&self, key: &str) -> Result<Option<String>> {
+        Ok(match key {
+            "api.base_url" => self.api().base_url,
+            "ssh.key_path" => self.ssh().key_path,
+            "ssh.user" => self.ssh().user,
+            "ssh.identities" => self.ssh().identities.map(|paths| paths.join(", ")),
+            "ssh.agent" => self.ssh().agent.map(|agent| agent.to_string()),
+            "template.default_id" => self.template().default_id,
+            "docker.username" => self.docker().username,
+            "theme.name" => self.get_theme(),
+            "secrets.key_file" => self.secrets().key_file,
+            _ => return Err(unknown_key(key)),
+        })
+    }
+
+    /// Validates and sets a setting by its dotted key.
+    ///
+    /// Each key is checked before it is stored: `api.base_url` must be an http(s) URL,
+    /// `ssh.key_path` must point at an existing file, `ssh.identities` must be a
+    /// comma-separated list of existing files, `ssh.agent` must be `true` or `false`,
+    /// `theme.name` must be a known theme, and the remaining keys must be non-empty
+    /// without whitespace. Keys under `api`,
+    /// `ssh`, `template` and `docker` are written to the active profile.
+    ///
+    /// # Arguments
+    /// * `key` - One of [`CONFIG_KEYS`], e.g. `template.default_id`
+    /// * `value` - The new value
+    ///
+    /// # Returns
+    /// * `Result<()>` - Success or error; the config is unchanged on error
+    ///
+    /// # Errors
+    /// * `ConfigError::UnknownKey` - If the key is not a known setting, with suggestions
+    /// * `ConfigError::InvalidValue` - If the value fails validation for the key
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use lium_cli::Config;
+    /// # fn example(mut config: Config) -> lium_cli::Result<()> {
+    /// config.set_key("ssh.user", "ubuntu")?;
+    /// config.save()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn set_key(&mut self, key: &str, value: &str) -> Result<()> {
+        let value = value.trim();
+        match key {
+            "api.base_url" => {
+                let url = reqwest::Url::parse(value)
+                    .map_err(|e| invalid_value(key, format!("'{}' is not a URL: {}", value, e)))?;
+                if !matches!(url.scheme(), "http" | "https") {
+                    return Err(invalid_value(
+                        key,
+                        format!("'{}' must use http or https", value),
+                    ));
+                }
+                self.api_mut().base_url = Some(value.trim_end_matches('/').to_string());
+            }
+            "ssh.key_path" => {
+                if !expand_path(value)?.is_file() {
+                    return Err(invalid_value(key, format!("'{}' does not exist", value)));
+                }
+                self.set_ssh_public_key_path(value)?;
+            }
+            "ssh.user" => self.set_ssh_user(require_word(key, value)?)?,
+            "ssh.identities" => {
+                let paths: Vec<String> = value
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|path| !path.is_empty())
+                    .map(str::to_string)
+                    .collect();
+                if let Some(missing) = paths
+                    .iter()
+                    .find(|path| !expand_path(path).is_ok_and(|path| path.is_file()))
+                {
+                    return Err(invalid_value(key, format!("'{}' does not exist", missing)));
+                }
+                self.ssh_mut().identities = (!paths.is_empty()).then_some(paths);
+            }
+            "ssh.agent" => {
+                let agent = value.parse::<bool>().map_err(|_| {
+                    invalid_value(key, format!("'{}' must be true or false", value))
+                })?;
+                self.ssh_mut().agent = Some(agent);
+            }
+            "template.default_id" => self.set_default_template_id(require_word(key, value)?)?,
+            "docker.username" => {
+                self.docker_mut().username = Some(require_word(key, value)?.to_string())
+            }
+            "theme.name" => self.set_theme(value)?,
+            "secrets.key_file" => {
+                if !expand_path(value)?.is_file() {
+                    return Err(invalid_value(key, format!("'{}' does not exist", value)));
+                }
+                self.data
+                    .secrets
+                    .get_or_insert_with(Default::default)
+                    .key_file = Some(value.to_string());
+            }
+            _ => return Err(unknown_key(key)),
+        }
+        Ok(())
+    }
+
+    /// Resets the configuration file to defaults, keeping a copy of the old file.
+    ///
+    /// The existing file is copied to `config.toml.bak-<timestamp>` next to it before
+    /// an empty configuration is written.
+    ///
+    /// # Returns
+    /// * `Result<Option<PathBuf>>` - Path of the backup, `None` if there was no file to back up
+    ///
+    /// # Errors
+    /// * `CliError::Io` - If the backup or the new file cannot be written
+    pub fn reset(&mut self) -> Result<Option<PathBuf>> {
+        let _lock = FileLock::acquire(&self.config_path)?;
+        let backup = if self.config_path.exists() {
+            let mut name = self.config_path.as_os_str().to_owned();
+            name.push(format!(
+                ".bak-{}",
+                chrono::Local::now().format("%Y%m%d%H%M%S")
+            ));
+            let backup = PathBuf::from(name);
+            fs::copy(&self.config_path, &backup).map_err(CliError::Io)?;
+            Some(backup)
+        } else {
+            None
+        };
+
+        self.data = ConfigData::default();
+        self.profile = None;
+        // The backup keeps the old file, so even one from a newer lium may be replaced
+        write_config_file(&self.config_path, &self.data)?;
+        Ok(backup)
+    }
+
     /// Retrieves SSH public keys from the configured key file.
     ///
     /// This method reads the SSH public key file specified in the configuration,
@@ -427,36 +963,22 @@ impl Config {
     ///
     /// # Examples
     /// ```rust
+    /// # use lium_cli::Config;
+    /// # fn example() -> lium_cli::Result<()> {
     /// let config = Config::new()?;
     /// let keys = config.get_ssh_public_keys()?;
     /// for key in keys {
     ///     println!("Found SSH key: {}", key);
     /// }
+    /// # Ok(())
+    /// # }
     /// ```
     pub fn get_ssh_public_keys(&self) -> Result<Vec<String>> {
         let key_path = self
             .get_ssh_public_key_path()?
             .ok_or_else(|| ConfigError::MissingField("ssh.key_path".to_string()))?;
 
-        let expanded_path = expand_path(&key_path)?;
-        if !expanded_path.exists() {
-            return Err(ConfigError::NotFound.into());
-        }
-
-        let content = fs::read_to_string(&expanded_path).map_err(CliError::Io)?;
-
-        let keys: Vec<String> = content
-            .lines()
-            .map(|line| line.trim())
-            .filter(|line| !line.is_empty() && !line.starts_with('#'))
-            .map(|line| line.to_string())
-            .collect();
-
-        if keys.is_empty() {
-            return Err(ConfigError::InvalidFormat("No valid SSH keys found".to_string()).into());
-        }
-
-        Ok(keys)
+        read_ssh_public_keys(&expand_path(&key_path)?)
     }
 
     /// Get SSH private key path (derive from public key path)
@@ -465,66 +987,882 @@ impl Config {
             .get_ssh_public_key_path()?
             .ok_or_else(|| ConfigError::MissingField("ssh.key_path".to_string()))?;
 
-        let expanded_path = expand_path(&public_key_path)?;
-
-        // Remove .pub extension if present
-        let private_key_path = if expanded_path.extension() == Some(std::ffi::OsStr::new("pub")) {
-            expanded_path.with_extension("")
-        } else {
-            expanded_path
-        };
+        Ok(private_key_path(&expand_path(&public_key_path)?))
+    }
 
-        Ok(private_key_path)
+    /// Returns the private keys to offer when connecting, most specific first.
+    ///
+    /// The key next to `ssh.key_path` comes first, followed by `ssh.identities`. Entries
+    /// may name either the private key or its `.pub` file. Files that do not exist are
+    /// kept; authentication skips them, so keys can live solely in ssh-agent.
+    ///
+    /// # Returns
+    /// * `Result<Vec<PathBuf>>` - Private key paths without duplicates, possibly empty
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use lium_cli::Config;
+    /// # fn example(mut config: Config) -> lium_cli::Result<()> {
+    /// config.set_key("ssh.identities", "~/.ssh/id_ed25519, ~/.ssh/work_rsa")?;
+    /// let identities = config.get_ssh_identities()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn get_ssh_identities(&self) -> Result<Vec<PathBuf>> {
+        let ssh = self.ssh();
+        let mut identities = Vec::new();
+        for path in ssh.key_path.iter().chain(ssh.identities.iter().flatten()) {
+            let identity = private_key_path(&expand_path(path)?);
+            if !identities.contains(&identity) {
+                identities.push(identity);
+            }
+        }
+        Ok(identities)
     }
 
-    /// Show all configuration as a formatted string
-    pub fn show_config(&self) -> String {
-        toml::to_string_pretty(&self.data).unwrap_or_else(|_| "Error formatting config".to_string())
+    /// Whether keys held by ssh-agent are offered when connecting (`ssh.agent`, default true)
+    pub fn ssh_agent_enabled(&self) -> bool {
+        self.ssh().agent.unwrap_or(true)
     }
 
-    /// Set a generic key-value pair in the config
+    /// Selects the profile used for this run.
+    ///
+    /// The profile is chosen from, in order: `requested` (the `--profile` flag), the
+    /// `LIUM_PROFILE` environment variable, and the `default_profile` setting. The name
+    /// `default` selects the top-level settings.
     ///
     /// # Arguments
-    /// * `section` - The section name to store the value in
-    /// * `key` - The key to store the value under
-    /// * `value` - The value to store
+    /// * `requested` - Profile named on the command line, if any
     ///
     /// # Returns
     /// * `Result<()>` - Success or error
-    pub fn set_value(&mut self, section: &str, key: &str, value: &str) -> Result<()> {
-        if self.data.selections.is_none() {
-            self.data.selections = Some(HashMap::new());
+    ///
+    /// # Errors
+    /// * `ConfigError::ProfileNotFound` - If a profile named by `--profile` or
+    ///   `LIUM_PROFILE` does not exist
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use lium_cli::Config;
+    /// # fn example() -> lium_cli::Result<()> {
+    /// let mut config = Config::new()?;
+    /// config.select_profile(Some("team"))?;
+    /// assert_eq!(config.active_profile(), Some("team"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn select_profile(&mut self, requested: Option<&str>) -> Result<()> {
+        let env_profile = std::env::var("LIUM_PROFILE").ok();
+        self.select_profile_from(requested, env_profile.as_deref())
+    }
+
+    fn select_profile_from(
+        &mut self,
+        requested: Option<&str>,
+        env_profile: Option<&str>,
+    ) -> Result<()> {
+        let explicit = requested
+            .or(env_profile)
+            .map(str::to_string)
+            .filter(|name| !name.trim().is_empty());
+
+        let name = match explicit {
+            Some(name) => {
+                if name != DEFAULT_PROFILE && !self.has_profile(&name) {
+                    return Err(ConfigError::ProfileNotFound(name).into());
+                }
+                name
+            }
+            None => match self.data.default_profile.clone() {
+                // A stale default must not lock users out of `lium config profiles use`
+                Some(name) if name != DEFAULT_PROFILE && !self.has_profile(&name) => {
+                    log::warn!(
+                        "Default profile '{}' does not exist; using top-level settings",
+                        name
+                    );
+                    DEFAULT_PROFILE.to_string()
+                }
+                Some(name) => name,
+                None => DEFAULT_PROFILE.to_string(),
+            },
+        };
+
+        self.profile = (name != DEFAULT_PROFILE).then_some(name);
+        Ok(())
+    }
+
+    /// Returns the name of the active profile, or `None` for the top-level settings
+    pub fn active_profile(&self) -> Option<&str> {
+        self.profile.as_deref()
+    }
+
+    /// Returns the names of all configured profiles, sorted
+    pub fn profile_names(&self) -> Vec<String> {
+        self.data
+            .profiles
+            .as_ref()
+            .map(|profiles| profiles.keys().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    /// Returns true if a profile with the given name exists
+    pub fn has_profile(&self, name: &str) -> bool {
+        self.data
+            .profiles
+            .as_ref()
+            .is_some_and(|profiles| profiles.contains_key(name))
+    }
+
+    /// Adds a new named profile
+    ///
+    /// # Arguments
+    /// * `name` - The profile name (letters, digits, `-` and `_`)
+    /// * `profile` - The profile's settings
+    ///
+    /// # Errors
+    /// * `ConfigError::InvalidValue` - If the name is empty, `default`, or has other characters
+    /// * `ConfigError::ProfileExists` - If a profile with that name already exists
+    pub fn add_profile(&mut self, name: &str, profile: ProfileConfig) -> Result<()> {
+        let valid = !name.is_empty()
+            && name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+        if !valid || name == DEFAULT_PROFILE {
+            return Err(ConfigError::InvalidValue {
+                field: "profile name".to_string(),
+                value: name.to_string(),
+            }
+            .into());
+        }
+        if self.has_profile(name) {
+            return Err(ConfigError::ProfileExists(name.to_string()).into());
         }
 
-        let selections = self.data.selections.as_mut().unwrap();
-        if !selections.contains_key(section) {
-            selections.insert(section.to_string(), HashMap::new());
+        self.data
+            .profiles
+            .get_or_insert_with(BTreeMap::new)
+            .insert(name.to_string(), profile);
+        Ok(())
+    }
+
+    /// Removes a named profile, clearing it as the default profile if needed
+    ///
+    /// # Errors
+    /// * `ConfigError::ProfileNotFound` - If the profile does not exist
+    pub fn remove_profile(&mut self, name: &str) -> Result<()> {
+        let removed = self
+            .data
+            .profiles
+            .as_mut()
+            .and_then(|profiles| profiles.remove(name));
+        if removed.is_none() {
+            return Err(ConfigError::ProfileNotFound(name.to_string()).into());
         }
 
-        selections
-            .get_mut(section)
-            .unwrap()
-            .insert(key.to_string(), value.to_string());
+        if self.data.profiles.as_ref().is_some_and(BTreeMap::is_empty) {
+            self.data.profiles = None;
+        }
+        if self.data.default_profile.as_deref() == Some(name) {
+            self.data.default_profile = None;
+        }
+        if self.profile.as_deref() == Some(name) {
+            self.profile = None;
+        }
         Ok(())
     }
 
-    /// Get a generic key-value pair from the config
+    /// Sets the profile used when neither `--profile` nor `LIUM_PROFILE` is given
     ///
     /// # Arguments
-    /// * `section` - The section name to retrieve from
-    /// * `key` - The key to retrieve
+    /// * `name` - A profile name, or `default` for the top-level settings
+    ///
+    /// # Errors
+    /// * `ConfigError::ProfileNotFound` - If the profile does not exist
+    pub fn set_default_profile(&mut self, name: &str) -> Result<()> {
+        if name == DEFAULT_PROFILE {
+            self.data.default_profile = None;
+            return Ok(());
+        }
+        if !self.has_profile(name) {
+            return Err(ConfigError::ProfileNotFound(name.to_string()).into());
+        }
+
+        self.data.default_profile = Some(name.to_string());
+        Ok(())
+    }
+
+    /// Effective API settings (see [`Config`] for how layers are resolved)
+    pub fn api(&self) -> ApiConfig {
+        self.section("api")
+    }
+
+    /// Effective SSH settings (see [`Config`] for how layers are resolved)
+    pub fn ssh(&self) -> SshConfig {
+        self.section("ssh")
+    }
+
+    /// Effective template settings (see [`Config`] for how layers are resolved)
+    pub fn template(&self) -> TemplateConfig {
+        self.section("template")
+    }
+
+    /// Effective Docker settings (see [`Config`] for how layers are resolved)
+    pub fn docker(&self) -> DockerConfig {
+        self.section("docker")
+    }
+
+    /// Effective workflow defaults (see [`Config`] for how layers are resolved)
+    pub fn defaults(&self) -> DefaultsConfig {
+        self.section("defaults")
+    }
+
+    /// Returns the effective `defaults.sync_paths`.
+    ///
+    /// Relative paths set in a `.lium.toml` are resolved against the directory of that
+    /// file, so they work from anywhere inside the project; other relative paths are
+    /// left relative to the current directory.
     ///
     /// # Returns
-    /// * `Result<Option<String>>` - The value if found, None if not found
-    pub fn get_value(&self, section: &str, key: &str) -> Result<Option<String>> {
-        Ok(self
-            .data
-            .selections
-            .as_ref()
-            .and_then(|sections| sections.get(section))
-            .and_then(|keys| keys.get(key))
-            .cloned())
+    /// * `Result<Vec<PathBuf>>` - The paths in the configured order, empty if unset
+    ///
+    /// # Errors
+    /// * `ConfigError::InvalidValue` - If a `~` path is used and there is no home directory
+    pub fn sync_paths(&self) -> Result<Vec<PathBuf>> {
+        self.defaults()
+            .sync_paths
+            .unwrap_or_default()
+            .iter()
+            .map(|path| self.defaults_path("defaults.sync_paths", path))
+            .collect()
+    }
+
+    /// Returns the effective `defaults.bootstrap_script`, resolved like
+    /// [`Config::sync_paths`].
+    ///
+    /// # Returns
+    /// * `Result<Option<PathBuf>>` - The script path, `None` if unset
+    ///
+    /// # Errors
+    /// * `ConfigError::InvalidValue` - If a `~` path is used and there is no home directory
+    pub fn bootstrap_script(&self) -> Result<Option<PathBuf>> {
+        self.defaults()
+            .bootstrap_script
+            .map(|path| self.defaults_path("defaults.bootstrap_script", &path))
+            .transpose()
     }
+
+    /// Expands a path from the `defaults` section, anchoring relative paths that came
+    /// from a project file to that file's directory
+    fn defaults_path(&self, key: &str, path: &str) -> Result<PathBuf> {
+        let expanded = expand_path(path)?;
+        match self.origin_of(key) {
+            Some(ConfigOrigin::ProjectFile(file)) if expanded.is_relative() => Ok(file
+                .parent()
+                .map(|dir| dir.join(&expanded))
+                .unwrap_or(expanded)),
+            _ => Ok(expanded),
+        }
+    }
+
+    /// Effective secret store settings (see [`Config`] for how layers are resolved)
+    pub fn secrets(&self) -> SecretsConfig {
+        self.section("secrets")
+    }
+
+    /// Lists every configured value with the layer it came from.
+    ///
+    /// # Returns
+    /// * `Vec<(String, serde_json::Value, ConfigOrigin)>` - Dotted key, effective value and
+    ///   origin, sorted by key; unset keys are omitted
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use lium_cli::Config;
+    /// # fn example(mut config: Config) -> lium_cli::Result<()> {
+    /// for (key, value, origin) in config.origins() {
+    ///     println!("{} = {}  # {}", key, value, origin);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn origins(&self) -> Vec<(String, serde_json::Value, ConfigOrigin)> {
+        let mut effective = BTreeMap::new();
+        for (origin, layer) in self.layers() {
+            let serde_json::Value::Object(sections) = layer else {
+                continue;
+            };
+            for (section, fields) in sections {
+                let serde_json::Value::Object(fields) = fields else {
+                    continue;
+                };
+                for (field, value) in fields.into_iter().filter(|(_, v)| !v.is_null()) {
+                    let origin = match &origin {
+                        ConfigOrigin::Env(_) => ConfigOrigin::Env(env_var_name(&section, &field)),
+                        origin => origin.clone(),
+                    };
+                    let key = format!("{}.{}", section, field);
+                    let value = if CREDENTIAL_KEYS.contains(&key.as_str()) {
+                        serde_json::Value::String(redact(value.as_str().unwrap_or_default()))
+                    } else {
+                        value
+                    };
+                    effective.insert(key, (value, origin));
+                }
+            }
+        }
+
+        effective
+            .into_iter()
+            .map(|(key, (value, origin))| (key, value, origin))
+            .collect()
+    }
+
+    /// Returns the layer a dotted key's effective value came from, `None` if unset
+    pub fn origin_of(&self, key: &str) -> Option<ConfigOrigin> {
+        self.origins()
+            .into_iter()
+            .find(|(k, ..)| k == key)
+            .map(|(.., origin)| origin)
+    }
+
+    /// Configuration layers as JSON objects of sections, lowest precedence first
+    fn layers(&self) -> Vec<(ConfigOrigin, serde_json::Value)> {
+        let user = ConfigData {
+            version: None,
+            default_profile: None,
+            profiles: None,
+            ..self.data.clone()
+        };
+        let mut layers = vec![(
+            ConfigOrigin::UserFile(self.config_path.clone()),
+            to_json(&user),
+        )];
+        if let (Some(name), Some(profile)) = (&self.profile, self.active_profile_config()) {
+            layers.push((ConfigOrigin::Profile(name.clone()), to_json(profile)));
+        }
+        if let Some((path, project)) = &self.project {
+            layers.push((ConfigOrigin::ProjectFile(path.clone()), to_json(project)));
+        }
+        // The variable name is filled in per field by `origins`
+        layers.push((
+            ConfigOrigin::Env(String::new()),
+            to_json(&self.env_overrides),
+        ));
+        layers
+    }
+
+    /// Merges one section across all layers; fields set in higher layers win
+    fn section<T>(&self, name: &str) -> T
+    where
+        T: DeserializeOwned + Default,
+    {
+        let mut merged = serde_json::Map::new();
+        for (_, layer) in self.layers() {
+            if let Some(serde_json::Value::Object(fields)) = layer.get(name) {
+                for (field, value) in fields.iter().filter(|(_, v)| !v.is_null()) {
+                    merged.insert(field.clone(), value.clone());
+                }
+            }
+        }
+
+        serde_json::from_value(serde_json::Value::Object(merged)).unwrap_or_default()
+    }
+
+    fn active_profile_config(&self) -> Option<&ProfileConfig> {
+        let name = self.profile.as_ref()?;
+        self.data.profiles.as_ref()?.get(name)
+    }
+
+    /// The settings that setters write to: the active profile, or the top level
+    fn writable_profile(&mut self) -> Option<&mut ProfileConfig> {
+        let name = self.profile.clone()?;
+        Some(
+            self.data
+                .profiles
+                .get_or_insert_with(BTreeMap::new)
+                .entry(name)
+                .or_default(),
+        )
+    }
+
+    fn api_mut(&mut self) -> &mut ApiConfig {
+        if self.profile.is_some() {
+            self.writable_profile()
+                .unwrap()
+                .api
+                .get_or_insert_with(Default::default)
+        } else {
+            self.data.api.get_or_insert_with(Default::default)
+        }
+    }
+
+    fn ssh_mut(&mut self) -> &mut SshConfig {
+        if self.profile.is_some() {
+            self.writable_profile()
+                .unwrap()
+                .ssh
+                .get_or_insert_with(Default::default)
+        } else {
+            self.data.ssh.get_or_insert_with(Default::default)
+        }
+    }
+
+    fn template_mut(&mut self) -> &mut TemplateConfig {
+        if self.profile.is_some() {
+            self.writable_profile()
+                .unwrap()
+                .template
+                .get_or_insert_with(Default::default)
+        } else {
+            self.data.template.get_or_insert_with(Default::default)
+        }
+    }
+
+    fn docker_mut(&mut self) -> &mut DockerConfig {
+        if self.profile.is_some() {
+            self.writable_profile()
+                .unwrap()
+                .docker
+                .get_or_insert_with(Default::default)
+        } else {
+            self.data.docker.get_or_insert_with(Default::default)
+        }
+    }
+
+    /// Get the directory holding the config file
+    pub fn config_dir(&self) -> PathBuf {
+        self.config_path
+            .parent()
+            .map(Path::to_path_buf)
+            .unwrap_or_default()
+    }
+
+    /// Get the directory API responses are cached in (`cache/` next to the config file)
+    pub fn cache_dir(&self) -> PathBuf {
+        self.config_dir().join("cache")
+    }
+
+    /// Show all configuration as a formatted string, with plaintext credentials redacted
+    pub fn show_config(&self) -> String {
+        let mut data = self.data.clone();
+        for (api, docker) in credential_sections(&mut data) {
+            if let Some(key) = api.and_then(|api| api.api_key.as_mut()) {
+                *key = redact(key);
+            }
+            if let Some(token) = docker.and_then(|docker| docker.token.as_mut()) {
+                *token = redact(token);
+            }
+        }
+        toml::to_string_pretty(&data).unwrap_or_else(|_| "Error formatting config".to_string())
+    }
+
+    /// Returns true if the config file holds a credential in plaintext rather than
+    /// as a `secret:` reference
+    pub fn has_plaintext_credentials(&self) -> bool {
+        !self.clone().take_plaintext_credentials().is_empty()
+    }
+
+    /// Replaces every plaintext credential in the config file with a `secret:` reference.
+    ///
+    /// Top-level credentials become `secret:api_key` and `secret:docker_token`; those in
+    /// a profile are prefixed with its name, e.g. `secret:work_api_key`.
+    ///
+    /// # Returns
+    /// * `Vec<(String, String)>` - Secret name and value of each credential taken out,
+    ///   to be stored in the secret store before the config is saved
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use lium_cli::{secrets::SecretStore, Config};
+    /// # fn example(mut config: Config, mut store: SecretStore) -> lium_cli::Result<()> {
+    /// let moved = config.take_plaintext_credentials();
+    /// store.update(|secrets| moved.iter().try_for_each(|(n, v)| secrets.set(n, v)))?;
+    /// config.save()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn take_plaintext_credentials(&mut self) -> Vec<(String, String)> {
+        fn take(slot: Option<&mut String>, name: String, taken: &mut Vec<(String, String)>) {
+            if let Some(value) = slot.filter(|value| secrets::secret_ref(value).is_none()) {
+                let reference = format!("{}{}", secrets::SECRET_REF_PREFIX, name);
+                taken.push((name, std::mem::replace(value, reference)));
+            }
+        }
+
+        let mut taken = Vec::new();
+        let prefixes = std::iter::once(String::new())
+            .chain(self.profile_names().iter().map(|name| format!("{}_", name)))
+            .collect::<Vec<_>>();
+        for ((api, docker), prefix) in credential_sections(&mut self.data)
+            .into_iter()
+            .zip(prefixes)
+        {
+            take(
+                api.and_then(|api| api.api_key.as_mut()),
+                format!("{}api_key", prefix),
+                &mut taken,
+            );
+            take(
+                docker.and_then(|docker| docker.token.as_mut()),
+                format!("{}docker_token", prefix),
+                &mut taken,
+            );
+        }
+        taken
+    }
+}
+
+fn invalid_value(field: &str, value: String) -> CliError {
+    ConfigError::InvalidValue {
+        field: field.to_string(),
+        value,
+    }
+    .into()
+}
+
+/// Rejects empty values and values containing whitespace
+fn require_word<'a>(key: &str, value: &'a str) -> Result<&'a str> {
+    if value.is_empty() || value.contains(char::is_whitespace) {
+        return Err(invalid_value(
+            key,
+            format!("'{}' must be non-empty without spaces", value),
+        ));
+    }
+    Ok(value)
+}
+
+/// Returns the private key belonging to `path`, stripping a `.pub` extension
+pub(crate) fn private_key_path(path: &Path) -> PathBuf {
+    if path.extension() == Some(std::ffi::OsStr::new("pub")) {
+        path.with_extension("")
+    } else {
+        path.to_path_buf()
+    }
+}
+
+/// Reads the public keys listed in `path`, skipping blank lines and comments.
+///
+/// # Errors
+/// * `ConfigError::NotFound` - If the file doesn't exist
+/// * `ConfigError::InvalidFormat` - If the file lists no keys
+/// * `CliError::Io` - If the file cannot be read
+pub(crate) fn read_ssh_public_keys(path: &Path) -> Result<Vec<String>> {
+    if !path.exists() {
+        return Err(ConfigError::NotFound.into());
+    }
+
+    let content = fs::read_to_string(path).map_err(CliError::Io)?;
+
+    let keys: Vec<String> = content
+        .lines()
+        .map(|line| line.trim())
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(|line| line.to_string())
+        .collect();
+
+    if keys.is_empty() {
+        return Err(ConfigError::InvalidFormat("No valid SSH keys found".to_string()).into());
+    }
+
+    Ok(keys)
+}
+
+/// Builds the error for an unknown dotted key, suggesting the closest known keys.
+///
+/// A key matches if it is a close spelling of a known key or if it names the same
+/// field without its section (`user` suggests `ssh.user`).
+fn unknown_key(key: &str) -> CliError {
+    let key_lower = key.to_lowercase();
+    let suggestions: Vec<&str> = CONFIG_KEYS
+        .iter()
+        .copied()
+        .filter(|known| {
+            let field = known.rsplit('.').next().unwrap_or(known);
+            strsim::normalized_damerau_levenshtein(&key_lower, known) >= 0.7
+                || strsim::normalized_damerau_levenshtein(&key_lower, field) >= 0.7
+        })
+        .collect();
+
+    let hint = if suggestions.is_empty() {
+        format!("Valid keys: {}", CONFIG_KEYS.join(", "))
+    } else {
+        format!("Did you mean: {}?", suggestions.join(", "))
+    };
+
+    ConfigError::UnknownKey {
+        key: key.to_string(),
+        hint,
+    }
+    .into()
+}
+
+/// Reads a config file, upgrading it through the migration chain if needed.
+///
+/// Migrating rewrites the file, so an outdated file is re-read and upgraded while
+/// holding the config lock; `locked` tells whether the caller already holds it.
+/// A missing file yields defaults.
+fn read_config_data(config_path: &Path, locked: bool) -> Result<ConfigData> {
+    if !config_path.exists() {
+        return Ok(ConfigData::default());
+    }
+
+    let content = fs::read_to_string(config_path).map_err(CliError::Io)?;
+    let mut table: toml::Table =
+        toml::from_str(&content).map_err(|e| ConfigError::TomlError(e.to_string()))?;
+
+    let version = table_version(&table)?;
+    if version > CONFIG_VERSION {
+        log::warn!(
+            "{} was written by a newer lium (config version {}, this build supports {}); unknown settings are ignored and the file will not be saved",
+            config_path.display(),
+            version,
+            CONFIG_VERSION
+        );
+    }
+
+    let migrated = version < CONFIG_VERSION;
+    if migrated {
+        if !locked {
+            // Another process may be migrating too; start over under the lock
+            let _lock = FileLock::acquire(config_path)?;
+            return read_config_data(config_path, true);
+        }
+
+        let mut backup = config_path.as_os_str().to_owned();
+        backup.push(format!(".v{}.bak", version));
+        fs::copy(config_path, &backup).map_err(CliError::Io)?;
+
+        let config_dir = config_path.parent().unwrap_or(Path::new("."));
+        for (from, description, migrate) in MIGRATIONS.iter().filter(|(from, ..)| *from >= version)
+        {
+            log::info!(
+                "Migrating config from version {} to {}: {}",
+                from,
+                from + 1,
+                description
+            );
+            migrate(&mut table, config_dir)?;
+        }
+        table.insert("version".to_string(), i64::from(CONFIG_VERSION).into());
+    }
+
+    let data = toml::Value::Table(table)
+        .try_into()
+        .map_err(|e: toml::de::Error| ConfigError::TomlError(e.to_string()))?;
+    if migrated {
+        write_config_data(config_path, &data)?;
+    }
+    Ok(data)
+}
+
+/// Returns the schema version of a config table; files without one are version 1
+fn table_version(table: &toml::Table) -> Result<u32> {
+    match table.get("version") {
+        None => Ok(1),
+        Some(toml::Value::Integer(version)) if *version >= 1 => Ok(*version as u32),
+        Some(other) => Err(ConfigError::InvalidFormat(format!(
+            "version must be a positive integer, found {}",
+            other
+        ))
+        .into()),
+    }
+}
+
+/// Writes config data atomically, stamped with the schema version this build writes.
+/// Callers must hold the config lock.
+///
+/// A file written by a newer lium is never overwritten: this build would stamp it with
+/// an older version and drop the settings it does not know.
+///
+/// # Errors
+/// * `ConfigError::NewerVersion` - If the file on disk has a newer schema version
+fn write_config_data(config_path: &Path, data: &ConfigData) -> Result<()> {
+    if let Ok(content) = fs::read_to_string(config_path) {
+        let table: toml::Table =
+            toml::from_str(&content).map_err(|e| ConfigError::TomlError(e.to_string()))?;
+        let found = table_version(&table)?;
+        if found > CONFIG_VERSION {
+            return Err(ConfigError::NewerVersion {
+                path: config_path.display().to_string(),
+                found,
+                supported: CONFIG_VERSION,
+            }
+            .into());
+        }
+    }
+    write_config_file(config_path, data)
+}
+
+/// Writes config data atomically without looking at the file it replaces
+fn write_config_file(config_path: &Path, data: &ConfigData) -> Result<()> {
+    let data = ConfigData {
+        version: Some(CONFIG_VERSION),
+        ..data.clone()
+    };
+    let content =
+        toml::to_string_pretty(&data).map_err(|e| ConfigError::TomlError(e.to_string()))?;
+    write_atomic(config_path, content.as_bytes())
+}
+
+/// Migration 1 → 2: moves the `[selections]` table to the state file.
+///
+/// Version 1 stored the last `ls`/`ps` listings as JSON strings under
+/// `selections.last_selection.data` and `selections.last_pod_selection.data`, which
+/// made every listing rewrite the user's config file.
+fn migrate_selections_to_state(table: &mut toml::Table, config_dir: &Path) -> Result<()> {
+    let Some(selections) = table.remove("selections") else {
+        return Ok(());
+    };
+
+    let saved = |section: &str| {
+        selections
+            .get(section)
+            .and_then(|keys| keys.get("data"))
+            .and_then(toml::Value::as_str)
+            .and_then(|data| serde_json::from_str::<serde_json::Value>(data).ok())
+    };
+
+    State::load_from(config_dir.join(STATE_FILE))?.update(|data| {
+        data.last_executor_selection = data
+            .last_executor_selection
+            .take()
+            .or_else(|| saved("last_selection"));
+        data.last_pod_selection = data
+            .last_pod_selection
+            .take()
+            .or_else(|| saved("last_pod_selection"));
+    })
+}
+
+/// The `[api]` and `[docker]` sections at the top level, then in each profile by name
+fn credential_sections(
+    data: &mut ConfigData,
+) -> Vec<(Option<&mut ApiConfig>, Option<&mut DockerConfig>)> {
+    let mut sections = vec![(data.api.as_mut(), data.docker.as_mut())];
+    if let Some(profiles) = data.profiles.as_mut() {
+        sections.extend(
+            profiles
+                .values_mut()
+                .map(|profile| (profile.api.as_mut(), profile.docker.as_mut())),
+        );
+    }
+    sections
+}
+
+/// Hides a credential for display; `secret:` references are shown as they are
+fn redact(value: &str) -> String {
+    if secrets::secret_ref(value).is_some() {
+        value.to_string()
+    } else {
+        REDACTED.to_string()
+    }
+}
+
+fn to_json<T: Serialize>(value: &T) -> serde_json::Value {
+    serde_json::to_value(value).unwrap_or(serde_json::Value::Null)
+}
+
+/// Environment variable that overrides a setting, e.g. `LIUM_SSH_USER` for `ssh.user`
+pub fn env_var_name(section: &str, field: &str) -> String {
+    format!("LIUM_{}_{}", section, field).to_uppercase()
+}
+
+/// Reads `LIUM_<SECTION>_<KEY>` overrides for every section a user may configure.
+///
+/// # Arguments
+/// * `var` - Looks up an environment variable (`std::env::var` outside tests)
+///
+/// # Errors
+/// * `ConfigError::InvalidValue` - If a variable's value does not fit its setting
+fn env_overrides(var: impl Fn(&str) -> Option<String>) -> Result<ConfigData> {
+    Ok(ConfigData {
+        api: env_section("api", &var)?,
+        ssh: env_section("ssh", &var)?,
+        template: env_section("template", &var)?,
+        docker: env_section("docker", &var)?,
+        theme: env_section("theme", &var)?,
+        defaults: env_section("defaults", &var)?,
+        cache: env_section("cache", &var)?,
+        secrets: env_section("secrets", &var)?,
+        ..Default::default()
+    })
+}
+
+/// Reads the overrides for one section.
+///
+/// Each variable is tried as a string, then as a JSON scalar (numbers, booleans), then
+/// as a comma-separated list, and the first form the field accepts is used.
+fn env_section<T>(section: &str, var: &impl Fn(&str) -> Option<String>) -> Result<Option<T>>
+where
+    T: Serialize + DeserializeOwned + Default,
+{
+    let serde_json::Value::Object(fields) = to_json(&T::default()) else {
+        return Ok(None);
+    };
+
+    let mut overrides = serde_json::Map::new();
+    for field in fields.keys() {
+        let name = env_var_name(section, field);
+        let Some(raw) = var(&name) else {
+            continue;
+        };
+
+        let candidates = [
+            serde_json::Value::String(raw.clone()),
+            serde_json::from_str(&raw).unwrap_or(serde_json::Value::Null),
+            raw.split(',')
+                .map(|item| serde_json::Value::String(item.trim().to_string()))
+                .collect(),
+        ];
+        let value = candidates
+            .into_iter()
+            .filter(|candidate| !candidate.is_null())
+            .find(|candidate| {
+                serde_json::from_value::<T>(serde_json::json!({ field: candidate })).is_ok()
+            })
+            .ok_or_else(|| invalid_value(&name, raw.clone()))?;
+        overrides.insert(field.clone(), value);
+    }
+
+    if overrides.is_empty() {
+        return Ok(None);
+    }
+    Ok(serde_json::from_value(serde_json::Value::Object(overrides)).ok())
+}
+
+/// Finds the nearest `.lium.toml`, walking up from `start` to the filesystem root
+fn find_project_config(start: &Path) -> Option<PathBuf> {
+    start
+        .ancestors()
+        .map(|dir| dir.join(PROJECT_CONFIG_FILE))
+        .find(|path| path.is_file())
+}
+
+/// Loads a project config file, ignoring sections project files may not set
+///
+/// # Errors
+/// * `ConfigError::TomlError` - If the file is not valid TOML
+/// * `CliError::Io` - If the file cannot be read
+fn load_project_config(path: &Path) -> Result<ProjectConfig> {
+    let content = fs::read_to_string(path).map_err(CliError::Io)?;
+    let mut table: toml::Table = toml::from_str(&content)
+        .map_err(|e| ConfigError::TomlError(format!("{}: {}", path.display(), e)))?;
+
+    for section in PROJECT_IGNORED_SECTIONS {
+        if table.remove(*section).is_some() {
+            log::warn!(
+                "Ignoring [{}] in {}: project files may only set ssh, template, theme and defaults",
+                section,
+                path.display()
+            );
+        }
+    }
+
+    toml::Value::Table(table)
+        .try_into()
+        .map_err(|e: toml::de::Error| {
+            ConfigError::TomlError(format!("{}: {}", path.display(), e)).into()
+        })
 }
 
 /// Loads the configuration from the default location.
@@ -544,8 +1882,12 @@ impl Config {
 ///
 /// # Examples
 /// ```rust
+/// # use lium_cli::config::load_config;
+/// # fn example() -> lium_cli::Result<()> {
 /// let config = load_config()?;
 /// println!("Loaded configuration: {}", config.show_config());
+/// # Ok(())
+/// # }
 /// ```
 pub fn load_config() -> Result<Config> {
     Config::new()
@@ -569,8 +1911,12 @@ pub fn load_config() -> Result<Config> {
 ///
 /// # Examples
 /// ```rust
+/// # use lium_cli::config::load_config_async;
+/// # async fn example() -> lium_cli::Result<()> {
 /// let config = load_config_async().await?;
 /// println!("Loaded configuration: {}", config.show_config());
+/// # Ok(())
+/// # }
 /// ```
 pub async fn load_config_async() -> Result<Config> {
     // For config loading, we can use spawn_blocking since it's not nested
@@ -592,11 +1938,11 @@ pub async fn load_config_async() -> Result<Config> {
 /// * `ConfigError::DirectoryCreationFailed` - If the home directory cannot be found
 ///
 /// # Examples
-/// ```rust
+/// ```rust,ignore
 /// let config_dir = get_config_dir()?;
 /// println!("Configuration directory: {}", config_dir.display());
 /// ```
-fn get_config_dir() -> Result<PathBuf> {
+pub(crate) fn get_config_dir() -> Result<PathBuf> {
     let home_dir = home::home_dir().ok_or_else(|| {
         ConfigError::DirectoryCreationFailed("Could not find home directory".to_string())
     })?;
@@ -619,11 +1965,11 @@ fn get_config_dir() -> Result<PathBuf> {
 /// * `ConfigError::InvalidValue` - If the home directory cannot be found
 ///
 /// # Examples
-/// ```rust
+/// ```rust,ignore
 /// let expanded = expand_path("~/config.toml")?;
 /// println!("Expanded path: {}", expanded.display());
 /// ```
-fn expand_path(path: &str) -> Result<PathBuf> {
+pub(crate) fn expand_path(path: &str) -> Result<PathBuf> {
     let expanded = if path.starts_with('~') {
         let home_dir = home::home_dir().ok_or_else(|| ConfigError::InvalidValue {
             field: "path".to_string(),
@@ -662,7 +2008,7 @@ fn expand_path(path: &str) -> Result<PathBuf> {
 /// * `ConfigError::TomlError` - If the TOML serialization fails
 ///
 /// # Examples
-/// ```rust
+/// ```rust,ignore
 /// migrate_from_json(
 ///     Path::new("~/.lium/config.json"),
 ///     Path::new("~/.lium/config.toml")
@@ -680,7 +2026,7 @@ fn migrate_from_json(json_path: &Path, toml_path: &Path) -> Result<()> {
     if let Some(api_key) = json_data.get("api_key").and_then(|v| v.as_str()) {
         config.api = Some(ApiConfig {
             api_key: Some(api_key.to_string()),
-            base_url: None,
+            ..Default::default()
         });
     }
 
@@ -734,6 +2080,577 @@ fn migrate_from_json(json_path: &Path, toml_path: &Path) -> Result<()> {
 }
 
 // TODO: Add config validation functions
-// TODO: Add config schema versioning
 // TODO: Add more specific getter/setter methods
 // TODO: Add config file watching for live updates
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::capture_output;
+    use crate::{Cli, Commands, ConfigCommands};
+    use clap::Parser;
+    use lium_api::ApiConfig as _;
+    use lium_api::{HttpResponse, InMemoryTransport, LiumApiClient, RetryPolicy};
+    use reqwest::{Method, StatusCode};
+    use std::collections::HashMap;
+
+    fn config_with_mode(cache_mode: CacheMode, cache: Option<CacheConfig>) -> Config {
+        Config {
+            config_path: PathBuf::from("/tmp/lium-test/config.toml"),
+            data: ConfigData {
+                cache,
+                ..Default::default()
+            },
+            cache_mode,
+            profile: None,
+            project: None,
+            env_overrides: ConfigData::default(),
+        }
+    }
+
+    #[test]
+    fn test_cache_flags_select_cache_mode() {
+        let mode = |args: &[&str]| Cli::try_parse_from(args).unwrap().cache_mode();
+        assert_eq!(mode(&["lium", "ps"]), CacheMode::Normal);
+        assert_eq!(mode(&["lium", "ps", "--refresh"]), CacheMode::Refresh);
+        assert_eq!(mode(&["lium", "--no-cache", "ls"]), CacheMode::Disabled);
+        assert_eq!(mode(&["lium", "ps", "--offline"]), CacheMode::Offline);
+        assert!(Cli::try_parse_from(["lium", "ps", "--offline", "--refresh"]).is_err());
+    }
+
+    #[test]
+    fn test_get_cache_honours_settings_and_mode() {
+        let config = config_with_mode(CacheMode::Normal, None);
+        let cache = config.get_cache().unwrap().unwrap();
+        assert_eq!(cache.mode(), CacheMode::Normal);
+        assert!(cache.dir().starts_with("/tmp/lium-test/cache"));
+
+        assert!(config_with_mode(CacheMode::Disabled, None)
+            .get_cache()
+            .unwrap()
+            .is_none());
+
+        let disabled = CacheConfig {
+            enabled: Some(false),
+            ..Default::default()
+        };
+        assert!(config_with_mode(CacheMode::Normal, Some(disabled.clone()))
+            .get_cache()
+            .unwrap()
+            .is_none());
+        // Offline still reads whatever is cached, even with caching turned off
+        assert!(config_with_mode(CacheMode::Offline, Some(disabled))
+            .get_cache()
+            .unwrap()
+            .is_some());
+    }
+
+    #[test]
+    fn test_http_settings_from_api_section() {
+        let mut config = config_with_mode(CacheMode::Normal, None);
+        assert_eq!(config.get_http_settings().unwrap(), HttpSettings::default());
+
+        config.data = toml::from_str(
+            r#"
+            [api]
+            timeout = 15
+            proxy = "http://proxy.corp:3128"
+            ca_bundle = "/etc/ssl/corp.pem"
+            "#,
+        )
+        .unwrap();
+        let settings = config.get_http_settings().unwrap();
+        assert_eq!(settings.timeout, Duration::from_secs(15));
+        assert_eq!(settings.connect_timeout, lium_api::DEFAULT_CONNECT_TIMEOUT);
+        assert_eq!(settings.proxy.as_deref(), Some("http://proxy.corp:3128"));
+        assert_eq!(settings.ca_bundle, Some(PathBuf::from("/etc/ssl/corp.pem")));
+
+        // A missing CA bundle is reported when the client is built
+        assert!(lium_api::LiumApiClient::from_config(&Config {
+            data: ConfigData {
+                api: Some(ApiConfig {
+                    api_key: Some("key".to_string()),
+                    ..config.data.api.clone().unwrap()
+                }),
+                ..Default::default()
+            },
+            ..config.clone()
+        })
+        .is_err());
+
+        config.data.api.as_mut().unwrap().timeout = Some(0);
+        assert!(config.get_http_settings().is_err());
+    }
+
+    #[test]
+    fn test_unversioned_config_is_migrated_with_backup() {
+        let dir = tempfile::tempdir().unwrap();
+        let config_path = dir.path().join("config.toml");
+        let original = r#"
+            [ssh]
+            user = "ubuntu"
+
+            [selections.last_pod_selection]
+            data = '{"pods":[{"id":"pod-1"}]}'
+        "#;
+        fs::write(&config_path, original).unwrap();
+
+        let config = Config::load_from(config_path.clone()).unwrap();
+        assert_eq!(config.data.version, Some(CONFIG_VERSION));
+        assert_eq!(config.get_ssh_user().unwrap(), "ubuntu");
+
+        let backup = fs::read_to_string(dir.path().join("config.toml.v1.bak")).unwrap();
+        assert_eq!(backup, original);
+        let upgraded = fs::read_to_string(&config_path).unwrap();
+        assert!(upgraded.starts_with(&format!("version = {}", CONFIG_VERSION)));
+        assert!(!upgraded.contains("selections"));
+
+        let state = State::load_from(dir.path().join(STATE_FILE)).unwrap();
+        assert_eq!(
+            state.data.last_pod_selection,
+            Some(serde_json::json!({"pods": [{"id": "pod-1"}]}))
+        );
+
+        // Current files load without another migration or backup
+        fs::remove_file(dir.path().join("config.toml.v1.bak")).unwrap();
+        Config::load_from(config_path).unwrap();
+        assert!(!dir.path().join("config.toml.v1.bak").exists());
+    }
+
+    #[test]
+    fn test_newer_config_version_is_never_downgraded() {
+        let dir = tempfile::tempdir().unwrap();
+        let config_path = dir.path().join("config.toml");
+        let original = r#"
+            version = 99
+
+            [ssh]
+            user = "ubuntu"
+            jump_host = "bastion.example"
+        "#;
+        fs::write(&config_path, original).unwrap();
+
+        // Reading still works, ignoring settings this build does not know
+        let mut config = Config::load_from(config_path.clone()).unwrap();
+        assert_eq!(config.get_ssh_user().unwrap(), "ubuntu");
+
+        config.set_key("ssh.user", "root").unwrap();
+        let error = config.save().unwrap_err();
+        assert!(matches!(
+            error,
+            CliError::Config(ConfigError::NewerVersion { found: 99, .. })
+        ));
+        assert!(error.to_string().contains("upgrade lium"));
+        let error = config
+            .update(|config| config.set_key("ssh.user", "root"))
+            .unwrap_err();
+        assert!(matches!(
+            error,
+            CliError::Config(ConfigError::NewerVersion { .. })
+        ));
+        assert_eq!(fs::read_to_string(&config_path).unwrap(), original);
+
+        // An explicit reset replaces it, keeping the old file as a backup
+        let backup = config.reset().unwrap().unwrap();
+        assert_eq!(fs::read_to_string(backup).unwrap(), original);
+    }
+
+    #[test]
+    fn test_layers_resolve_env_over_project_over_profile() {
+        let mut config = profiles_config();
+        config.select_profile_from(Some("team"), None).unwrap();
+
+        let dir = tempfile::tempdir().unwrap();
+        let nested = dir.path().join("src/models");
+        fs::create_dir_all(&nested).unwrap();
+        let project_path = dir.path().join(PROJECT_CONFIG_FILE);
+        fs::write(
+            &project_path,
+            r#"
+            [api]
+            base_url = "https://attacker.example"
+
+            [ssh]
+            user = "project-user"
+
+            [defaults]
+            gpu_types = ["H100"]
+            "#,
+        )
+        .unwrap();
+        let found = find_project_config(&nested).unwrap();
+        assert_eq!(found, project_path);
+        config.project = Some((found.clone(), load_project_config(&found).unwrap()));
+
+        let vars = HashMap::from([
+            ("LIUM_API_TIMEOUT", "20"),
+            ("LIUM_TEMPLATE_DEFAULT_ID", "tmpl-env"),
+            ("LIUM_DEFAULTS_GPU_TYPES", "A100, H100"),
+            ("LIUM_CACHE_ENABLED", "false"),
+        ]);
+        config.env_overrides = env_overrides(|name| vars.get(name).map(|v| v.to_string())).unwrap();
+
+        // Project files cannot redirect the API
+        assert_eq!(
+            config.api().base_url.as_deref(),
+            Some("https://lium.example")
+        );
+        assert_eq!(config.api().timeout, Some(20));
+        assert_eq!(config.get_ssh_user().unwrap(), "project-user");
+        assert_eq!(
+            config.get_key("template.default_id").unwrap().as_deref(),
+            Some("tmpl-env")
+        );
+        assert_eq!(
+            config.defaults().gpu_types,
+            Some(vec!["A100".to_string(), "H100".to_string()])
+        );
+        assert!(config.get_cache().unwrap().is_none());
+
+        assert_eq!(
+            config.origin_of("api.api_key"),
+            Some(ConfigOrigin::Profile("team".to_string()))
+        );
+        assert_eq!(
+            config.origin_of("api.base_url"),
+            Some(ConfigOrigin::UserFile(config.config_path.clone()))
+        );
+        assert_eq!(
+            config.origin_of("ssh.user"),
+            Some(ConfigOrigin::ProjectFile(found))
+        );
+        assert_eq!(
+            config.origin_of("defaults.gpu_types"),
+            Some(ConfigOrigin::Env("LIUM_DEFAULTS_GPU_TYPES".to_string()))
+        );
+
+        let bad = HashMap::from([("LIUM_API_TIMEOUT", "soon")]);
+        assert!(env_overrides(|name| bad.get(name).map(|v| v.to_string())).is_err());
+    }
+
+    #[test]
+    fn test_sync_paths_and_bootstrap_script_resolve_across_layers() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut config = config_with_mode(CacheMode::Normal, None);
+        assert!(config.sync_paths().unwrap().is_empty());
+        assert!(config.bootstrap_script().unwrap().is_none());
+
+        let project_path = dir.path().join(PROJECT_CONFIG_FILE);
+        fs::write(
+            &project_path,
+            r#"
+            [defaults]
+            sync_paths = ["src/", "configs/train.yaml"]
+            bootstrap_script = "scripts/setup.sh"
+            "#,
+        )
+        .unwrap();
+        config.project = Some((
+            project_path.clone(),
+            load_project_config(&project_path).unwrap(),
+        ));
+
+        // Relative project paths are anchored to the project directory
+        assert_eq!(
+            config.sync_paths().unwrap(),
+            vec![
+                dir.path().join("src/"),
+                dir.path().join("configs/train.yaml")
+            ]
+        );
+        assert_eq!(
+            config.bootstrap_script().unwrap(),
+            Some(dir.path().join("scripts/setup.sh"))
+        );
+        assert_eq!(
+            config.origin_of("defaults.bootstrap_script"),
+            Some(ConfigOrigin::ProjectFile(project_path))
+        );
+
+        let vars = HashMap::from([
+            ("LIUM_DEFAULTS_SYNC_PATHS", "/data/a, /data/b"),
+            ("LIUM_DEFAULTS_BOOTSTRAP_SCRIPT", "setup.sh"),
+        ]);
+        config.env_overrides = env_overrides(|name| vars.get(name).map(|v| v.to_string())).unwrap();
+        assert_eq!(
+            config.sync_paths().unwrap(),
+            vec![PathBuf::from("/data/a"), PathBuf::from("/data/b")]
+        );
+        // Paths from the environment stay relative to the current directory
+        assert_eq!(
+            config.bootstrap_script().unwrap(),
+            Some(PathBuf::from("setup.sh"))
+        );
+        assert_eq!(
+            config.origin_of("defaults.sync_paths"),
+            Some(ConfigOrigin::Env("LIUM_DEFAULTS_SYNC_PATHS".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_concurrent_updates_are_not_lost() {
+        let dir = tempfile::tempdir().unwrap();
+        let config_path = dir.path().join("config.toml");
+        let mut config = Config::load_from(config_path.clone()).unwrap();
+        config
+            .update(|config| config.set_api_key("original-key"))
+            .unwrap();
+
+        // Every writer loads the file before any of them writes, then updates it
+        let writers = 16;
+        let barrier = std::sync::Arc::new(std::sync::Barrier::new(writers));
+        let handles: Vec<_> = (0..writers)
+            .map(|i| {
+                let config_path = config_path.clone();
+                let barrier = barrier.clone();
+                std::thread::spawn(move || {
+                    let mut config = Config::load_from(config_path).unwrap();
+                    barrier.wait();
+                    config
+                        .update(|config| {
+                            config.add_profile(&format!("writer-{}", i), ProfileConfig::default())
+                        })
+                        .unwrap();
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        let config = Config::load_from(config_path).unwrap();
+        assert_eq!(config.profile_names().len(), writers);
+        assert_eq!(config.api().api_key.as_deref(), Some("original-key"));
+        // Only the config file and its lock file remain
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 2);
+    }
+
+    fn profiles_config() -> Config {
+        let mut config = config_with_mode(CacheMode::Normal, None);
+        config.data = toml::from_str(
+            r#"
+            default_profile = "personal"
+
+            [api]
+            api_key = "top-key"
+            base_url = "https://lium.example"
+
+            [ssh]
+            user = "ubuntu"
+
+            [profiles.team.api]
+            api_key = "team-key"
+            timeout = 5
+
+            [profiles.personal.ssh]
+            key_path = "~/.ssh/personal.pub"
+            "#,
+        )
+        .unwrap();
+        config
+    }
+
+    #[test]
+    fn test_profile_selection_precedence() {
+        let mut config = profiles_config();
+        config.select_profile_from(None, None).unwrap();
+        assert_eq!(config.active_profile(), Some("personal"));
+
+        config.select_profile_from(None, Some("team")).unwrap();
+        assert_eq!(config.active_profile(), Some("team"));
+
+        config
+            .select_profile_from(Some("default"), Some("team"))
+            .unwrap();
+        assert_eq!(config.active_profile(), None);
+
+        assert!(matches!(
+            config.select_profile_from(Some("nope"), None),
+            Err(CliError::Config(ConfigError::ProfileNotFound(_)))
+        ));
+
+        // A stale default profile falls back to the top-level settings
+        config.data.default_profile = Some("gone".to_string());
+        config.select_profile_from(None, None).unwrap();
+        assert_eq!(config.active_profile(), None);
+    }
+
+    #[test]
+    fn test_active_profile_overrides_top_level_fields() {
+        let mut config = profiles_config();
+        config.select_profile_from(Some("team"), None).unwrap();
+
+        assert_eq!(config.api().api_key.as_deref(), Some("team-key"));
+        assert_eq!(
+            config.get_base_url().unwrap().as_deref(),
+            Some("https://lium.example")
+        );
+        assert_eq!(
+            config.get_http_settings().unwrap().timeout,
+            Duration::from_secs(5)
+        );
+        assert_eq!(config.get_ssh_user().unwrap(), "ubuntu");
+
+        // Setters write into the active profile and leave the top level alone
+        config.set_ssh_user("admin").unwrap();
+        assert_eq!(config.get_ssh_user().unwrap(), "admin");
+        assert_eq!(
+            config.data.ssh.as_ref().unwrap().user.as_deref(),
+            Some("ubuntu")
+        );
+        config.select_profile_from(Some("personal"), None).unwrap();
+        assert_eq!(config.get_ssh_user().unwrap(), "ubuntu");
+        assert_eq!(config.api().api_key.as_deref(), Some("top-key"));
+
+        assert!(matches!(
+            config.add_profile("team", ProfileConfig::default()),
+            Err(CliError::Config(ConfigError::ProfileExists(_)))
+        ));
+        assert!(config
+            .add_profile("default", ProfileConfig::default())
+            .is_err());
+    }
+
+    #[test]
+    fn test_ssh_identities_and_agent_settings() {
+        let dir = tempfile::tempdir().unwrap();
+        let work_key = dir.path().join("work_rsa");
+        fs::write(dir.path().join("id_ed25519.pub"), "ssh-ed25519 AAAA me\n").unwrap();
+        fs::write(&work_key, "private").unwrap();
+        let mut config = config_with_mode(CacheMode::default(), None);
+        assert!(config.get_ssh_identities().unwrap().is_empty());
+        assert!(config.ssh_agent_enabled());
+
+        let key_path = dir.path().join("id_ed25519.pub");
+        config
+            .set_key("ssh.key_path", key_path.to_str().unwrap())
+            .unwrap();
+        config
+            .set_key(
+                "ssh.identities",
+                &format!("{}, {}", work_key.display(), key_path.display()),
+            )
+            .unwrap();
+        config.set_key("ssh.agent", "false").unwrap();
+
+        // The key_path key comes first and duplicates collapse to the private key
+        assert_eq!(
+            config.get_ssh_identities().unwrap(),
+            vec![dir.path().join("id_ed25519"), work_key.clone()]
+        );
+        assert!(!config.ssh_agent_enabled());
+        assert_eq!(
+            config.get_key("ssh.agent").unwrap().as_deref(),
+            Some("false")
+        );
+        assert!(config.set_key("ssh.agent", "maybe").is_err());
+        assert!(config
+            .set_key("ssh.identities", "/nonexistent/lium_key")
+            .is_err());
+        assert_eq!(
+            config.ssh().identities.unwrap()[0],
+            work_key.display().to_string()
+        );
+    }
+
+    #[test]
+    fn test_plaintext_credentials_are_redacted_and_migrated() {
+        let mut config = profiles_config();
+        config.data.docker = Some(DockerConfig {
+            username: Some("me".to_string()),
+            token: Some("secret:docker_token".to_string()),
+        });
+
+        let shown = config.show_config();
+        assert!(!shown.contains("top-key") && !shown.contains("team-key"));
+        assert!(shown.contains(REDACTED) && shown.contains("secret:docker_token"));
+        let api_key = config
+            .origins()
+            .into_iter()
+            .find(|(k, ..)| k == "api.api_key");
+        assert_eq!(api_key.unwrap().1, serde_json::json!(REDACTED));
+        assert!(config.has_plaintext_credentials());
+
+        let taken = config.take_plaintext_credentials();
+        assert_eq!(
+            taken,
+            vec![
+                ("api_key".to_string(), "top-key".to_string()),
+                ("team_api_key".to_string(), "team-key".to_string()),
+            ]
+        );
+        assert!(!config.has_plaintext_credentials());
+        assert_eq!(
+            config.data.profiles.as_ref().unwrap()["team"]
+                .api
+                .as_ref()
+                .unwrap()
+                .api_key
+                .as_deref(),
+            Some("secret:team_api_key")
+        );
+        // References that are already in place are left for the store to resolve
+        assert_eq!(
+            config.docker().token.as_deref(),
+            Some("secret:docker_token")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_api_keys_from_config_and_env_never_reach_output() {
+        let config = profiles_config();
+        let key = config.get_api_key().unwrap().unwrap();
+        let line = crate::display::status_line("✓".into(), &format!("Using API key {}", key));
+        assert!(!line.contains(&key));
+
+        let mut config = profiles_config();
+        let vars = HashMap::from([("LIUM_API_API_KEY", "env-key-93c0be71")]);
+        config.env_overrides = env_overrides(|name| vars.get(name).map(|v| v.to_string())).unwrap();
+        assert_eq!(
+            config.get_api_key().unwrap().as_deref(),
+            Some("env-key-93c0be71")
+        );
+        let line = crate::display::status_line("✗".into(), "Request with env-key-93c0be71 failed");
+        assert!(!line.contains("env-key-93c0be71"));
+
+        // Show the config, then make a request the API rejects by echoing the key back
+        config.cache_mode = CacheMode::Disabled;
+        let transport = InMemoryTransport::new().with_response(
+            Method::GET,
+            "pods",
+            HttpResponse::json_body(
+                StatusCode::UNAUTHORIZED,
+                &serde_json::json!({ "detail": "Invalid API key env-key-93c0be71" }),
+            ),
+        );
+        let (_, captured) = capture_output(async {
+            for origin in [false, true] {
+                let show = ConfigCommands::Show { origin };
+                crate::commands::config::handle(show, &config)
+                    .await
+                    .unwrap();
+            }
+            let client = LiumApiClient::from_config(&config)
+                .unwrap()
+                .with_retry_policy(RetryPolicy::disabled())
+                .with_transport(transport.clone());
+            let Commands::Ps(args) = Cli::try_parse_from(["lium", "ps"]).unwrap().command else {
+                unreachable!("parsed a ps command");
+            };
+            let error = crate::commands::ps::handle_with_client(args, &client)
+                .await
+                .unwrap_err();
+            crate::report_error(&error);
+        })
+        .await;
+
+        assert!(captured.stdout.contains("Current Configuration"));
+        assert!(captured.stderr.contains("Error:"));
+        assert!(captured.log.contains("https://lium.example/pods"));
+        for key in ["top-key", "env-key-93c0be71"] {
+            assert!(!captured.stdout.contains(key), "{} in stdout", key);
+            assert!(!captured.stderr.contains(key), "{} in stderr", key);
+            assert!(!captured.log.contains(key), "{} in log", key);
+        }
+    }
+}
@@ -1,8 +1,14 @@
 use crate::Result;
 use colored::Colorize;
 use dialoguer::{theme::ColorfulTheme, Confirm, Input, Select};
+use lium_api::{LiumApiClient, PodEvent};
 use lium_core::{ExecutorInfo, PodInfo, TemplateInfo};
+use lium_utils::{format_uptime, redact, OutputStream};
+use serde::Serialize;
+use serde_json::Value;
 use std::collections::HashMap;
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::{Arc, PoisonError, RwLock};
 
 /// A utility struct for formatting and displaying tabular data in the terminal.
 ///
@@ -17,6 +23,7 @@ use std::collections::HashMap;
 ///
 /// # Examples
 /// ```rust
+/// # use lium_cli::display::Table;
 /// let mut table = Table::new(vec!["Name".to_string(), "Age".to_string()]);
 /// table.add_row(vec!["John".to_string(), "30".to_string()]);
 /// table.print();
@@ -41,6 +48,7 @@ impl Table {
     ///
     /// # Examples
     /// ```rust
+    /// # use lium_cli::display::Table;
     /// let table = Table::new(vec!["Name".to_string(), "Age".to_string()]);
     /// ```
     pub fn new(headers: Vec<String>) -> Self {
@@ -67,6 +75,7 @@ impl Table {
     ///
     /// # Examples
     /// ```rust
+    /// # use lium_cli::display::Table;
     /// let mut table = Table::new(vec!["Name".to_string(), "Age".to_string()]);
     /// table.add_row(vec!["John".to_string(), "30".to_string()]);
     /// ```
@@ -94,6 +103,7 @@ impl Table {
     ///
     /// # Examples
     /// ```rust
+    /// # use lium_cli::display::Table;
     /// let mut table = Table::new(vec!["Name".to_string(), "Age".to_string()]);
     /// table.add_row(vec!["John".to_string(), "30".to_string()]);
     /// table.print();
@@ -119,7 +129,7 @@ impl Table {
     /// corresponding column plus 2 spaces for padding.
     ///
     /// # Examples
-    /// ```rust
+    /// ```rust,ignore
     /// let table = Table::new(vec!["Name".to_string(), "Age".to_string()]);
     /// table.print_top_border(); // Prints: ┌────┬────┐
     /// ```
@@ -143,7 +153,7 @@ impl Table {
     /// corresponding column plus 2 spaces for padding.
     ///
     /// # Examples
-    /// ```rust
+    /// ```rust,ignore
     /// let table = Table::new(vec!["Name".to_string(), "Age".to_string()]);
     /// table.print_middle_border(); // Prints: ├────┼────┤
     /// ```
@@ -167,7 +177,7 @@ impl Table {
     /// corresponding column plus 2 spaces for padding.
     ///
     /// # Examples
-    /// ```rust
+    /// ```rust,ignore
     /// let table = Table::new(vec!["Name".to_string(), "Age".to_string()]);
     /// table.print_bottom_border(); // Prints: └────┴────┘
     /// ```
@@ -191,7 +201,7 @@ impl Table {
     /// - Vertical borders between columns
     ///
     /// # Examples
-    /// ```rust
+    /// ```rust,ignore
     /// let table = Table::new(vec!["Name".to_string(), "Age".to_string()]);
     /// table.print_header(); // Prints: │ Name │ Age │
     /// ```
@@ -216,7 +226,7 @@ impl Table {
     /// * `row` - A slice of strings representing the row data
     ///
     /// # Examples
-    /// ```rust
+    /// ```rust,ignore
     /// let mut table = Table::new(vec!["Name".to_string(), "Age".to_string()]);
     /// table.print_row(&["John".to_string(), "30".to_string()]); // Prints: │ John │ 30 │
     /// ```
@@ -258,8 +268,12 @@ impl Table {
 ///
 /// # Examples
 /// ```rust
-/// let executors = vec![ExecutorInfo::default()];
+/// # use lium_cli::display::display_executors_table;
+/// # use lium_api::ExecutorInfo;
+/// # fn example(executor: ExecutorInfo) {
+/// let executors = vec![executor];
 /// display_executors_table(&executors, true);
+/// # }
 /// ```
 pub fn display_executors_table(executors: &[ExecutorInfo], show_pareto: bool) {
     // Early return with warning if no executors are available
@@ -389,9 +403,14 @@ pub fn display_executors_table(executors: &[ExecutorInfo], show_pareto: bool) {
 ///
 /// # Examples
 /// ```rust
+/// # use lium_cli::display::display_gpu_summary;
+/// # use lium_api::ExecutorInfo;
+/// # use std::collections::HashMap;
+/// # fn example(executor: ExecutorInfo) {
 /// let mut gpu_types = HashMap::new();
-/// gpu_types.insert("RTX 3090".to_string(), vec![ExecutorInfo::default()]);
+/// gpu_types.insert("RTX 3090".to_string(), vec![executor]);
 /// display_gpu_summary(&gpu_types);
+/// # }
 /// ```
 pub fn display_gpu_summary(gpu_types: &HashMap<String, Vec<ExecutorInfo>>) {
     // Early return with warning if no GPU types are available
@@ -472,8 +491,12 @@ pub fn display_gpu_summary(gpu_types: &HashMap<String, Vec<ExecutorInfo>>) {
 ///
 /// # Examples
 /// ```rust
+/// # use lium_cli::display::display_pods_table;
+/// # use lium_api::PodInfo;
+/// # fn example(pod1: PodInfo, pod2: PodInfo, pod3: PodInfo) {
 /// let pods = vec![pod1, pod2, pod3];
 /// display_pods_table(&pods);
+/// # }
 /// ```
 ///
 /// # Notes
@@ -651,7 +674,7 @@ fn calculate_uptime(pod: &PodInfo) -> String {
 /// * `&str` - A standardized GPU model name or "GPU" if no match is found
 ///
 /// # Examples
-/// ```rust
+/// ```rust,ignore
 /// assert_eq!(extract_gpu_model("NVIDIA H100 SXM5"), "H100");
 /// assert_eq!(extract_gpu_model("RTX 4090"), "RTX4090");
 /// assert_eq!(extract_gpu_model("Unknown GPU 123"), "123");
@@ -708,12 +731,17 @@ fn extract_gpu_model(gpu_name: &str) -> &str {
 ///
 /// # Examples
 /// ```rust
+/// # use lium_cli::display::display_pod_details;
+/// # use lium_api::PodInfo;
+/// # fn example(pod: PodInfo) {
 /// let pod = PodInfo {
 ///     name: "my-pod".to_string(),
 ///     status: "running".to_string(),
 ///     // ... other fields ...
+/// #   ..pod
 /// };
 /// display_pod_details(&pod);
+/// # }
 /// ```
 ///
 /// # Notes
@@ -768,6 +796,62 @@ pub fn display_pod_details(pod: &PodInfo) {
     println!();
 }
 
+/// Displays detailed information about a single template.
+///
+/// Shows the template's identity, Docker image, verification status and image
+/// digest, so users can confirm exactly which image a template will run.
+///
+/// # Arguments
+/// * `template` - The template to display
+///
+/// # Examples
+/// ```rust
+/// # use lium_cli::display::display_template_details;
+/// # async fn example(client: lium_api::LiumApiClient) -> lium_cli::Result<()> {
+/// let template = client.get_template("tmpl-123").await?;
+/// display_template_details(&template);
+/// # Ok(())
+/// # }
+/// ```
+///
+/// # Notes
+/// - Status colors: green for verified, red for failed, yellow while verifying
+/// - Missing optional fields are shown as "-"
+pub fn display_template_details(template: &TemplateInfo) {
+    println!(
+        "{}",
+        format!("Template Details: {}", template.name).bold().blue()
+    );
+    println!("  {}: {}", "ID".bold(), template.id);
+
+    let image = match &template.docker_image_tag {
+        Some(tag) => format!("{}:{}", template.docker_image, tag),
+        None => template.docker_image.clone(),
+    };
+    println!("  {}: {}", "Image".bold(), image);
+    println!(
+        "  {}: {}",
+        "Digest".bold(),
+        template.docker_image_digest.as_deref().unwrap_or("-")
+    );
+
+    let status = template.status.as_deref().unwrap_or("pending");
+    let status_colored = match status {
+        "VERIFY_SUCCESS" => "verified".green(),
+        "VERIFY_FAILED" => "verification failed".red(),
+        "VERIFYING" | "VERIFY_PENDING" | "pending" => "verifying".yellow(),
+        other => other.normal(),
+    };
+    println!("  {}: {}", "Verification".bold(), status_colored);
+    println!(
+        "  {}: {}",
+        "Description".bold(),
+        template.description.as_deref().unwrap_or("-")
+    );
+
+    println!();
+}
+
 /// Displays a formatted table of available templates with their details.
 ///
 /// This function creates a comprehensive table showing template information including:
@@ -787,8 +871,12 @@ pub fn display_pod_details(pod: &PodInfo) {
 ///
 /// # Examples
 /// ```rust
-/// let templates = vec![TemplateInfo::default()];
+/// # use lium_cli::display::display_templates_table;
+/// # use lium_api::TemplateInfo;
+/// # fn example(template: TemplateInfo) {
+/// let templates = vec![template];
 /// display_templates_table(&templates);
+/// # }
 /// ```
 ///
 /// # Notes
@@ -865,6 +953,8 @@ pub fn display_templates_table(templates: &[TemplateInfo]) {
 ///
 /// # Examples
 /// ```rust
+/// # use lium_cli::display::*;
+/// # fn example() -> lium_cli::Result<()> {
 /// // Confirm an action
 /// let confirmed = prompt_confirm("Delete this file?", false)?;
 ///
@@ -880,6 +970,8 @@ pub fn display_templates_table(templates: &[TemplateInfo]) {
 /// print_error("An error occurred");
 /// print_warning("This action cannot be undone");
 /// print_info("Processing your request");
+/// # Ok(())
+/// # }
 /// ```
 
 /// Prompts the user for a yes/no confirmation with an optional default value.
@@ -896,10 +988,14 @@ pub fn display_templates_table(templates: &[TemplateInfo]) {
 ///
 /// # Examples
 /// ```rust
+/// # use lium_cli::display::prompt_confirm;
+/// # fn example() -> lium_cli::Result<()> {
 /// let confirmed = prompt_confirm("Are you sure?", false)?;
 /// if confirmed {
 ///     // Proceed with action
 /// }
+/// # Ok(())
+/// # }
 /// ```
 pub fn prompt_confirm(message: &str, default: bool) -> Result<bool> {
     let result = Confirm::with_theme(&ColorfulTheme::default())
@@ -926,9 +1022,13 @@ pub fn prompt_confirm(message: &str, default: bool) -> Result<bool> {
 ///
 /// # Examples
 /// ```rust
+/// # use lium_cli::display::prompt_select;
+/// # fn example() -> lium_cli::Result<()> {
 /// let options = vec!["Option 1", "Option 2", "Option 3"];
 /// let selection = prompt_select("Choose an option:", &options)?;
 /// println!("Selected: {}", options[selection]);
+/// # Ok(())
+/// # }
 /// ```
 pub fn prompt_select<T: ToString>(message: &str, items: &[T]) -> Result<usize> {
     let item_strings: Vec<String> = items.iter().map(|item| item.to_string()).collect();
@@ -957,8 +1057,12 @@ pub fn prompt_select<T: ToString>(message: &str, items: &[T]) -> Result<usize> {
 ///
 /// # Examples
 /// ```rust
+/// # use lium_cli::display::prompt_input;
+/// # fn example() -> lium_cli::Result<()> {
 /// let name = prompt_input("Enter your name:", Some("John"))?;
 /// println!("Hello, {}!", name);
+/// # Ok(())
+/// # }
 /// ```
 pub fn prompt_input(message: &str, default: Option<&str>) -> Result<String> {
     let theme = ColorfulTheme::default();
@@ -981,17 +1085,20 @@ pub fn prompt_input(message: &str, default: Option<&str>) -> Result<String> {
 /// messages with appropriate colors and icons. They use the colored crate for
 /// terminal styling.
 
-/// Displays a success message with a green checkmark icon.
+/// Displays a success message with a green checkmark icon. Hidden with `-q`.
 ///
 /// # Arguments
 /// * `message` - The message to display
 ///
 /// # Examples
 /// ```rust
+/// # use lium_cli::display::print_success;
 /// print_success("Operation completed successfully");
 /// ```
 pub fn print_success(message: &str) {
-    println!("{} {}", "✓".green().bold(), message);
+    if !crate::logging::is_quiet() {
+        emit_status(status_line("✓".green().bold(), message));
+    }
 }
 
 /// Displays an error message with a red X icon.
@@ -1001,10 +1108,11 @@ pub fn print_success(message: &str) {
 ///
 /// # Examples
 /// ```rust
+/// # use lium_cli::display::print_error;
 /// print_error("Failed to connect to server");
 /// ```
 pub fn print_error(message: &str) {
-    println!("{} {}", "✗".red().bold(), message);
+    emit_status(status_line("✗".red().bold(), message));
 }
 
 /// Displays a warning message with a yellow warning icon.
@@ -1014,23 +1122,329 @@ pub fn print_error(message: &str) {
 ///
 /// # Examples
 /// ```rust
+/// # use lium_cli::display::print_warning;
 /// print_warning("This action cannot be undone");
 /// ```
 pub fn print_warning(message: &str) {
-    println!("{} {}", "⚠".yellow().bold(), message);
+    emit_status(status_line("⚠".yellow().bold(), message));
 }
 
-/// Displays an informational message with a blue info icon.
+/// Displays an informational message with a blue info icon. Hidden with `-q`.
 ///
 /// # Arguments
 /// * `message` - The informational message to display
 ///
 /// # Examples
 /// ```rust
+/// # use lium_cli::display::print_info;
 /// print_info("Processing your request");
 /// ```
 pub fn print_info(message: &str) {
-    println!("{} {}", "ℹ".blue().bold(), message);
+    if !crate::logging::is_quiet() {
+        emit_status(status_line("ℹ".blue().bold(), message));
+    }
+}
+
+/// Formats a status message behind its icon, masking any secret in the message
+/// (see [`lium_utils::redact`])
+pub(crate) fn status_line(icon: colored::ColoredString, message: &str) -> String {
+    format!("{} {}", icon, redact(message))
+}
+
+/// Prints a status line to stdout, or to stderr when a structured output format is
+/// selected so that stdout carries nothing but the document.
+fn emit_status(line: String) {
+    let stream = if is_structured_output() {
+        OutputStream::Stderr
+    } else {
+        OutputStream::Stdout
+    };
+    write_console(stream, &format!("{}\n", line));
+}
+
+/// Prints a plain line of command output to stdout, as is.
+///
+/// # Arguments
+/// * `line` - The line to print, without a trailing newline
+///
+/// # Examples
+/// ```rust
+/// use lium_cli::display::print_line;
+///
+/// print_line("📋 Current Configuration:");
+/// ```
+pub fn print_line(line: &str) {
+    write_console(OutputStream::Stdout, &format!("{}\n", line));
+}
+
+/// Receives everything the CLI prints to the console.
+///
+/// The default sink writes to the process's stdout and stderr; embedders and tests
+/// install their own with [`set_console_sink`].
+pub trait ConsoleSink: Send + Sync {
+    /// Writes `text`, which already ends with a newline where one is wanted
+    fn write(&self, stream: OutputStream, text: &str);
+}
+
+/// Writes to the process's stdout and stderr
+pub struct StdConsole;
+
+impl ConsoleSink for StdConsole {
+    fn write(&self, stream: OutputStream, text: &str) {
+        match stream {
+            OutputStream::Stdout => print!("{}", text),
+            OutputStream::Stderr => eprint!("{}", text),
+        }
+    }
+}
+
+static CONSOLE_SINK: RwLock<Option<Arc<dyn ConsoleSink>>> = RwLock::new(None);
+
+/// Sends console output to `sink` instead of stdout and stderr for the rest of the
+/// process
+pub fn set_console_sink(sink: Arc<dyn ConsoleSink>) {
+    *CONSOLE_SINK.write().unwrap_or_else(PoisonError::into_inner) = Some(sink);
+}
+
+/// Writes `text` unchanged to the console sink.
+///
+/// Status lines, structured results, remote command output and console log records
+/// all go through here, so a [`ConsoleSink`] sees everything a command prints.
+pub(crate) fn write_console(stream: OutputStream, text: &str) {
+    let sink = CONSOLE_SINK
+        .read()
+        .unwrap_or_else(PoisonError::into_inner)
+        .clone();
+    match sink {
+        Some(sink) => sink.write(stream, text),
+        None => StdConsole.write(stream, text),
+    }
+}
+
+/// Output format selected with the global `--output` flag.
+///
+/// Every command builds a serializable result and hands it to [`print_output`],
+/// which renders it as a human-readable table or as one of the machine-readable
+/// formats below, so automation can parse lium's output instead of scraping tables.
+#[derive(clap::ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum OutputFormat {
+    /// Human-readable tables and messages
+    #[default]
+    Table,
+    /// One pretty-printed JSON document
+    Json,
+    /// One YAML document
+    Yaml,
+    /// One compact JSON document per line; lists are written one item per line
+    Ndjson,
+}
+
+static OUTPUT_FORMAT: AtomicU8 = AtomicU8::new(OutputFormat::Table as u8);
+
+/// Selects the output format for this process; called once by [`crate::run`]
+pub fn set_output_format(format: OutputFormat) {
+    OUTPUT_FORMAT.store(format as u8, Ordering::Relaxed);
+}
+
+/// The output format selected with `--output`
+pub fn output_format() -> OutputFormat {
+    match OUTPUT_FORMAT.load(Ordering::Relaxed) {
+        x if x == OutputFormat::Json as u8 => OutputFormat::Json,
+        x if x == OutputFormat::Yaml as u8 => OutputFormat::Yaml,
+        x if x == OutputFormat::Ndjson as u8 => OutputFormat::Ndjson,
+        _ => OutputFormat::Table,
+    }
+}
+
+/// Returns true if a machine-readable format was selected, in which case commands
+/// skip decorative output and status messages go to stderr
+pub fn is_structured_output() -> bool {
+    output_format() != OutputFormat::Table
+}
+
+/// Prints a command's result in the selected output format.
+///
+/// In table mode `table` is called to print the human-readable view; otherwise the
+/// result is serialized to stdout and `table` is not called.
+///
+/// # Arguments
+/// * `result` - The command's result, e.g. the listed pods
+/// * `table` - Prints the human-readable view of `result`
+///
+/// # Returns
+/// * `Result<()>` - Success or error if the result cannot be serialized
+///
+/// # Examples
+/// ```rust
+/// # use lium_cli::display::{display_pods_table, print_output};
+/// # async fn example(client: lium_api::LiumApiClient) -> lium_cli::Result<()> {
+/// let pods = client.get_pods().await?;
+/// print_output(&pods, |pods| display_pods_table(pods))?;
+/// # Ok(())
+/// # }
+/// ```
+pub fn print_output<T: Serialize + ?Sized>(result: &T, table: impl FnOnce(&T)) -> Result<()> {
+    match output_format() {
+        OutputFormat::Table => table(result),
+        format => write_console(OutputStream::Stdout, &render_output(result, format)?),
+    }
+    Ok(())
+}
+
+/// Serializes a result in a machine-readable format, ending with a newline.
+///
+/// # Arguments
+/// * `result` - The value to serialize
+/// * `format` - `Json`, `Yaml` or `Ndjson`; `Table` renders as JSON
+///
+/// # Returns
+/// * `Result<String>` - The rendered document
+pub fn render_output<T: Serialize + ?Sized>(result: &T, format: OutputFormat) -> Result<String> {
+    let value = serde_json::to_value(result)?;
+    Ok(match format {
+        OutputFormat::Table | OutputFormat::Json => {
+            format!("{}\n", serde_json::to_string_pretty(&value)?)
+        }
+        OutputFormat::Yaml => serde_yaml::to_string(&value)?,
+        OutputFormat::Ndjson => match &value {
+            Value::Array(items) => items
+                .iter()
+                .map(|item| serde_json::to_string(item).map(|line| line + "\n"))
+                .collect::<std::result::Result<String, _>>()?,
+            value => format!("{}\n", serde_json::to_string(value)?),
+        },
+    })
+}
+
+/// Prints a banner when data is being served from the local cache in offline mode.
+///
+/// Does nothing when the client is online. The banner shows how old the cached data
+/// for `endpoint` is, so users know how stale the listing below it may be.
+///
+/// # Arguments
+/// * `client` - The API client that fetched the data
+/// * `endpoint` - The cached endpoint the data came from (e.g. "pods")
+///
+/// # Examples
+/// ```rust
+/// # use lium_cli::display::{display_pods_table, print_offline_banner};
+/// # async fn example(client: lium_api::LiumApiClient) -> lium_cli::Result<()> {
+/// let pods = client.get_pods().await?;
+/// print_offline_banner(&client, "pods");
+/// display_pods_table(&pods);
+/// # Ok(())
+/// # }
+/// ```
+pub fn print_offline_banner(client: &LiumApiClient, endpoint: &str) {
+    if !client.is_offline() {
+        return;
+    }
+
+    let age = client
+        .cache_age(endpoint)
+        .map(|age| match age.as_secs() {
+            0..=59 => "less than a minute ago".to_string(),
+            secs => format!("{} ago", format_uptime(secs)),
+        })
+        .unwrap_or_else(|| "an unknown time ago".to_string());
+
+    emit_status(
+        format!(
+            "📴 Offline mode: showing cached {} from {} (may be stale)",
+            endpoint, age
+        )
+        .yellow()
+        .bold()
+        .to_string(),
+    );
+}
+
+/// Formats a pod change event as a single timestamped line for `lium ps --watch`.
+///
+/// # Arguments
+/// * `event` - The event yielded by the pod watcher
+///
+/// # Returns
+/// A colored line such as `[14:03:12] ~ brave-cat-42 PENDING → RUNNING`
+///
+/// # Examples
+/// ```rust
+/// # use futures::{Stream, StreamExt};
+/// # use lium_api::PodEvent;
+/// # use lium_cli::display::format_pod_event;
+/// # async fn example(
+/// #     mut events: impl Stream<Item = lium_api::Result<PodEvent>> + Unpin,
+/// # ) -> lium_cli::Result<()> {
+/// while let Some(event) = events.next().await {
+///     println!("{}", format_pod_event(&event?));
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub fn format_pod_event(event: &PodEvent) -> String {
+    let timestamp = format!("[{}]", chrono::Local::now().format("%H:%M:%S")).dimmed();
+    let pod = event.pod();
+
+    let description = match event {
+        PodEvent::PodAdded(_) => format!(
+            "{} {} added ({})",
+            "+".green().bold(),
+            pod.huid.bold(),
+            pod.status
+        ),
+        PodEvent::PodRemoved(_) => format!("{} {} removed", "-".red().bold(), pod.huid.bold()),
+        PodEvent::StatusChanged { from, to, .. } => format!(
+            "{} {} {} → {}",
+            "~".yellow().bold(),
+            pod.huid.bold(),
+            from,
+            to
+        ),
+        PodEvent::SshReady(_) => format!(
+            "{} {} ready: {}",
+            "🔑".bold(),
+            pod.huid.bold(),
+            pod.ssh_cmd.as_deref().unwrap_or_default().green()
+        ),
+    };
+
+    format!("{} {}", timestamp, description)
+}
+
+/// Colors cycled through for the `[huid]` prefixes of parallel `lium exec` output
+const POD_PREFIX_COLORS: &[colored::Color] = &[
+    colored::Color::Cyan,
+    colored::Color::Magenta,
+    colored::Color::Yellow,
+    colored::Color::Green,
+    colored::Color::Blue,
+    colored::Color::BrightCyan,
+    colored::Color::BrightMagenta,
+    colored::Color::BrightYellow,
+    colored::Color::BrightGreen,
+    colored::Color::BrightBlue,
+];
+
+/// Formats the prefix marking output lines of one pod when several pods run at once.
+///
+/// # Arguments
+/// * `huid` - The pod's HUID
+/// * `index` - Position of the pod among the targets; selects its color
+/// * `width` - Width of the longest HUID, so output lines of all pods line up
+///
+/// # Returns
+/// A colored prefix such as `[brave-cat-1a2b]` followed by padding
+///
+/// # Examples
+/// ```rust
+/// # use lium_cli::display::pod_output_prefix;
+/// # let line = "GPU 0: NVIDIA H100";
+/// println!("{} {}", pod_output_prefix("brave-cat-1a2b", 0, 14), line);
+/// ```
+pub fn pod_output_prefix(huid: &str, index: usize, width: usize) -> String {
+    let color = POD_PREFIX_COLORS[index % POD_PREFIX_COLORS.len()];
+    let padding = " ".repeat(width.saturating_sub(huid.chars().count()));
+    format!("{}{}", format!("[{}]", huid).color(color).bold(), padding)
 }
 
 /// Displays a spinning progress indicator with a message.
@@ -1044,6 +1458,7 @@ pub fn print_info(message: &str) {
 ///
 /// # Examples
 /// ```rust
+/// # use lium_cli::display::{print_spinner_start, print_spinner_stop};
 /// print_spinner_start("Loading data");
 /// // ... perform operation ...
 /// print_spinner_stop();
@@ -1061,6 +1476,7 @@ pub fn print_spinner_start(message: &str) {
 ///
 /// # Examples
 /// ```rust
+/// # use lium_cli::display::{print_spinner_start, print_spinner_stop};
 /// print_spinner_start("Loading data");
 /// // ... perform operation ...
 /// print_spinner_stop();
@@ -1086,8 +1502,12 @@ pub fn print_spinner_stop() {
 ///
 /// # Examples
 /// ```rust
-/// let executors = vec![ExecutorInfo::default()];
+/// # use lium_cli::display::display_executors_compact;
+/// # use lium_api::ExecutorInfo;
+/// # fn example(executor: ExecutorInfo) {
+/// let executors = vec![executor];
 /// display_executors_compact(&executors);
+/// # }
 /// ```
 pub fn display_executors_compact(executors: &[ExecutorInfo]) {
     if executors.is_empty() {
@@ -1134,8 +1554,12 @@ pub fn display_executors_compact(executors: &[ExecutorInfo]) {
 ///
 /// # Examples
 /// ```rust
-/// let executors = vec![ExecutorInfo::default()];
+/// # use lium_cli::display::display_executors_detailed;
+/// # use lium_api::ExecutorInfo;
+/// # fn example(executor: ExecutorInfo) {
+/// let executors = vec![executor];
 /// display_executors_detailed(&executors);
+/// # }
 /// ```
 pub fn display_executors_detailed(executors: &[ExecutorInfo]) {
     if executors.is_empty() {
@@ -1186,3 +1610,90 @@ pub fn display_executors_detailed(executors: &[ExecutorInfo]) {
 // TODO: Add theme support for different color schemes
 // TODO: Add export options (JSON, CSV)
 // TODO: Add pagination for large tables
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_output_flag_is_global() {
+        use clap::Parser;
+        let cli = crate::Cli::try_parse_from(["lium", "ps", "--output", "ndjson"]).unwrap();
+        assert_eq!(cli.output, OutputFormat::Ndjson);
+        assert!(crate::Cli::try_parse_from(["lium", "--output", "xml", "ps"]).is_err());
+    }
+
+    #[test]
+    fn test_yaml_output_round_trips_ambiguous_strings() {
+        let values = json!({
+            "hex": "0x1F",
+            "octal": "0o17",
+            "separated": "1_000",
+            "yes": "y",
+            "no": "n",
+            "date": "2024-01-01",
+            "float": "1e3",
+            "null": "~",
+            "comment": "# not a comment",
+            "multiline": "line 1\nline 2",
+            "number": 1_000,
+            "nested": [{"empty": "", "bool": false}],
+        });
+
+        let yaml = render_output(&values, OutputFormat::Yaml).unwrap();
+        assert_eq!(serde_yaml::from_str::<Value>(&yaml).unwrap(), values);
+        // Strings that read as other YAML scalars are quoted
+        for line in [
+            "hex: '0x1F'",
+            "octal: '0o17'",
+            "float: '1e3'",
+            "'null': '~'",
+        ] {
+            assert!(yaml.lines().any(|l| l == line), "{} in\n{}", line, yaml);
+        }
+    }
+
+    #[test]
+    fn test_render_output_formats() {
+        let pods = json!([
+            {"huid": "brave-cat-42", "status": "RUNNING", "gpus": 2, "ports": {"22": 40022}},
+            {"huid": "calm-dog-07", "status": "PENDING", "ssh": null, "tags": []},
+        ]);
+
+        let ndjson = render_output(&pods, OutputFormat::Ndjson).unwrap();
+        assert_eq!(ndjson.lines().count(), 2);
+        assert_eq!(
+            serde_json::from_str::<Value>(ndjson.lines().next().unwrap()).unwrap(),
+            pods[0]
+        );
+        assert_eq!(
+            serde_json::from_str::<Value>(&render_output(&pods, OutputFormat::Json).unwrap())
+                .unwrap(),
+            pods
+        );
+
+        assert_eq!(
+            render_output(&pods, OutputFormat::Yaml).unwrap(),
+            "\
+- gpus: 2
+  huid: brave-cat-42
+  ports:
+    '22': 40022
+  status: RUNNING
+- huid: calm-dog-07
+  ssh: null
+  status: PENDING
+  tags: []
+"
+        );
+        assert_eq!(
+            render_output(
+                &json!({"cmd": "ssh root@1.2.3.4 -p 22", "ok": "true"}),
+                OutputFormat::Yaml
+            )
+            .unwrap(),
+            "cmd: ssh root@1.2.3.4 -p 22\nok: 'true'\n"
+        );
+    }
+}
@@ -0,0 +1,100 @@
+//! CLI wrappers around [`lium_utils::files`] that report lock and write failures as CLI errors.
+
+use crate::config::ConfigError;
+use crate::{CliError, Result};
+use lium_utils::files;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::Path;
+use std::time::Duration;
+
+pub use lium_utils::files::LOCK_TIMEOUT;
+
+/// An exclusive advisory lock on `<path>.lock`, released when dropped
+#[derive(Debug)]
+pub struct FileLock {
+    _lock: files::FileLock,
+}
+
+impl FileLock {
+    /// Locks `path` for writing, retrying with backoff until `LOCK_TIMEOUT`.
+    ///
+    /// The lock is taken on a sidecar `<path>.lock` file rather than on `path` itself,
+    /// because writes replace `path` with a renamed temporary file.
+    ///
+    /// # Arguments
+    /// * `path` - The file about to be read and rewritten
+    ///
+    /// # Returns
+    /// * `Result<FileLock>` - The held lock
+    ///
+    /// # Errors
+    /// * `ConfigError::Locked` - If another process held the lock for the whole timeout
+    /// * `CliError::Io` - If the lock file cannot be opened
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use lium_cli::{files::FileLock, Config};
+    /// # fn example(config: Config) -> lium_cli::Result<()> {
+    /// let _lock = FileLock::acquire(&config.config_path)?;
+    /// // read, modify and write the config file
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn acquire(path: &Path) -> Result<Self> {
+        Self::acquire_with_timeout(path, LOCK_TIMEOUT)
+    }
+
+    fn acquire_with_timeout(path: &Path, timeout: Duration) -> Result<Self> {
+        create_parent_dir(path)?;
+        match files::FileLock::acquire_with_timeout(path, timeout) {
+            Ok(lock) => Ok(FileLock { _lock: lock }),
+            Err(e) if e.kind() == ErrorKind::TimedOut => {
+                Err(ConfigError::Locked(files::lock_path(path).display().to_string()).into())
+            }
+            Err(e) => Err(CliError::Io(e)),
+        }
+    }
+}
+
+/// Writes `content` to `path` atomically.
+///
+/// The content goes to a uniquely named temporary file in the same directory, which
+/// is synced and then renamed over `path`. Missing parent directories are created.
+///
+/// # Errors
+/// * `ConfigError::DirectoryCreationFailed` - If the parent directory cannot be created
+/// * `CliError::Io` - If writing or renaming fails
+pub fn write_atomic(path: &Path, content: &[u8]) -> Result<()> {
+    create_parent_dir(path)?;
+    files::write_atomic(path, content).map_err(CliError::Io)
+}
+
+fn create_parent_dir(path: &Path) -> Result<()> {
+    match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() && !parent.exists() => {
+            fs::create_dir_all(parent)
+                .map_err(|e| ConfigError::DirectoryCreationFailed(e.to_string()).into())
+        }
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lock_timeout_maps_to_config_locked() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.toml");
+
+        let _held = FileLock::acquire(&path).unwrap();
+        match FileLock::acquire_with_timeout(&path, Duration::from_millis(30)) {
+            Err(CliError::Config(ConfigError::Locked(lock))) => {
+                assert_eq!(lock, files::lock_path(&path).display().to_string());
+            }
+            other => panic!("expected ConfigError::Locked, got {other:?}"),
+        }
+    }
+}
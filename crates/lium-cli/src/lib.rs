@@ -7,12 +7,21 @@
 pub mod commands;
 pub mod config;
 pub mod display;
+pub mod files;
 pub mod helpers;
+pub mod logging;
+pub mod remote;
 pub mod resolvers;
+pub mod secrets;
+pub mod state;
 pub mod storage;
 
+#[cfg(test)]
+mod test_support;
+
 // Re-export common types
 pub use config::Config;
+pub use lium_utils::redact;
 
 use clap::{Parser, Subcommand};
 use thiserror::Error;
@@ -32,6 +41,9 @@ pub enum CliError {
     #[error("Config error: {0}")]
     Config(#[from] crate::config::ConfigError),
 
+    #[error("Secret store error: {0}")]
+    Secret(#[from] crate::secrets::SecretError),
+
     #[error("IO error: {0}")]
     Io(#[from] std::io::Error),
 
@@ -41,6 +53,9 @@ pub enum CliError {
     #[error("Serialization error: {0}")]
     Serde(#[from] serde_json::Error),
 
+    #[error("YAML serialization error: {0}")]
+    Yaml(#[from] serde_yaml::Error),
+
     #[error("Command failed: {0}")]
     Command(String),
 
@@ -55,6 +70,33 @@ pub enum CliError {
 
     #[error("Other: {0}")]
     Other(String),
+
+    /// The command already reported its failure and the process should exit quietly
+    /// with this status, e.g. the exit status of a remote command
+    #[error("Exited with status {0}")]
+    Exit(i32),
+}
+
+impl CliError {
+    /// Process exit status for this error: the status carried by [`CliError::Exit`]
+    /// (1 if it does not fit a process status), otherwise 1
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            CliError::Exit(code) if (1..=255).contains(code) => *code,
+            _ => 1,
+        }
+    }
+}
+
+/// Prints `error` to stderr the way the `lium` binary reports a failed command, with
+/// secrets masked. [`CliError::Exit`] only carries a status and prints nothing.
+pub fn report_error(error: &CliError) {
+    if !matches!(error, CliError::Exit(_)) {
+        display::write_console(
+            lium_utils::OutputStream::Stderr,
+            &format!("Error: {}\n", redact(&error.to_string())),
+        );
+    }
 }
 
 impl From<dialoguer::Error> for CliError {
@@ -73,6 +115,53 @@ pub type Result<T> = std::result::Result<T, CliError>;
 pub struct Cli {
     #[command(subcommand)]
     pub command: Commands,
+
+    /// Bypass the local API response cache for this run
+    #[arg(long, global = true, conflicts_with_all = ["refresh", "offline"])]
+    pub no_cache: bool,
+
+    /// Ignore cached API responses and fetch fresh data (the cache is updated)
+    #[arg(long, global = true, conflicts_with = "offline")]
+    pub refresh: bool,
+
+    /// Show the last known data from the local cache without contacting the API
+    #[arg(long, global = true)]
+    pub offline: bool,
+
+    /// Use the named profile's settings (overrides LIUM_PROFILE and the default profile)
+    #[arg(long, global = true, value_name = "NAME")]
+    pub profile: Option<String>,
+
+    /// Show more diagnostics (-v: info, -vv: debug and HTTP requests, -vvv: trace)
+    #[arg(short, long, global = true, action = clap::ArgAction::Count, conflicts_with = "quiet")]
+    pub verbose: u8,
+
+    /// Only print errors and command output
+    #[arg(short, long, global = true)]
+    pub quiet: bool,
+
+    /// Also append log records (debug level and above) to FILE
+    #[arg(long, global = true, value_name = "FILE")]
+    pub log_file: Option<std::path::PathBuf>,
+
+    /// Output format; json, yaml and ndjson print the command's result for scripts
+    #[arg(long, global = true, value_enum, default_value = "table")]
+    pub output: display::OutputFormat,
+}
+
+impl Cli {
+    /// Cache mode selected by the global cache flags
+    pub fn cache_mode(&self) -> lium_api::CacheMode {
+        if self.offline {
+            lium_api::CacheMode::Offline
+        } else if self.no_cache {
+            lium_api::CacheMode::Disabled
+        } else if self.refresh {
+            lium_api::CacheMode::Refresh
+        } else {
+            lium_api::CacheMode::Normal
+        }
+    }
 }
 
 /// All available CLI commands
@@ -96,7 +185,13 @@ pub enum Commands {
         lium exec 1,2,3 nvidia-smi\n  \
         lium exec all uptime\n  \
         lium exec 1 --script script.py\n  \
-        lium exec 3 --env API_KEY=secret echo \\$API_KEY"
+        lium exec 3 --env API_KEY=secret echo \\$API_KEY\n  \
+        lium exec 3 --secret HF_TOKEN -- python train.py\n  \
+        lium exec all --parallel 8 --fail-fast --timeout 600 nvidia-smi\n  \
+        lium exec 1 --tty htop\n  \
+        cat data.csv | lium exec 1,2 --stdin wc -l\n\n\
+        Options (before the command): --script/-s, --env/-e, --secret, --parallel N,\n\
+        --fail-fast, --timeout SECONDS, --tty/-t, --stdin/-i"
     )]
     Exec {
         /// Arguments: <POD_TARGETS> [OPTIONS] [COMMAND...]
@@ -132,10 +227,11 @@ pub enum Commands {
         lium rsync ~/project/ 1,2:/home/project/ -v\n  \
         lium rsync 1:/home/project/ ~/backup/ -z\n  \
         lium rsync all:/home/logs/ ~/collected/\n  \
-        lium rsync ~/data/ all:/workspace/ --delete --exclude '*.tmp'"
+        lium rsync ~/data/ all:/workspace/ --delete --exclude '*.tmp'\n  \
+        lium rsync 1:~/project/    # uploads defaults.sync_paths"
     )]
     Rsync {
-        /// Arguments: <SOURCE> <DESTINATION> [OPTIONS...]
+        /// Arguments: [SOURCE] <DESTINATION> [OPTIONS...]
         #[arg(value_name = "ARGS")]
         args: Vec<String>,
     },
@@ -180,6 +276,21 @@ pub enum Commands {
         #[command(subcommand)]
         action: ConfigCommands,
     },
+    /// Encrypted secret store for credentials
+    #[command(
+        long_about = "Manage the encrypted secret store at ~/.lium/secrets.\n\n\
+        Config values can reference a secret as secret:NAME, e.g. docker.token = \"secret:docker_token\".\n\
+        The store is unlocked with the key file set by secrets.key_file (or LIUM_SECRETS_KEY_FILE),\n\
+        else LIUM_SECRETS_PASSPHRASE, else an interactive passphrase prompt.\n\n\
+        Examples:\n  \
+        lium secrets set docker_token\n  \
+        lium secrets list\n  \
+        lium secrets migrate"
+    )]
+    Secrets {
+        #[command(subcommand)]
+        action: SecretCommands,
+    },
     /// Funding and billing
     Fund {
         #[command(subcommand)]
@@ -206,33 +317,144 @@ pub enum ImageCommands {
         #[arg(short, long)]
         tag: Option<String>,
     },
+    /// Show template details, including verification status and image digest
+    Show {
+        /// Template ID
+        id: String,
+    },
+    /// Edit a template's name or description
+    Edit {
+        /// Template ID
+        id: String,
+        /// New template name
+        #[arg(long)]
+        name: Option<String>,
+        /// New template description (prompted for if no flags are given)
+        #[arg(short, long)]
+        description: Option<String>,
+    },
     /// Delete template
     Delete {
         /// Template ID
         id: String,
+        /// Skip the confirmation prompt
+        #[arg(short, long)]
+        yes: bool,
     },
 }
 
 #[derive(Subcommand)]
 pub enum ConfigCommands {
     /// Show current configuration
-    Show,
+    Show {
+        /// Print each effective value with the layer it came from
+        /// (env var, project .lium.toml, profile or user config)
+        #[arg(long)]
+        origin: bool,
+    },
     /// Set configuration value
+    #[command(long_about = "Set a configuration value by its dotted key.\n\n\
+        Keys: api.base_url, ssh.key_path, ssh.user, ssh.identities, ssh.agent, template.default_id,\n\
+        docker.username, theme.name, secrets.key_file\n\n\
+        Examples:\n  \
+        lium config set ssh.user ubuntu\n  \
+        lium config set template.default_id <template-id>")]
     Set {
-        /// Configuration key
+        /// Configuration key, e.g. ssh.user
         key: String,
         /// Configuration value
         value: String,
     },
-    /// Get configuration value
+    /// Print a configuration value (fails with nothing on stdout if unset)
     Get {
-        /// Configuration key
+        /// Configuration key, e.g. api.base_url
         key: String,
     },
-    /// Reset configuration to defaults
-    Reset,
+    /// Reset configuration to defaults, keeping a backup of the old file
+    Reset {
+        /// Skip the confirmation prompt
+        #[arg(short, long)]
+        yes: bool,
+    },
     /// Initialize configuration interactively
     Init,
+    /// Manage named account profiles
+    #[command(long_about = "Manage named account profiles.\n\n\
+        Each profile overrides the top-level api, ssh, template and docker settings.\n\
+        Select one per run with --profile NAME or LIUM_PROFILE=NAME.\n\n\
+        Examples:\n  \
+        lium config profiles add team --base-url https://lium.team.internal\n  \
+        lium config profiles use team\n  \
+        lium --profile personal ps")]
+    Profiles {
+        #[command(subcommand)]
+        action: ProfileCommands,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum ProfileCommands {
+    /// List profiles, marking the active and default ones
+    List,
+    /// Make a profile the default ("default" selects the top-level settings)
+    Use {
+        /// Profile name
+        name: String,
+    },
+    /// Add a new profile
+    Add {
+        /// Profile name
+        name: String,
+        /// API key for this profile (prompted for if not given)
+        #[arg(long)]
+        api_key: Option<String>,
+        /// API base URL for this profile
+        #[arg(long)]
+        base_url: Option<String>,
+        /// SSH public key path for this profile
+        #[arg(long, value_name = "PATH")]
+        ssh_key: Option<String>,
+    },
+    /// Remove a profile
+    Remove {
+        /// Profile name
+        name: String,
+        /// Skip the confirmation prompt
+        #[arg(short, long)]
+        yes: bool,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum SecretCommands {
+    /// Store a secret, replacing any previous value
+    Set {
+        /// Secret name (letters, digits, '_', '-' and '.')
+        name: String,
+        /// Secret value (prompted for without echo if not given)
+        value: Option<String>,
+    },
+    /// Print a secret's value
+    Get {
+        /// Secret name
+        name: String,
+    },
+    /// List secret names
+    List,
+    /// Remove a secret
+    Rm {
+        /// Secret name
+        name: String,
+        /// Skip the confirmation prompt
+        #[arg(short, long)]
+        yes: bool,
+    },
+    /// Move plaintext credentials from config.toml into the secret store
+    Migrate {
+        /// Skip the confirmation prompt
+        #[arg(short, long)]
+        yes: bool,
+    },
 }
 
 #[derive(Subcommand)]
@@ -262,7 +484,14 @@ pub enum ThemeCommands {
 /// Main CLI runner - clean routing without massive handlers
 pub async fn run() -> Result<()> {
     let cli = Cli::parse();
-    let config = Config::new()?;
+    logging::init(
+        logging::Verbosity::from_flags(cli.verbose, cli.quiet),
+        cli.log_file.as_deref(),
+    )?;
+    display::set_output_format(cli.output);
+    let mut config = Config::new()?;
+    config.cache_mode = cli.cache_mode();
+    config.select_profile(cli.profile.as_deref())?;
 
     match cli.command {
         Commands::Init => commands::init::handle().await,
@@ -270,56 +499,7 @@ pub async fn run() -> Result<()> {
         Commands::Up(args) => commands::up::handle(args, &config).await,
         Commands::Ps(args) => commands::ps::handle(args, &config).await,
         Commands::Exec { args } => {
-            // Manually parse the exec arguments
-            if args.is_empty() {
-                return Err(CliError::InvalidInput(
-                    "No pod targets specified".to_string(),
-                ));
-            }
-
-            let pod_targets = args[0].clone();
-            let mut command = Vec::new();
-            let mut script = None;
-            let mut env = Vec::new();
-
-            let mut i = 1;
-            while i < args.len() {
-                match args[i].as_str() {
-                    "--script" | "-s" => {
-                        if i + 1 < args.len() {
-                            script = Some(args[i + 1].clone());
-                            i += 2;
-                        } else {
-                            return Err(CliError::InvalidInput(
-                                "--script requires a value".to_string(),
-                            ));
-                        }
-                    }
-                    "--env" | "-e" => {
-                        if i + 1 < args.len() {
-                            env.push(args[i + 1].clone());
-                            i += 2;
-                        } else {
-                            return Err(CliError::InvalidInput(
-                                "--env requires a value".to_string(),
-                            ));
-                        }
-                    }
-                    _ => {
-                        // Everything else is part of the command
-                        command.extend_from_slice(&args[i..]);
-                        break;
-                    }
-                }
-            }
-
-            let exec_args = commands::exec::ExecArgs {
-                pod_targets,
-                command,
-                script,
-                env,
-            };
-
+            let exec_args = parse_exec_args(&args)?;
             commands::exec::handle(exec_args, &config).await
         }
         Commands::Ssh { pod } => commands::ssh::handle(pod, &config).await,
@@ -331,12 +511,19 @@ pub async fn run() -> Result<()> {
         } => commands::scp::handle(source, destination, coldkey, hotkey, &config).await,
         Commands::Rsync { args } => {
             // Manually parse rsync arguments
-            if args.len() < 2 {
+            if args.is_empty() {
                 return Err(CliError::InvalidInput(
                     "Rsync requires source and destination arguments".to_string(),
                 ));
             }
 
+            // A lone destination uploads the configured defaults.sync_paths
+            if args.get(1).is_none_or(|arg| arg.starts_with('-')) {
+                let destination = args[0].clone();
+                let options = args[1..].to_vec();
+                return commands::rsync::handle_sync_paths(destination, options, &config).await;
+            }
+
             let source = args[0].clone();
             let destination = args[1].clone();
             let options = args[2..].to_vec();
@@ -365,6 +552,7 @@ pub async fn run() -> Result<()> {
             }
         }
         Commands::Config { action } => commands::config::handle(action, &config).await,
+        Commands::Secrets { action } => commands::secrets::handle(action, &config).await,
         Commands::Fund { action } => commands::fund::handle(action, &config).await,
         Commands::Theme { action } => commands::theme::handle(action, &config).await,
     }
@@ -372,5 +560,166 @@ pub async fn run() -> Result<()> {
 
 // TODO: Add command aliases and shortcuts
 // TODO: Add shell completion support
-// TODO: Add command history and caching
+// TODO: Add command history
 // TODO: Add batch operations support
+
+/// Parses the raw `lium exec` arguments into [`commands::exec::ExecArgs`]
+///
+/// The first argument is the pod target list. Options are read until the
+/// first token that is not an option, or until `--`, and everything after
+/// that is the command to run.
+fn parse_exec_args(args: &[String]) -> Result<commands::exec::ExecArgs> {
+    if args.is_empty() {
+        return Err(CliError::InvalidInput(
+            "No pod targets specified".to_string(),
+        ));
+    }
+
+    let pod_targets = args[0].clone();
+    let mut command = Vec::new();
+    let mut script = None;
+    let mut env = Vec::new();
+    let mut secret = Vec::new();
+    let mut parallel = 1;
+    let mut fail_fast = false;
+    let mut timeout = None;
+    let mut tty = false;
+    let mut stdin = false;
+
+    let mut i = 1;
+    while i < args.len() {
+        match args[i].as_str() {
+            "--script" | "-s" => {
+                script = Some(exec_option_value(args, i)?.to_string());
+                i += 2;
+            }
+            "--env" | "-e" => {
+                env.push(exec_option_value(args, i)?.to_string());
+                i += 2;
+            }
+            "--secret" => {
+                secret.push(exec_option_value(args, i)?.to_string());
+                i += 2;
+            }
+            "--parallel" => {
+                parallel = parse_exec_number(args, i)?;
+                i += 2;
+            }
+            "--timeout" => {
+                timeout = Some(parse_exec_number(args, i)?);
+                i += 2;
+            }
+            "--fail-fast" => {
+                fail_fast = true;
+                i += 1;
+            }
+            "--tty" | "-t" => {
+                tty = true;
+                i += 1;
+            }
+            "--stdin" | "-i" => {
+                stdin = true;
+                i += 1;
+            }
+            "-it" | "-ti" => {
+                (tty, stdin) = (true, true);
+                i += 1;
+            }
+            "--" => {
+                // `--` ends option parsing; the rest is the command
+                command.extend_from_slice(&args[i + 1..]);
+                break;
+            }
+            _ => {
+                // Everything else is part of the command
+                command.extend_from_slice(&args[i..]);
+                break;
+            }
+        }
+    }
+
+    Ok(commands::exec::ExecArgs {
+        pod_targets,
+        command,
+        script,
+        env,
+        secret,
+        parallel,
+        fail_fast,
+        timeout,
+        tty,
+        stdin,
+    })
+}
+
+/// Returns the value following the `lium exec` option at `args[i]`
+fn exec_option_value(args: &[String], i: usize) -> Result<&str> {
+    args.get(i + 1)
+        .map(String::as_str)
+        .ok_or_else(|| CliError::InvalidInput(format!("{} requires a value", args[i])))
+}
+
+/// Parses the number following the `lium exec` option at `args[i]`
+fn parse_exec_number<T: std::str::FromStr>(args: &[String], i: usize) -> Result<T> {
+    let value = exec_option_value(args, i)?;
+    value.parse().map_err(|_| {
+        CliError::InvalidInput(format!(
+            "{} expects a whole number, got '{}'",
+            args[i], value
+        ))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Splits a documented `lium exec ...` line and parses what follows `exec`
+    fn parse_exec_line(line: &str) -> commands::exec::ExecArgs {
+        let args: Vec<String> = line
+            .split_whitespace()
+            .skip_while(|word| *word != "exec")
+            .skip(1)
+            .map(String::from)
+            .collect();
+        parse_exec_args(&args).unwrap()
+    }
+
+    #[test]
+    fn test_parse_exec_args_double_dash_ends_options() {
+        let args = parse_exec_line(
+            "lium exec my-pod --secret HF_TOKEN --secret WANDB_API_KEY=wandb_key -- python train.py",
+        );
+
+        assert_eq!(args.pod_targets, "my-pod");
+        assert_eq!(args.secret, ["HF_TOKEN", "WANDB_API_KEY=wandb_key"]);
+        assert_eq!(args.command, ["python", "train.py"]);
+    }
+
+    #[test]
+    fn test_parse_exec_args_parallel_options_before_double_dash() {
+        let args = parse_exec_line(
+            "lium exec all --parallel 8 --fail-fast --timeout 600 -- pip install -r requirements.txt",
+        );
+
+        assert_eq!(args.pod_targets, "all");
+        assert_eq!(args.parallel, 8);
+        assert!(args.fail_fast);
+        assert_eq!(args.timeout, Some(600));
+        assert_eq!(args.command, ["pip", "install", "-r", "requirements.txt"]);
+    }
+
+    #[test]
+    fn test_parse_exec_args_stdin_and_tty_before_double_dash() {
+        let args = parse_exec_line("cat data.csv | lium exec 1,2 --stdin -- wc -l");
+        assert_eq!(args.pod_targets, "1,2");
+        assert!(args.stdin);
+        assert!(!args.tty);
+        assert_eq!(args.command, ["wc", "-l"]);
+
+        let args = parse_exec_line("lium exec my-pod -t -- python -i");
+        assert!(args.tty);
+        assert!(!args.stdin);
+        assert_eq!(args.command, ["python", "-i"]);
+    }
+}
@@ -0,0 +1,287 @@
+//! Logger setup for the CLI.
+//!
+//! Diagnostics are emitted as `log` records by every crate in the workspace and
+//! printed to stderr according to the global `-v`/`-q` flags, so normal output stays
+//! clean and more detail is available on demand. With `--log-file`, records are also
+//! appended to a file at debug level or above. Every record passes through
+//! [`lium_utils::RedactingLogger`], so secrets never reach the terminal or the file.
+
+use crate::display::write_console;
+use crate::Result;
+use colored::Colorize;
+use lium_utils::{OutputStream, RedactingLogger};
+use log::{Level, LevelFilter, Log, Metadata, Record};
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+
+/// Set when `-q` is given; status messages other than warnings and errors are hidden
+static QUIET: AtomicBool = AtomicBool::new(false);
+
+/// Returns true if `-q` was given and informational output should be suppressed
+pub fn is_quiet() -> bool {
+    QUIET.load(Ordering::Relaxed)
+}
+
+/// How much diagnostic output the user asked for with `-v`/`-q`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verbosity {
+    /// `-q`: only errors
+    Quiet,
+    /// No flag: warnings and errors
+    Normal,
+    /// `-v`: progress information
+    Verbose,
+    /// `-vv`: debug records, including one line per HTTP request
+    Debug,
+    /// `-vvv`: everything, including request/response bodies and dependency logs
+    Trace,
+}
+
+impl Verbosity {
+    /// Maps the global `-v` count and `-q` flag to a verbosity
+    pub fn from_flags(verbose: u8, quiet: bool) -> Self {
+        match (quiet, verbose) {
+            (true, _) => Verbosity::Quiet,
+            (false, 0) => Verbosity::Normal,
+            (false, 1) => Verbosity::Verbose,
+            (false, 2) => Verbosity::Debug,
+            (false, _) => Verbosity::Trace,
+        }
+    }
+
+    /// The most detailed level shown on the console for lium's own crates
+    pub fn level(self) -> LevelFilter {
+        match self {
+            Verbosity::Quiet => LevelFilter::Error,
+            Verbosity::Normal => LevelFilter::Warn,
+            Verbosity::Verbose => LevelFilter::Info,
+            Verbosity::Debug => LevelFilter::Debug,
+            Verbosity::Trace => LevelFilter::Trace,
+        }
+    }
+}
+
+/// Writes records to stderr and, optionally, a log file.
+///
+/// Records from lium's own crates are filtered by the selected level. Records from
+/// dependencies (reqwest, hyper, ...) are only shown at `-vvv`, and otherwise limited
+/// to warnings, so `-vv` stays readable.
+struct CliLogger {
+    console: LevelFilter,
+    file: Option<(LevelFilter, Mutex<File>)>,
+    dependencies: LevelFilter,
+}
+
+impl CliLogger {
+    fn new(verbosity: Verbosity, file: Option<File>) -> Self {
+        let console = verbosity.level();
+        let dependencies = if verbosity == Verbosity::Trace {
+            LevelFilter::Trace
+        } else {
+            console.min(LevelFilter::Warn)
+        };
+        Self {
+            console,
+            file: file.map(|file| (console.max(LevelFilter::Debug), Mutex::new(file))),
+            dependencies,
+        }
+    }
+
+    /// The most detailed level any destination accepts, for `log::set_max_level`
+    fn max_level(&self) -> LevelFilter {
+        let file = self
+            .file
+            .as_ref()
+            .map_or(LevelFilter::Off, |(level, _)| *level);
+        self.console.max(file)
+    }
+
+    /// Level accepted from `target` by a destination that accepts `level` from lium
+    fn level_for(&self, target: &str, level: LevelFilter) -> LevelFilter {
+        if target.starts_with("lium") {
+            level
+        } else {
+            level.min(self.dependencies)
+        }
+    }
+
+    fn console_enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= self.level_for(metadata.target(), self.console)
+    }
+
+    fn file_enabled(&self, metadata: &Metadata) -> bool {
+        self.file
+            .as_ref()
+            .is_some_and(|(level, _)| metadata.level() <= self.level_for(metadata.target(), *level))
+    }
+}
+
+impl Log for CliLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        self.console_enabled(metadata) || self.file_enabled(metadata)
+    }
+
+    fn log(&self, record: &Record) {
+        if self.console_enabled(record.metadata()) {
+            write_console(OutputStream::Stderr, &format!("{}\n", console_line(record)));
+        }
+        if self.file_enabled(record.metadata()) {
+            if let Some((_, file)) = &self.file {
+                let mut file = file.lock().unwrap_or_else(|e| e.into_inner());
+                let _ = writeln!(file, "{}", file_line(record));
+            }
+        }
+    }
+
+    fn flush(&self) {
+        if let Some((_, file)) = &self.file {
+            let _ = file.lock().unwrap_or_else(|e| e.into_inner()).flush();
+        }
+    }
+}
+
+/// Formats a record for the terminal, e.g. `DEBUG lium_api::client: Fetching pods`
+fn console_line(record: &Record) -> String {
+    let level = match record.level() {
+        Level::Error => "ERROR".red().bold(),
+        Level::Warn => "WARN".yellow().bold(),
+        Level::Info => "INFO".blue().bold(),
+        Level::Debug => "DEBUG".dimmed(),
+        Level::Trace => "TRACE".dimmed(),
+    };
+    if record.level() <= Level::Info {
+        format!("{} {}", level, record.args())
+    } else {
+        format!("{} {}: {}", level, record.target(), record.args())
+    }
+}
+
+/// Formats a record for the log file, with a UTC timestamp and without colors
+fn file_line(record: &Record) -> String {
+    format!(
+        "{} {:<5} {}: {}",
+        chrono::Utc::now().format("%Y-%m-%dT%H:%M:%S%.3fZ"),
+        record.level(),
+        record.target(),
+        record.args()
+    )
+}
+
+/// Installs the CLI logger for this process.
+///
+/// Called once by [`crate::run`] after parsing the global flags. Later calls (for
+/// example from tests) leave the first logger in place.
+///
+/// # Arguments
+/// * `verbosity` - Console verbosity chosen with `-v`/`-q`
+/// * `log_file` - File to append records to, at debug level or above
+///
+/// # Returns
+/// * `Result<()>` - Success or error if the log file cannot be opened
+///
+/// # Examples
+/// ```rust,no_run
+/// use lium_cli::logging::{init, Verbosity};
+/// use std::path::Path;
+///
+/// init(Verbosity::from_flags(2, false), Some(Path::new("/tmp/lium.log")))?;
+/// log::debug!("shown with -vv and written to /tmp/lium.log");
+/// # Ok::<(), lium_cli::CliError>(())
+/// ```
+pub fn init(verbosity: Verbosity, log_file: Option<&Path>) -> Result<()> {
+    QUIET.store(verbosity == Verbosity::Quiet, Ordering::Relaxed);
+
+    let file = match log_file {
+        Some(path) => {
+            let path = crate::config::expand_path(&path.to_string_lossy())?;
+            Some(OpenOptions::new().create(true).append(true).open(path)?)
+        }
+        None => None,
+    };
+    let logger = CliLogger::new(verbosity, file);
+    let max_level = logger.max_level();
+
+    if log::set_logger(Box::leak(Box::new(RedactingLogger::new(logger)))).is_ok() {
+        log::set_max_level(max_level);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Cli;
+    use clap::Parser;
+
+    fn metadata(level: Level, target: &str) -> Metadata<'_> {
+        Metadata::builder().level(level).target(target).build()
+    }
+
+    #[test]
+    fn test_verbosity_flags() {
+        let verbosity = |args: &[&str]| {
+            let cli = Cli::try_parse_from(args).unwrap();
+            Verbosity::from_flags(cli.verbose, cli.quiet)
+        };
+
+        assert_eq!(verbosity(&["lium", "ps"]), Verbosity::Normal);
+        assert_eq!(verbosity(&["lium", "-v", "ps"]), Verbosity::Verbose);
+        assert_eq!(verbosity(&["lium", "ps", "-vv"]), Verbosity::Debug);
+        assert_eq!(verbosity(&["lium", "-vvvv", "ps"]), Verbosity::Trace);
+        assert_eq!(verbosity(&["lium", "ps", "--quiet"]), Verbosity::Quiet);
+        assert!(Cli::try_parse_from(["lium", "-v", "-q", "ps"]).is_err());
+
+        // Flags after an exec or rsync target belong to the remote command
+        let cli = Cli::try_parse_from(["lium", "rsync", "src/", "1:/dst/", "-v"]).unwrap();
+        assert_eq!(cli.verbose, 0);
+    }
+
+    #[test]
+    fn test_logger_filters_by_target_and_destination() {
+        let file = tempfile::tempfile().unwrap();
+        let logger = CliLogger::new(Verbosity::Normal, Some(file));
+
+        assert!(logger.console_enabled(&metadata(Level::Warn, "lium_cli::config")));
+        assert!(!logger.console_enabled(&metadata(Level::Info, "lium_api::client")));
+        assert!(logger.file_enabled(&metadata(Level::Debug, "lium_api::client")));
+        assert!(!logger.file_enabled(&metadata(Level::Trace, "lium_api::client")));
+        assert!(!logger.file_enabled(&metadata(Level::Debug, "reqwest::connect")));
+        assert_eq!(logger.max_level(), LevelFilter::Debug);
+
+        let logger = CliLogger::new(Verbosity::Debug, None);
+        assert!(logger.console_enabled(&metadata(Level::Debug, "lium_cli::commands::up")));
+        assert!(!logger.console_enabled(&metadata(Level::Debug, "hyper::client")));
+        assert!(!logger.enabled(&metadata(Level::Trace, "lium_api::client")));
+
+        let logger = CliLogger::new(Verbosity::Trace, None);
+        assert!(logger.console_enabled(&metadata(Level::Trace, "hyper::client")));
+    }
+
+    #[test]
+    fn test_log_file_records_are_written() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("lium.log");
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .unwrap();
+        let logger = CliLogger::new(Verbosity::Quiet, Some(file));
+
+        logger.log(
+            &Record::builder()
+                .level(Level::Debug)
+                .target("lium_api::client")
+                .args(format_args!("HTTP GET https://lium.example/pods -> 200 OK"))
+                .build(),
+        );
+        logger.flush();
+
+        let written = std::fs::read_to_string(&path).unwrap();
+        assert!(written
+            .ends_with("DEBUG lium_api::client: HTTP GET https://lium.example/pods -> 200 OK\n"));
+    }
+}
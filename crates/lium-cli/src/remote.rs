@@ -0,0 +1,286 @@
+//! SSH access to pods for CLI commands.
+//!
+//! Commands resolve a pod's [`SshTarget`] and the configured [`SshAuth`], then run
+//! their work through [`with_session`], which borrows a connection from the
+//! process-wide [`SshPool`] on a blocking thread. A command that touches the same pod
+//! several times therefore connects once per invocation.
+//!
+//! Host keys are pinned per pod id in `~/.lium/known_hosts` on first connect and
+//! verified on every later one; `lium down` forgets them with [`forget_pod`].
+//!
+//! Pods created with `lium up --ssh-key` remember that key in `~/.lium/state.json`;
+//! [`pod_ssh_auth`] offers it before the configured identities and ssh-agent keys.
+
+use crate::state::State;
+use crate::{config::Config, CliError, Result};
+use lium_core::PodInfo;
+use lium_utils::{CancelToken, ExecInput, OutputStream, SshAuth, SshPool, SshSession, SshTarget};
+use std::path::{Path, PathBuf};
+use std::sync::{mpsc, Arc, PoisonError, RwLock};
+use std::time::Duration;
+
+/// Returns where `pod` can be reached over SSH.
+///
+/// The target carries the pod id, so the host key is pinned to this pod rather than
+/// to its address.
+///
+/// # Errors
+/// * `CliError::InvalidInput` - If the pod has no SSH command or it cannot be parsed
+pub fn pod_ssh_target(pod: &PodInfo) -> Result<SshTarget> {
+    let ssh_cmd = pod
+        .ssh_cmd
+        .as_deref()
+        .filter(|cmd| !cmd.trim().is_empty())
+        .ok_or_else(|| {
+            CliError::InvalidInput(format!("Pod {} has no SSH connection info", pod.huid))
+        })?;
+    let target = SshTarget::from_ssh_command(ssh_cmd)
+        .map_err(|e| CliError::InvalidInput(format!("Failed to parse SSH command: {}", e)))?;
+    Ok(target.for_pod(&pod.id))
+}
+
+/// Returns the credentials configured with `ssh.key_path`, `ssh.identities` and
+/// `ssh.agent`.
+///
+/// # Errors
+/// * `CliError::InvalidInput` - If ssh-agent is disabled or not running and none of
+///   the configured private keys exist
+pub fn ssh_auth(config: &Config) -> Result<SshAuth> {
+    build_auth(config, None)
+}
+
+/// Returns the credentials for `pod`: the key it was created with (if recorded by
+/// `lium up --ssh-key`), then those from [`ssh_auth`].
+///
+/// # Errors
+/// * `CliError::InvalidInput` - If no key or ssh-agent is available
+pub fn pod_ssh_auth(config: &Config, pod: &PodInfo) -> Result<SshAuth> {
+    let pod_key = State::load()?.data.pod_keys.get(&pod.id).cloned();
+    build_auth(config, pod_key)
+}
+
+fn build_auth(config: &Config, pod_key: Option<PathBuf>) -> Result<SshAuth> {
+    let identities = pod_key.into_iter().chain(config.get_ssh_identities()?);
+    let auth = identities
+        .fold(SshAuth::default(), SshAuth::with_identity)
+        .with_agent(config.ssh_agent_enabled() && std::env::var_os("SSH_AUTH_SOCK").is_some());
+
+    if !auth.agent && !auth.identities.iter().any(|path| path.exists()) {
+        let tried = auth
+            .identities
+            .iter()
+            .map(|path| path.display().to_string())
+            .collect::<Vec<_>>();
+        return Err(CliError::InvalidInput(format!(
+            "No SSH private key found{} and no ssh-agent is running. Configure one with: \
+             lium config set ssh.key_path /path/to/key.pub",
+            if tried.is_empty() {
+                String::new()
+            } else {
+                format!(" (looked for {})", tried.join(", "))
+            }
+        )));
+    }
+    Ok(auth)
+}
+
+/// Records that `pod_id` was created with the private key at `private_key`.
+///
+/// # Errors
+/// * `CliError::Io` - If `~/.lium/state.json` cannot be written
+pub fn remember_pod_key(pod_id: &str, private_key: &Path) -> Result<()> {
+    State::load()?.update(|data| {
+        data.pod_keys
+            .insert(pod_id.to_string(), private_key.to_path_buf())
+    })?;
+    Ok(())
+}
+
+/// Runs `work` with the pooled session for `target`, connecting first if needed.
+///
+/// libssh2 calls block, so `work` runs on tokio's blocking thread pool.
+///
+/// # Arguments
+/// * `target` - Pod to connect to
+/// * `auth` - Credentials used if a new connection is needed
+/// * `work` - Operation to run with the session
+///
+/// # Returns
+/// * `Result<T>` - Whatever `work` returns
+///
+/// # Examples
+/// ```rust
+/// # use lium_cli::Config;
+/// # use lium_api::PodInfo;
+/// # async fn example(config: Config, pod: PodInfo) -> lium_cli::Result<()> {
+/// use lium_cli::remote::{pod_ssh_target, ssh_auth, with_session};
+///
+/// let target = pod_ssh_target(&pod)?;
+/// let (stdout, _, code) =
+///     with_session(&target, &ssh_auth(&config)?, |s| s.exec("nvidia-smi -L")).await?;
+/// # Ok(())
+/// # }
+/// ```
+pub async fn with_session<T, F>(target: &SshTarget, auth: &SshAuth, work: F) -> Result<T>
+where
+    T: Send + 'static,
+    F: FnOnce(&SshSession) -> lium_utils::Result<T> + Send + 'static,
+{
+    let (target, auth) = (target.clone(), auth.clone());
+    tokio::task::spawn_blocking(move || {
+        let session = SshPool::global().get(&target, &auth)?;
+        work(&session)
+    })
+    .await
+    .map_err(|e| CliError::OperationFailed(format!("SSH task failed: {}", e)))?
+    .map_err(CliError::from)
+}
+
+/// A command to run on a pod with [`exec_on_pod`].
+pub struct RemoteCommand {
+    /// Shell command line
+    pub command: String,
+    /// Bytes written to the command's stdin before `stream`
+    pub stdin: Vec<u8>,
+    /// Further stdin, forwarded as it arrives (`lium exec --stdin`)
+    pub stream: Option<mpsc::Receiver<Vec<u8>>>,
+    /// Stops the command when cancelled
+    pub cancel: CancelToken,
+    /// Stops the command when it runs longer than this
+    pub timeout: Option<Duration>,
+}
+
+/// Runs commands on pods for [`exec_on_pod`].
+///
+/// The default, [`PooledSessions`], connects over SSH through the process-wide
+/// [`SshPool`]; tests and embedders install their own with [`set_pod_sessions`].
+/// Implementations may block: they are always called on tokio's blocking thread pool.
+pub trait PodSessions: Send + Sync {
+    /// Runs `command` on `target`, passing each output line to `on_output`, and
+    /// returns its exit status
+    fn exec(
+        &self,
+        target: &SshTarget,
+        auth: &SshAuth,
+        command: RemoteCommand,
+        on_output: &mut dyn FnMut(OutputStream, &str),
+    ) -> lium_utils::Result<i32>;
+}
+
+/// Runs commands through the pooled SSH session of each pod
+pub struct PooledSessions;
+
+impl PodSessions for PooledSessions {
+    fn exec(
+        &self,
+        target: &SshTarget,
+        auth: &SshAuth,
+        command: RemoteCommand,
+        on_output: &mut dyn FnMut(OutputStream, &str),
+    ) -> lium_utils::Result<i32> {
+        let session = SshPool::global().get(target, auth)?;
+        let RemoteCommand {
+            command,
+            stdin,
+            stream,
+            cancel,
+            timeout,
+        } = command;
+        let mut input = ExecInput::bytes(&stdin);
+        if let Some(stream) = stream {
+            input = input.with_stream(stream);
+        }
+        session.exec_cancellable(&command, input, &cancel, timeout, on_output)
+    }
+}
+
+static POD_SESSIONS: RwLock<Option<Arc<dyn PodSessions>>> = RwLock::new(None);
+
+/// Runs every later [`exec_on_pod`] through `sessions` instead of [`PooledSessions`]
+pub fn set_pod_sessions(sessions: Arc<dyn PodSessions>) {
+    *POD_SESSIONS.write().unwrap_or_else(PoisonError::into_inner) = Some(sessions);
+}
+
+/// Runs `command` on `target` through the installed [`PodSessions`], passing each line
+/// it prints to `on_output` together with `state`.
+///
+/// # Arguments
+/// * `target` - Pod to run the command on
+/// * `auth` - Credentials used if a new connection is needed
+/// * `command` - The command and its input
+/// * `state` - Handed to every `on_output` call and returned, e.g. captured output
+/// * `on_output` - Called with each line of stdout and stderr as it arrives
+///
+/// # Returns
+/// * `Result<(i32, S)>` - The command's exit status and the final `state`
+///
+/// # Errors
+/// * `CliError::Utils` - If the connection fails, or the command times out or is
+///   cancelled
+///
+/// # Examples
+/// ```rust,ignore
+/// use lium_cli::remote::{exec_on_pod, pod_ssh_auth, pod_ssh_target, RemoteCommand};
+/// use lium_utils::CancelToken;
+///
+/// let command = RemoteCommand {
+///     command: "nvidia-smi -L".to_string(),
+///     stdin: Vec::new(),
+///     stream: None,
+///     cancel: CancelToken::new(),
+///     timeout: None,
+/// };
+/// let (code, lines) = exec_on_pod(
+///     &pod_ssh_target(&pod)?,
+///     &pod_ssh_auth(&config, &pod)?,
+///     command,
+///     Vec::new(),
+///     |lines: &mut Vec<String>, _, line| lines.push(line.to_string()),
+/// )
+/// .await?;
+/// ```
+pub async fn exec_on_pod<S, F>(
+    target: &SshTarget,
+    auth: &SshAuth,
+    command: RemoteCommand,
+    mut state: S,
+    mut on_output: F,
+) -> Result<(i32, S)>
+where
+    S: Send + 'static,
+    F: FnMut(&mut S, OutputStream, &str) + Send + 'static,
+{
+    let sessions: Arc<dyn PodSessions> = POD_SESSIONS
+        .read()
+        .unwrap_or_else(PoisonError::into_inner)
+        .clone()
+        .unwrap_or_else(|| Arc::new(PooledSessions));
+    let (target, auth) = (target.clone(), auth.clone());
+    tokio::task::spawn_blocking(move || {
+        let result = sessions.exec(&target, &auth, command, &mut |out, line| {
+            on_output(&mut state, out, line)
+        });
+        result.map(|code| (code, state))
+    })
+    .await
+    .map_err(|e| CliError::OperationFailed(format!("SSH task failed: {}", e)))?
+    .map_err(CliError::from)
+}
+
+/// Forgets a terminated pod: its host key in `~/.lium/known_hosts` and the key it
+/// was created with.
+///
+/// # Returns
+/// * `Result<bool>` - True if the pod had a recorded host key
+///
+/// # Errors
+/// * `CliError::Utils` - If the host key store cannot be read or rewritten
+/// * `CliError::Io` - If `~/.lium/state.json` cannot be written
+pub fn forget_pod(pod: &PodInfo) -> Result<bool> {
+    let had_host_key = SshPool::global().known_hosts().remove_pod(&pod.id)?;
+    let mut state = State::load()?;
+    if state.data.pod_keys.contains_key(&pod.id) {
+        state.update(|data| data.pod_keys.remove(&pod.id))?;
+    }
+    Ok(had_host_key)
+}
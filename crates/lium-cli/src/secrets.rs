@@ -0,0 +1,666 @@
+//! Encrypted local secret store.
+//!
+//! Secrets such as the API key and Docker token are kept in `~/.lium/secrets`,
+//! encrypted with ChaCha20-Poly1305 under a key derived (PBKDF2-HMAC-SHA256) from a
+//! passphrase or from the contents of a key file. Config values refer to them as
+//! `secret:<name>` and are resolved when they are used, so `config.toml` never has to
+//! hold credentials in plaintext.
+//!
+//! The store is unlocked with, in order:
+//! * the key file set by `secrets.key_file` (or `LIUM_SECRETS_KEY_FILE`), for headless use
+//! * the `LIUM_SECRETS_PASSPHRASE` environment variable
+//! * an interactive passphrase prompt
+
+use crate::config::{expand_path, get_config_dir, Config};
+use crate::files::{write_atomic, FileLock};
+use crate::{CliError, Result};
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use dialoguer::Password;
+use lium_utils::redact::register_secret;
+use ring::aead::{Aad, LessSafeKey, Nonce, UnboundKey, CHACHA20_POLY1305, NONCE_LEN};
+use ring::pbkdf2;
+use ring::rand::{SecureRandom, SystemRandom};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::num::NonZeroU32;
+use std::path::PathBuf;
+use std::sync::{Mutex, PoisonError};
+use thiserror::Error;
+
+/// File name of the secret store inside the configuration directory.
+pub const SECRETS_FILE: &str = "secrets";
+
+/// Prefix that marks a config value as a reference to a stored secret.
+pub const SECRET_REF_PREFIX: &str = "secret:";
+
+/// Environment variable holding the passphrase for non-interactive use.
+pub const PASSPHRASE_ENV: &str = "LIUM_SECRETS_PASSPHRASE";
+
+/// PBKDF2 iterations used for new stores.
+pub const DEFAULT_KDF_ITERATIONS: u32 = 600_000;
+
+pub use lium_utils::redact::MASK;
+
+/// Line that ends the variables sent ahead of a remote command's own stdin.
+const STDIN_ENV_END: &str = "__LIUM_SECRETS_END__";
+
+const STORE_VERSION: u32 = 1;
+const KDF_NAME: &str = "pbkdf2-hmac-sha256";
+const SALT_LEN: usize = 16;
+const MIN_KEY_FILE_LEN: usize = 16;
+
+/// Secret store errors
+///
+/// # Variants
+/// * `NotFound` - No secret with that name is stored
+/// * `InvalidName` - A secret name has characters other than letters, digits, `_`, `-` and `.`
+/// * `WrongKey` - The passphrase or key file does not unlock the store
+/// * `InvalidStore` - The store file is corrupt or has an unsupported format
+/// * `InvalidKeyFile` - The key file is missing or too short
+/// * `NoPassphrase` - No key file or passphrase is available and no terminal to prompt on
+#[derive(Error, Debug)]
+pub enum SecretError {
+    #[error("Secret '{0}' not found (see 'lium secrets list')")]
+    NotFound(String),
+
+    #[error("Invalid secret name '{0}': use letters, digits, '_', '-' and '.'")]
+    InvalidName(String),
+
+    #[error("Could not unlock the secret store: wrong passphrase or key file")]
+    WrongKey,
+
+    #[error("Invalid secret store: {0}")]
+    InvalidStore(String),
+
+    #[error("Invalid key file {0}: {1}")]
+    InvalidKeyFile(String, String),
+
+    #[error("The secret store is locked: set secrets.key_file or {PASSPHRASE_ENV}")]
+    NoPassphrase,
+}
+
+/// Material used to derive the store's encryption key
+#[derive(Clone)]
+pub enum Unlock {
+    Passphrase(String),
+    KeyFile(PathBuf),
+}
+
+impl std::fmt::Debug for Unlock {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Unlock::Passphrase(_) => write!(f, "Passphrase(***)"),
+            Unlock::KeyFile(path) => write!(f, "KeyFile({})", path.display()),
+        }
+    }
+}
+
+impl Unlock {
+    /// Chooses how to unlock the store for this run.
+    ///
+    /// # Arguments
+    /// * `config` - Configuration holding `secrets.key_file`
+    /// * `creating` - The store does not exist yet, so a prompted passphrase is confirmed
+    ///
+    /// # Errors
+    /// * `SecretError::NoPassphrase` - If there is nothing to unlock with
+    pub fn resolve(config: &Config, creating: bool) -> Result<Self> {
+        if let Some(key_file) = config.secrets().key_file {
+            return Ok(Unlock::KeyFile(expand_path(&key_file)?));
+        }
+        if let Ok(passphrase) = std::env::var(PASSPHRASE_ENV) {
+            return Ok(Unlock::Passphrase(passphrase));
+        }
+
+        let mut prompt = Password::new()
+            .with_prompt("Secret store passphrase")
+            .allow_empty_password(false);
+        if creating {
+            prompt = prompt.with_confirmation("Confirm passphrase", "Passphrases do not match");
+        }
+        let passphrase = prompt.interact().map_err(|_| SecretError::NoPassphrase)?;
+        Ok(Unlock::Passphrase(passphrase))
+    }
+
+    fn material(&self) -> Result<Vec<u8>> {
+        match self {
+            Unlock::Passphrase(passphrase) => Ok(passphrase.as_bytes().to_vec()),
+            Unlock::KeyFile(path) => {
+                let bytes = fs::read(path).map_err(|e| {
+                    SecretError::InvalidKeyFile(path.display().to_string(), e.to_string())
+                })?;
+                let trimmed = bytes.trim_ascii_end().to_vec();
+                if trimmed.len() < MIN_KEY_FILE_LEN {
+                    return Err(SecretError::InvalidKeyFile(
+                        path.display().to_string(),
+                        format!("must contain at least {} bytes", MIN_KEY_FILE_LEN),
+                    )
+                    .into());
+                }
+                Ok(trimmed)
+            }
+        }
+    }
+}
+
+/// On-disk format of the secret store
+#[derive(Serialize, Deserialize)]
+struct StoreFile {
+    version: u32,
+    kdf: String,
+    iterations: u32,
+    salt: String,
+    nonce: String,
+    ciphertext: String,
+}
+
+/// An unlocked secret store
+///
+/// # Fields
+/// * `path` - Path to the encrypted store file
+/// * `secrets` - Decrypted secrets by name
+#[derive(Debug)]
+pub struct SecretStore {
+    pub path: PathBuf,
+    secrets: BTreeMap<String, String>,
+    unlock: Unlock,
+    salt: Vec<u8>,
+    iterations: u32,
+}
+
+impl SecretStore {
+    /// Returns the default store path, `~/.lium/secrets`
+    pub fn default_path() -> Result<PathBuf> {
+        Ok(get_config_dir()?.join(SECRETS_FILE))
+    }
+
+    /// Unlocks the default store with the key file, passphrase variable or a prompt.
+    ///
+    /// # Arguments
+    /// * `config` - Configuration holding `secrets.key_file`
+    ///
+    /// # Returns
+    /// * `Result<SecretStore>` - The unlocked store, empty if it does not exist yet
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use lium_cli::{secrets::SecretStore, Config};
+    /// # fn example(config: Config) -> lium_cli::Result<()> {
+    /// let store = SecretStore::unlock(&config)?;
+    /// let token = store.get("docker_token")?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn unlock(config: &Config) -> Result<Self> {
+        let path = Self::default_path()?;
+        let unlock = Unlock::resolve(config, !path.exists())?;
+        Self::open(path, unlock)
+    }
+
+    /// Opens and decrypts the store at `path`, or starts an empty one if it does not exist.
+    ///
+    /// # Errors
+    /// * `SecretError::WrongKey` - If `unlock` does not decrypt the store
+    /// * `SecretError::InvalidStore` - If the file is corrupt
+    pub fn open(path: PathBuf, unlock: Unlock) -> Result<Self> {
+        Self::open_with_iterations(path, unlock, DEFAULT_KDF_ITERATIONS)
+    }
+
+    /// Like [`SecretStore::open`], with the PBKDF2 iterations used if a new store is created
+    fn open_with_iterations(path: PathBuf, unlock: Unlock, new_iterations: u32) -> Result<Self> {
+        let mut store = SecretStore {
+            path,
+            secrets: BTreeMap::new(),
+            unlock,
+            salt: random_bytes(SALT_LEN)?,
+            iterations: new_iterations,
+        };
+        store.reload()?;
+        Ok(store)
+    }
+
+    /// Re-reads and decrypts the store file, if it exists
+    fn reload(&mut self) -> Result<()> {
+        let content = match fs::read_to_string(&self.path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+            Err(e) => return Err(CliError::Io(e)),
+        };
+        let file: StoreFile =
+            serde_json::from_str(&content).map_err(|e| SecretError::InvalidStore(e.to_string()))?;
+        if file.version != STORE_VERSION || file.kdf != KDF_NAME || file.iterations == 0 {
+            return Err(SecretError::InvalidStore(format!(
+                "unsupported format (version {}, kdf {})",
+                file.version, file.kdf
+            ))
+            .into());
+        }
+
+        let decode = |field: &str, value: &str| {
+            BASE64
+                .decode(value)
+                .map_err(|e| SecretError::InvalidStore(format!("{}: {}", field, e)))
+        };
+        let salt = decode("salt", &file.salt)?;
+        let nonce = decode("nonce", &file.nonce)?;
+        let mut ciphertext = decode("ciphertext", &file.ciphertext)?;
+
+        let key = self.derive_key(&salt, file.iterations)?;
+        let nonce = Nonce::try_assume_unique_for_key(&nonce)
+            .map_err(|_| SecretError::InvalidStore("bad nonce".to_string()))?;
+        let plaintext = key
+            .open_in_place(nonce, aad(file.iterations), &mut ciphertext)
+            .map_err(|_| SecretError::WrongKey)?;
+
+        self.secrets = serde_json::from_slice(plaintext)
+            .map_err(|e| SecretError::InvalidStore(e.to_string()))?;
+        self.salt = salt;
+        self.iterations = file.iterations;
+        Ok(())
+    }
+
+    /// Returns a secret's value
+    ///
+    /// # Errors
+    /// * `SecretError::NotFound` - If no secret has that name
+    pub fn get(&self, name: &str) -> Result<&str> {
+        self.secrets
+            .get(name)
+            .map(String::as_str)
+            .ok_or_else(|| SecretError::NotFound(name.to_string()).into())
+    }
+
+    /// Returns the names of all stored secrets, sorted
+    pub fn names(&self) -> Vec<String> {
+        self.secrets.keys().cloned().collect()
+    }
+
+    /// Applies a change to the latest store on disk and saves it, holding the store's
+    /// file lock throughout.
+    ///
+    /// # Arguments
+    /// * `change` - Modifies the decrypted secrets
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use lium_cli::{secrets::SecretStore, Config};
+    /// # fn example(mut store: SecretStore, token: &str) -> lium_cli::Result<()> {
+    /// store.update(|secrets| secrets.set("docker_token", token))?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn update<R>(&mut self, change: impl FnOnce(&mut Secrets) -> Result<R>) -> Result<R> {
+        let _lock = FileLock::acquire(&self.path)?;
+        self.reload()?;
+
+        let mut secrets = Secrets(self.secrets.clone());
+        let result = change(&mut secrets)?;
+        self.secrets = secrets.0;
+        self.write()?;
+        Ok(result)
+    }
+
+    /// Encrypts the secrets under a fresh nonce and writes the store file
+    fn write(&self) -> Result<()> {
+        let key = self.derive_key(&self.salt, self.iterations)?;
+        let nonce = random_bytes(NONCE_LEN)?;
+        let mut buffer = serde_json::to_vec(&self.secrets)?;
+        key.seal_in_place_append_tag(
+            Nonce::try_assume_unique_for_key(&nonce)
+                .map_err(|_| SecretError::InvalidStore("bad nonce".to_string()))?,
+            aad(self.iterations),
+            &mut buffer,
+        )
+        .map_err(|_| SecretError::InvalidStore("encryption failed".to_string()))?;
+
+        let file = StoreFile {
+            version: STORE_VERSION,
+            kdf: KDF_NAME.to_string(),
+            iterations: self.iterations,
+            salt: BASE64.encode(&self.salt),
+            nonce: BASE64.encode(nonce),
+            ciphertext: BASE64.encode(buffer),
+        };
+        write_atomic(&self.path, serde_json::to_string_pretty(&file)?.as_bytes())
+    }
+
+    fn derive_key(&self, salt: &[u8], iterations: u32) -> Result<LessSafeKey> {
+        let iterations = NonZeroU32::new(iterations)
+            .ok_or_else(|| SecretError::InvalidStore("zero KDF iterations".to_string()))?;
+        let mut key = [0u8; 32];
+        pbkdf2::derive(
+            pbkdf2::PBKDF2_HMAC_SHA256,
+            iterations,
+            salt,
+            &self.unlock.material()?,
+            &mut key,
+        );
+        let key = UnboundKey::new(&CHACHA20_POLY1305, &key)
+            .map_err(|_| SecretError::InvalidStore("bad key".to_string()))?;
+        Ok(LessSafeKey::new(key))
+    }
+}
+
+/// Mutable view of the decrypted secrets passed to [`SecretStore::update`]
+pub struct Secrets(BTreeMap<String, String>);
+
+impl Secrets {
+    /// Stores a secret, replacing any previous value
+    ///
+    /// # Errors
+    /// * `SecretError::InvalidName` - If the name is empty or has unsupported characters
+    pub fn set(&mut self, name: &str, value: &str) -> Result<()> {
+        validate_name(name)?;
+        self.0.insert(name.to_string(), value.to_string());
+        Ok(())
+    }
+
+    /// Removes a secret
+    ///
+    /// # Errors
+    /// * `SecretError::NotFound` - If no secret has that name
+    pub fn remove(&mut self, name: &str) -> Result<()> {
+        self.0
+            .remove(name)
+            .map(|_| ())
+            .ok_or_else(|| SecretError::NotFound(name.to_string()).into())
+    }
+
+    /// Returns true if a secret with that name is stored
+    pub fn contains(&self, name: &str) -> bool {
+        self.0.contains_key(name)
+    }
+}
+
+/// Returns the secret name if `value` is a `secret:<name>` reference
+pub fn secret_ref(value: &str) -> Option<&str> {
+    value.strip_prefix(SECRET_REF_PREFIX)
+}
+
+/// The default store once unlocked, so a run asks for the passphrase at most once
+static UNLOCKED: Mutex<Option<SecretStore>> = Mutex::new(None);
+
+/// Resolves a config value that may be a `secret:<name>` reference.
+///
+/// Plain values are returned unchanged. The first reference resolved in a run unlocks
+/// the default store, which then stays unlocked until the process exits.
+///
+/// # Arguments
+/// * `config` - Configuration holding `secrets.key_file`
+/// * `value` - A config value, e.g. `secret:docker_token`
+///
+/// # Errors
+/// * `SecretError::NotFound` - If the store does not exist or has no such secret
+/// * `SecretError::WrongKey` - If the store cannot be unlocked
+///
+/// # Examples
+/// ```rust
+/// # use lium_cli::{secrets, Config};
+/// # fn example(config: Config) -> lium_cli::Result<()> {
+/// let token = secrets::resolve(&config, "secret:docker_token".to_string())?;
+/// # Ok(())
+/// # }
+/// ```
+pub fn resolve(config: &Config, value: String) -> Result<String> {
+    let Some(name) = secret_ref(&value) else {
+        return Ok(value);
+    };
+
+    stored(config, name)?.ok_or_else(|| SecretError::NotFound(name.to_string()).into())
+}
+
+/// Looks a secret up in the default store, unlocking it on first use
+///
+/// # Returns
+/// * `Result<Option<String>>` - The value, `None` if there is no store or no such secret
+fn stored(config: &Config, name: &str) -> Result<Option<String>> {
+    let mut unlocked = UNLOCKED.lock().unwrap_or_else(PoisonError::into_inner);
+    if unlocked.is_none() {
+        if !SecretStore::default_path()?.exists() {
+            return Ok(None);
+        }
+        *unlocked = Some(SecretStore::unlock(config)?);
+    }
+    let store = unlocked.as_ref().expect("store unlocked above");
+    let value = store.secrets.get(name).cloned();
+    if let Some(value) = &value {
+        register_secret(value);
+    }
+    Ok(value)
+}
+
+/// A secret exported as an environment variable on a pod
+///
+/// # Fields
+/// * `var` - Environment variable name
+/// * `value` - The secret's value, which must never be printed
+#[derive(Clone)]
+pub struct SecretVar {
+    pub var: String,
+    pub value: String,
+}
+
+impl std::fmt::Display for SecretVar {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}={}", self.var, MASK)
+    }
+}
+
+/// Resolves `--secret` flags into variables to export on a pod.
+///
+/// Each flag is `VAR` or `VAR=NAME`. The value of secret `NAME` (`VAR` if not given) is
+/// taken from the secret store, falling back to the local environment variable `NAME`.
+///
+/// # Arguments
+/// * `config` - Configuration holding `secrets.key_file`
+/// * `specs` - The `--secret` flag values
+///
+/// # Errors
+/// * `CliError::InvalidInput` - If `VAR` is not a valid environment variable name
+/// * `SecretError::NotFound` - If neither the store nor the environment has the secret
+///
+/// # Examples
+/// ```rust
+/// # use lium_cli::{secrets::resolve_secret_vars, Config};
+/// # fn example(config: Config) -> lium_cli::Result<()> {
+/// // lium exec 1 --secret HF_TOKEN --secret WANDB_API_KEY=wandb -- python train.py
+/// let vars = resolve_secret_vars(&config, &["HF_TOKEN".into(), "WANDB_API_KEY=wandb".into()])?;
+/// # Ok(())
+/// # }
+/// ```
+pub fn resolve_secret_vars(config: &Config, specs: &[String]) -> Result<Vec<SecretVar>> {
+    specs
+        .iter()
+        .map(|spec| {
+            let (var, name) = parse_secret_spec(spec)?;
+            let value = match stored(config, name)? {
+                Some(value) => value,
+                None => std::env::var(name).map_err(|_| SecretError::NotFound(name.to_string()))?,
+            };
+            register_secret(&value);
+            Ok(SecretVar {
+                var: var.to_string(),
+                value,
+            })
+        })
+        .collect()
+}
+
+/// Splits a `--secret` flag into the variable name and the secret name
+fn parse_secret_spec(spec: &str) -> Result<(&str, &str)> {
+    let (var, name) = spec.split_once('=').unwrap_or((spec, spec));
+    let valid_var = var.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+        && var.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+    if !valid_var {
+        return Err(CliError::InvalidInput(format!(
+            "Invalid --secret '{}': use VAR or VAR=NAME, where VAR is an environment variable name",
+            spec
+        )));
+    }
+    validate_name(name)?;
+    Ok((var, name))
+}
+
+/// `export VAR='value'` lines for the variables, one per variable.
+///
+/// Values are single-quoted, so they are never expanded by the remote shell and may
+/// contain any character, including newlines.
+pub fn secret_exports(vars: &[SecretVar]) -> String {
+    vars.iter()
+        .map(|v| format!("export {}='{}'\n", v.var, v.value.replace('\'', r"'\''")))
+        .collect()
+}
+
+/// Wraps a remote command so it first reads secret exports from stdin.
+///
+/// The wrapper reads lines up to an end marker, evaluates them, and then runs
+/// `command` with the rest of stdin, so the values reach the command's environment
+/// without ever appearing on a command line (and so in `ps` on the pod). Send
+/// [`stdin_env_payload`] on the SSH session's stdin.
+pub fn stdin_env_command(command: &str) -> String {
+    format!(
+        "__lium_env=; \
+         while IFS= read -r __lium_line && [ \"$__lium_line\" != {end} ]; do \
+         __lium_env=\"$__lium_env$__lium_line\n\"; done; \
+         eval \"$__lium_env\"; unset __lium_env __lium_line; {command}",
+        end = STDIN_ENV_END,
+        command = command
+    )
+}
+
+/// Stdin for a command wrapped by [`stdin_env_command`]
+pub fn stdin_env_payload(vars: &[SecretVar]) -> String {
+    format!("{}{}\n", secret_exports(vars), STDIN_ENV_END)
+}
+
+fn validate_name(name: &str) -> Result<()> {
+    let valid = !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.'));
+    if !valid {
+        return Err(SecretError::InvalidName(name.to_string()).into());
+    }
+    Ok(())
+}
+
+/// Binds the ciphertext to the store format and KDF parameters
+fn aad(iterations: u32) -> Aad<Vec<u8>> {
+    Aad::from(
+        format!(
+            "lium-secrets:v{}:{}:{}",
+            STORE_VERSION, KDF_NAME, iterations
+        )
+        .into_bytes(),
+    )
+}
+
+fn random_bytes(len: usize) -> Result<Vec<u8>> {
+    let mut bytes = vec![0u8; len];
+    SystemRandom::new()
+        .fill(&mut bytes)
+        .map_err(|_| SecretError::InvalidStore("system random source failed".to_string()))?;
+    Ok(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    const TEST_ITERATIONS: u32 = 1_000;
+
+    fn open(path: &Path, unlock: Unlock) -> Result<SecretStore> {
+        SecretStore::open_with_iterations(path.to_path_buf(), unlock, TEST_ITERATIONS)
+    }
+
+    #[test]
+    fn test_secrets_round_trip_and_reject_wrong_passphrase() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(SECRETS_FILE);
+        let passphrase = || Unlock::Passphrase("correct horse".to_string());
+
+        let mut store = open(&path, passphrase()).unwrap();
+        store
+            .update(|secrets| secrets.set("docker_token", "dckr_pat_123"))
+            .unwrap();
+        assert!(!fs::read_to_string(&path).unwrap().contains("dckr_pat_123"));
+
+        let mut store = open(&path, passphrase()).unwrap();
+        assert_eq!(store.get("docker_token").unwrap(), "dckr_pat_123");
+        assert_eq!(store.names(), vec!["docker_token"]);
+        assert!(store
+            .update(|secrets| secrets.set("bad name", "x"))
+            .is_err());
+        store
+            .update(|secrets| secrets.remove("docker_token"))
+            .unwrap();
+        assert!(matches!(
+            store.get("docker_token"),
+            Err(CliError::Secret(SecretError::NotFound(_)))
+        ));
+
+        assert!(matches!(
+            open(&path, Unlock::Passphrase("wrong".to_string())),
+            Err(CliError::Secret(SecretError::WrongKey))
+        ));
+    }
+
+    #[test]
+    fn test_stdin_env_wrapper_exports_secrets_and_keeps_stdin() {
+        use std::io::Write;
+        use std::process::{Command, Stdio};
+
+        let vars = vec![SecretVar {
+            var: "HF_TOKEN".to_string(),
+            value: "it's a\nmulti-line $secret".to_string(),
+        }];
+        let command = stdin_env_command(r#"printf '%s|' "$HF_TOKEN"; cat"#);
+        assert!(!command.contains("multi-line"));
+
+        let mut child = Command::new("sh")
+            .arg("-c")
+            .arg(&command)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()
+            .unwrap();
+        let mut stdin = child.stdin.take().unwrap();
+        stdin
+            .write_all(stdin_env_payload(&vars).as_bytes())
+            .unwrap();
+        stdin.write_all(b"user input").unwrap();
+        drop(stdin);
+        let output = child.wait_with_output().unwrap();
+
+        let stdout = String::from_utf8(output.stdout).unwrap();
+        assert_eq!(stdout, "it's a\nmulti-line $secret|user input");
+        assert_eq!(vars[0].to_string(), "HF_TOKEN=***");
+
+        assert_eq!(parse_secret_spec("HF=hf").unwrap(), ("HF", "hf"));
+        assert!(parse_secret_spec("1BAD").is_err());
+        assert!(parse_secret_spec("HF=bad name").is_err());
+    }
+
+    #[test]
+    fn test_key_file_unlocks_store() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(SECRETS_FILE);
+        let key_file = dir.path().join("secrets.key");
+        fs::write(&key_file, "0123456789abcdef0123456789abcdef\n").unwrap();
+
+        open(&path, Unlock::KeyFile(key_file.clone()))
+            .unwrap()
+            .update(|secrets| secrets.set("api_key", "lium-key"))
+            .unwrap();
+        let store = open(&path, Unlock::KeyFile(key_file.clone())).unwrap();
+        assert_eq!(store.get("api_key").unwrap(), "lium-key");
+
+        fs::write(&key_file, "short").unwrap();
+        assert!(matches!(
+            open(&path, Unlock::KeyFile(key_file)),
+            Err(CliError::Secret(SecretError::InvalidKeyFile(..)))
+        ));
+    }
+}
@@ -0,0 +1,153 @@
+//! Runtime state kept between CLI invocations.
+//!
+//! State such as the last `lium ls` and `lium ps` listings is written by the CLI
+//! itself on almost every run, so it lives in `~/.lium/state.json` rather than in
+//! the user's hand-edited `config.toml`.
+
+use crate::config::get_config_dir;
+use crate::files::{write_atomic, FileLock};
+use crate::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+
+/// File name of the state file inside the configuration directory.
+pub const STATE_FILE: &str = "state.json";
+
+/// Data stored in the state file
+///
+/// # Fields
+/// * `last_executor_selection` - The executors shown by the last `lium ls`, for index lookups
+/// * `last_pod_selection` - The pods shown by the last `lium ps`, for index lookups
+/// * `pod_keys` - Private key each pod was created with by `lium up --ssh-key`, by pod id
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+pub struct StateData {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_executor_selection: Option<serde_json::Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_pod_selection: Option<serde_json::Value>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub pod_keys: BTreeMap<String, PathBuf>,
+}
+
+/// Runtime state manager that loads and saves `state.json`
+///
+/// # Fields
+/// * `state_path` - Path to the state file
+/// * `data` - The state data
+#[derive(Debug, Clone)]
+pub struct State {
+    pub state_path: PathBuf,
+    pub data: StateData,
+}
+
+impl State {
+    /// Loads the state from `~/.lium/state.json`
+    ///
+    /// # Returns
+    /// * `Result<Self>` - The loaded state, empty if the file does not exist
+    ///
+    /// # Errors
+    /// * `ConfigError::DirectoryCreationFailed` - If the home directory cannot be found
+    pub fn load() -> Result<Self> {
+        Self::load_from(get_config_dir()?.join(STATE_FILE))
+    }
+
+    /// Loads the state from the given path
+    ///
+    /// State is disposable, so a missing or unreadable file yields empty state
+    /// instead of an error.
+    ///
+    /// # Arguments
+    /// * `state_path` - Path to the state file
+    ///
+    /// # Returns
+    /// * `Result<Self>` - The loaded state
+    pub fn load_from(state_path: PathBuf) -> Result<Self> {
+        let data = match fs::read_to_string(&state_path) {
+            Ok(content) => serde_json::from_str(&content).unwrap_or_else(|e| {
+                log::warn!(
+                    "Ignoring unreadable state file {}: {}",
+                    state_path.display(),
+                    e
+                );
+                StateData::default()
+            }),
+            Err(_) => StateData::default(),
+        };
+
+        Ok(State { state_path, data })
+    }
+
+    /// Saves the state to its file with an atomic write
+    ///
+    /// # Returns
+    /// * `Result<()>` - Success or error
+    ///
+    /// # Errors
+    /// * `ConfigError::Locked` - If another process holds the state file lock for too long
+    /// * `CliError::Io` - If file operations fail
+    pub fn save(&self) -> Result<()> {
+        let _lock = FileLock::acquire(&self.state_path)?;
+        self.write()
+    }
+
+    /// Applies a change to the latest state on disk and saves it, holding the state
+    /// file lock throughout so concurrent lium processes do not lose each other's updates.
+    ///
+    /// # Arguments
+    /// * `change` - Modifies the freshly loaded state data
+    ///
+    /// # Returns
+    /// * `Result<R>` - The value returned by `change`
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use lium_cli::state::State;
+    /// # fn example(selection: serde_json::Value) -> lium_cli::Result<()> {
+    /// State::load()?.update(|data| data.last_pod_selection = Some(selection))?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn update<R>(&mut self, change: impl FnOnce(&mut StateData) -> R) -> Result<R> {
+        let _lock = FileLock::acquire(&self.state_path)?;
+
+        self.data = Self::load_from(self.state_path.clone())?.data;
+        let result = change(&mut self.data);
+        self.write()?;
+        Ok(result)
+    }
+
+    fn write(&self) -> Result<()> {
+        let content = serde_json::to_string_pretty(&self.data)?;
+        write_atomic(&self.state_path, content.as_bytes())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_state_round_trip_and_unreadable_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(STATE_FILE);
+
+        let mut state = State::load_from(path.clone()).unwrap();
+        assert_eq!(state.data, StateData::default());
+
+        state.data.last_pod_selection = Some(json!({"pods": [{"id": "pod-1"}]}));
+        state
+            .data
+            .pod_keys
+            .insert("pod-1".to_string(), PathBuf::from("/keys/pod_ed25519"));
+        state.save().unwrap();
+        let reloaded = State::load_from(path.clone()).unwrap();
+        assert_eq!(reloaded.data, state.data);
+
+        fs::write(&path, "{not json").unwrap();
+        assert_eq!(State::load_from(path).unwrap().data, StateData::default());
+    }
+}
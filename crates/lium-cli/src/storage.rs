@@ -1,4 +1,5 @@
-use crate::{CliError, Result};
+use crate::state::State;
+use crate::Result;
 use lium_core::{ExecutorInfo, PodInfo};
 
 /// Trait for storing and retrieving selection data
@@ -21,7 +22,6 @@ impl SelectionStorage for ExecutorSelectionStorage {
             return Ok(());
         }
 
-        let mut config = crate::config::load_config()?;
         let gpu_type = &items[0].0; // Assume all items have the same GPU type
         let executors: Vec<&ExecutorInfo> = items.iter().map(|(_, e)| e).collect();
 
@@ -38,23 +38,14 @@ impl SelectionStorage for ExecutorSelectionStorage {
             })).collect::<Vec<_>>()
         });
 
-        config.set_value("last_selection", "data", &selection_data.to_string())?;
-        config.save()?;
+        State::load()?.update(|data| data.last_executor_selection = Some(selection_data))?;
 
         Ok(())
     }
 
     /// Get last executor selection data
     fn get_last_selection(&self) -> Result<Option<serde_json::Value>> {
-        let config = crate::config::load_config()?;
-
-        if let Some(data_str) = config.get_value("last_selection", "data")? {
-            let data: serde_json::Value = serde_json::from_str(&data_str)
-                .map_err(|e| CliError::InvalidInput(format!("Invalid selection data: {}", e)))?;
-            Ok(Some(data))
-        } else {
-            Ok(None)
-        }
+        Ok(State::load()?.data.last_executor_selection)
     }
 }
 
@@ -64,10 +55,8 @@ pub struct PodSelectionStorage;
 impl SelectionStorage for PodSelectionStorage {
     type Item = PodInfo;
 
-    /// Store pod selection data in config for later reference by index
+    /// Store pod selection data in the state file for later reference by index
     fn store_selection(&self, pods: &[Self::Item]) -> Result<()> {
-        let mut config = crate::config::load_config()?;
-
         let selection_data = serde_json::json!({
             "timestamp": chrono::Utc::now().to_rfc3339(),
             "pods": pods.iter().map(|p| serde_json::json!({
@@ -78,22 +67,14 @@ impl SelectionStorage for PodSelectionStorage {
             })).collect::<Vec<_>>()
         });
 
-        config.set_value("last_pod_selection", "data", &selection_data.to_string())?;
-        config.save()?;
+        State::load()?.update(|data| data.last_pod_selection = Some(selection_data))?;
 
         Ok(())
     }
 
-    /// Get last pod selection from config
+    /// Get last pod selection from the state file
     fn get_last_selection(&self) -> Result<Option<serde_json::Value>> {
-        let config = crate::config::load_config()?;
-
-        if let Some(data_str) = config.get_value("last_pod_selection", "data")? {
-            let selection_data: serde_json::Value = serde_json::from_str(&data_str)?;
-            Ok(Some(selection_data))
-        } else {
-            Ok(None)
-        }
+        Ok(State::load()?.data.last_pod_selection)
     }
 }
 
@@ -144,8 +125,8 @@ mod tests {
             template: json!({}),
         }];
 
-        // This would normally work with a proper config setup
-        // For testing, we'd need to mock the config system
+        // This would normally work with a proper state setup
+        // For testing, we'd need to mock the state file location
         // assert!(storage.store_selection(&pods).is_ok());
     }
 }
@@ -0,0 +1,224 @@
+//! Shared fixtures for command tests that drive the CLI flows against an
+//! in-memory API transport instead of the real Celium backend, fake pod sessions
+//! instead of SSH, and a captured console instead of the terminal.
+
+use crate::config::{Config, ConfigData, SshConfig};
+use crate::display::{set_console_sink, ConsoleSink, StdConsole};
+use crate::logging::{self, Verbosity};
+use crate::remote::{set_pod_sessions, PodSessions, PooledSessions, RemoteCommand};
+use lium_api::{CacheMode, InMemoryTransport, LiumApiClient, RetryPolicy};
+use lium_core::{ApiExecutorResponse, ApiPodResponse};
+use lium_utils::{OutputStream, SshAuth, SshTarget};
+use serde_json::json;
+use std::collections::HashMap;
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, Once, OnceLock, PoisonError};
+
+/// Config with no file on disk and no SSH key configured
+///
+/// ssh-agent is disabled as if by `LIUM_SSH_AGENT=false`, so tests never depend on
+/// an agent running on the developer's machine.
+pub fn test_config() -> Config {
+    Config {
+        config_path: std::env::temp_dir().join("lium-test-config.toml"),
+        data: ConfigData::default(),
+        cache_mode: CacheMode::Disabled,
+        profile: None,
+        project: None,
+        env_overrides: ConfigData {
+            ssh: Some(SshConfig {
+                agent: Some(false),
+                ..Default::default()
+            }),
+            ..Default::default()
+        },
+    }
+}
+
+/// Client that sends every request to `transport` and never retries
+pub fn test_client(transport: &InMemoryTransport) -> LiumApiClient {
+    LiumApiClient::with_base_url("test-api-key".to_string(), "http://lium.test".to_string())
+        .with_retry_policy(RetryPolicy::disabled())
+        .with_transport(transport.clone())
+}
+
+/// Raw executor as returned by `GET executors`; `rented` maps to the API's `active` flag
+pub fn api_executor(id: &str, price_per_hour: f64, rented: bool) -> ApiExecutorResponse {
+    serde_json::from_value(json!({
+        "id": id,
+        "machine_name": "nvidia-rtx-4090",
+        "gpu_count": 1,
+        "price_per_hour": price_per_hour,
+        "active": rented,
+    }))
+    .expect("valid executor fixture")
+}
+
+/// Raw pod as returned by `GET pods`
+pub fn api_pod(id: &str, name: &str, executor_id: &str, ssh_cmd: Option<&str>) -> ApiPodResponse {
+    serde_json::from_value(json!({
+        "id": id,
+        "pod_name": name,
+        "status": "RUNNING",
+        "ssh_connect_cmd": ssh_cmd,
+        "ports_mapping": {},
+        "created_at": "2024-01-01T00:00:00Z",
+        "updated_at": "2024-01-01T00:00:00Z",
+        "executor": { "id": executor_id },
+        "template": {},
+    }))
+    .expect("valid pod fixture")
+}
+
+/// Config like [`test_config`] with a (dummy) SSH key configured in `dir`, so commands
+/// get as far as connecting to the pod
+pub fn test_config_with_ssh_key(dir: &Path) -> Config {
+    let key = dir.join("id_test");
+    std::fs::write(&key, "private key").expect("write test key");
+    std::fs::write(dir.join("id_test.pub"), "ssh-ed25519 AAAA test").expect("write test key");
+
+    let mut config = test_config();
+    config.data.ssh = Some(SshConfig {
+        key_path: Some(key.with_extension("pub").display().to_string()),
+        ..Default::default()
+    });
+    config
+}
+
+/// What a fake pod session prints when a command runs on it
+#[derive(Debug, Clone)]
+pub struct FakeSession {
+    pub output: Vec<(OutputStream, String)>,
+    pub exit_code: i32,
+}
+
+static FAKE_SESSIONS: Mutex<Option<HashMap<String, FakeSession>>> = Mutex::new(None);
+
+/// Pod sessions that answer from [`FAKE_SESSIONS`] and connect over SSH otherwise
+struct FakeSessions;
+
+impl PodSessions for FakeSessions {
+    fn exec(
+        &self,
+        target: &SshTarget,
+        auth: &SshAuth,
+        command: RemoteCommand,
+        on_output: &mut dyn FnMut(OutputStream, &str),
+    ) -> lium_utils::Result<i32> {
+        match fake_session_for(target) {
+            Some(fake) => {
+                for (stream, line) in &fake.output {
+                    on_output(*stream, line);
+                }
+                Ok(fake.exit_code)
+            }
+            None => PooledSessions.exec(target, auth, command, on_output),
+        }
+    }
+}
+
+/// Makes every command run on `pod_id` print `output` and exit with `exit_code`,
+/// instead of connecting over SSH. Tests use pod ids of their own, so they do not
+/// see each other's sessions.
+pub fn fake_session(pod_id: &str, output: &[(OutputStream, &str)], exit_code: i32) {
+    static INSTALL: Once = Once::new();
+    INSTALL.call_once(|| set_pod_sessions(Arc::new(FakeSessions)));
+
+    let session = FakeSession {
+        output: output
+            .iter()
+            .map(|(stream, line)| (*stream, line.to_string()))
+            .collect(),
+        exit_code,
+    };
+    FAKE_SESSIONS
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .get_or_insert_with(HashMap::new)
+        .insert(pod_id.to_string(), session);
+}
+
+/// The fake session registered for `target`'s pod, if any
+fn fake_session_for(target: &SshTarget) -> Option<FakeSession> {
+    let pod_id = target.pod_id.as_ref()?;
+    FAKE_SESSIONS
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .as_ref()?
+        .get(pod_id)
+        .cloned()
+}
+
+/// Everything written to the console and the log file while [`capture_output`] ran
+#[derive(Debug, Default)]
+pub struct Captured {
+    pub stdout: String,
+    pub stderr: String,
+    pub log: String,
+}
+
+impl Captured {
+    /// True if `text` appears in stdout, stderr or the log
+    pub fn contains(&self, text: &str) -> bool {
+        [&self.stdout, &self.stderr, &self.log]
+            .iter()
+            .any(|output| output.contains(text))
+    }
+}
+
+static CAPTURED: Mutex<Option<Captured>> = Mutex::new(None);
+
+/// Held while a test captures output, so captures do not overlap
+static CAPTURE_LOCK: tokio::sync::Mutex<()> = tokio::sync::Mutex::const_new(());
+
+/// Console sink that appends to the running capture, printing as usual when nothing
+/// is capturing
+struct CaptureConsole;
+
+impl ConsoleSink for CaptureConsole {
+    fn write(&self, stream: OutputStream, text: &str) {
+        let mut captured = CAPTURED.lock().unwrap_or_else(PoisonError::into_inner);
+        match (captured.as_mut(), stream) {
+            (Some(captured), OutputStream::Stdout) => captured.stdout.push_str(text),
+            (Some(captured), OutputStream::Stderr) => captured.stderr.push_str(text),
+            (None, _) => StdConsole.write(stream, text),
+        }
+    }
+}
+
+/// Log file the CLI logger writes to in tests, installed at trace level on first use
+fn test_log_file() -> &'static Path {
+    static LOG_FILE: OnceLock<PathBuf> = OnceLock::new();
+    LOG_FILE.get_or_init(|| {
+        let path = std::env::temp_dir().join(format!("lium-test-{}.log", std::process::id()));
+        logging::init(Verbosity::Trace, Some(&path)).expect("install test logger");
+        path
+    })
+}
+
+/// Runs `run` with the CLI's console output and log records captured.
+///
+/// Output of tests running concurrently may also be captured, so assertions should
+/// look for values unique to the test.
+pub async fn capture_output<F: Future>(run: F) -> (F::Output, Captured) {
+    static INSTALL: Once = Once::new();
+    INSTALL.call_once(|| set_console_sink(Arc::new(CaptureConsole)));
+
+    let _guard = CAPTURE_LOCK.lock().await;
+    let log_file = test_log_file();
+    let log_start = std::fs::read(log_file).map_or(0, |log| log.len());
+
+    *CAPTURED.lock().unwrap_or_else(PoisonError::into_inner) = Some(Captured::default());
+    let output = run.await;
+    let mut captured = CAPTURED
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .take()
+        .unwrap_or_default();
+
+    log::logger().flush();
+    let log = std::fs::read(log_file).unwrap_or_default();
+    captured.log = String::from_utf8_lossy(log.get(log_start..).unwrap_or_default()).into_owned();
+    (output, captured)
+}
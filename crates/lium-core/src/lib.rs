@@ -23,7 +23,9 @@ pub mod utils;
 // Re-export commonly used types
 pub use errors::{LiumError, Result};
 pub use models::{
-    ApiExecutorResponse, ApiPodResponse, ApiTemplateResponse, ExecutorInfo, PodInfo, TemplateInfo,
+    ApiExecutorResponse, ApiPodResponse, ApiTemplateResponse, ExecutorInfo, FundingWallet,
+    ImageRegistration, PodInfo, PodSpec, RentPodRequest, RentPodResponse, TemplateInfo,
+    TemplateUpdate, UnrentPodResponse, UserProfile,
 };
 pub use optimization::{
     calculate_pareto_frontier, dominates, extract_executor_metrics, extract_metrics,
@@ -65,6 +65,7 @@ pub struct TemplateInfo {
     pub name: String,
     pub docker_image: String,
     pub docker_image_tag: Option<String>,
+    pub docker_image_digest: Option<String>,
     pub status: Option<String>,
     pub description: Option<String>,
 }
@@ -120,12 +121,246 @@ pub struct ApiTemplateResponse {
     pub name: String,
     pub docker_image: String,
     pub docker_image_tag: Option<String>,
+    #[serde(default)]
+    pub docker_image_digest: Option<String>,
     pub status: Option<String>,
     pub description: Option<String>,
     #[serde(flatten)]
     pub extra: HashMap<String, serde_json::Value>, // Catch unknown fields
 }
 
+/// Container options for a pod, sent as part of a [`RentPodRequest`].
+///
+/// Empty/unset fields are omitted from the request body, so the API applies its own defaults.
+///
+/// # Fields
+/// * `env` - Environment variables injected into the container
+/// * `ports` - Port mappings from host port to container port
+/// * `volume_size_gb` - Size of the persistent volume, in GB
+/// * `startup_command` - Command run when the container starts, overriding the template's
+#[derive(Debug, Deserialize, Serialize, Clone, Default, PartialEq)]
+pub struct PodSpec {
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub env: HashMap<String, String>,
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub ports: HashMap<String, String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub volume_size_gb: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub startup_command: Option<String>,
+}
+
+impl PodSpec {
+    /// Returns true if no options are set.
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// Request body for renting an executor (`POST executors/{id}/rent`).
+///
+/// # Examples
+/// ```rust
+/// use lium_core::{parse_env_vars, parse_port_mappings, RentPodRequest};
+///
+/// let request = RentPodRequest::new("trainer", "tmpl-pytorch")
+///     .with_ssh_keys(vec!["ssh-ed25519 AAAA...".to_string()])
+///     .with_env(parse_env_vars("WANDB_MODE=offline").unwrap())
+///     .with_ports(parse_port_mappings("8888:8888").unwrap());
+/// assert_eq!(request.spec.env["WANDB_MODE"], "offline");
+/// ```
+#[derive(Debug, Deserialize, Serialize, Clone, Default, PartialEq)]
+pub struct RentPodRequest {
+    pub pod_name: String,
+    pub template_id: String,
+    #[serde(rename = "user_public_key")] // The API expects the singular name for the key list
+    pub user_public_keys: Vec<String>,
+    #[serde(flatten)]
+    pub spec: PodSpec,
+}
+
+impl RentPodRequest {
+    /// Creates a request with no SSH keys and default pod options.
+    pub fn new(pod_name: impl Into<String>, template_id: impl Into<String>) -> Self {
+        Self {
+            pod_name: pod_name.into(),
+            template_id: template_id.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Sets the SSH public keys allowed to access the pod.
+    pub fn with_ssh_keys(mut self, keys: Vec<String>) -> Self {
+        self.user_public_keys = keys;
+        self
+    }
+
+    /// Sets the container environment variables.
+    pub fn with_env(mut self, env: HashMap<String, String>) -> Self {
+        self.spec.env = env;
+        self
+    }
+
+    /// Sets the host-to-container port mappings.
+    pub fn with_ports(mut self, ports: HashMap<String, String>) -> Self {
+        self.spec.ports = ports;
+        self
+    }
+
+    /// Sets the persistent volume size in GB.
+    pub fn with_volume_size_gb(mut self, size: u32) -> Self {
+        self.spec.volume_size_gb = Some(size);
+        self
+    }
+
+    /// Sets the command run when the container starts.
+    pub fn with_startup_command(mut self, command: impl Into<String>) -> Self {
+        self.spec.startup_command = Some(command.into());
+        self
+    }
+
+    /// Replaces all pod options at once.
+    pub fn with_spec(mut self, spec: PodSpec) -> Self {
+        self.spec = spec;
+        self
+    }
+}
+
+/// Request body for updating a template (`PUT templates/{id}`).
+///
+/// Only the fields that are set are sent, so the API leaves everything else unchanged.
+///
+/// # Examples
+/// ```rust
+/// use lium_core::TemplateUpdate;
+///
+/// let update = TemplateUpdate::new().with_description("PyTorch 2.3 with CUDA 12.1");
+/// assert!(!update.is_empty());
+/// assert_eq!(
+///     serde_json::to_value(&update).unwrap(),
+///     serde_json::json!({"description": "PyTorch 2.3 with CUDA 12.1"})
+/// );
+/// ```
+#[derive(Debug, Deserialize, Serialize, Clone, Default, PartialEq)]
+pub struct TemplateUpdate {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+impl TemplateUpdate {
+    /// Creates an update that changes nothing.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the new template name.
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// Sets the new template description.
+    pub fn with_description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Returns true if the update would not change anything.
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// Response returned when renting an executor (`POST executors/{id}/rent`).
+///
+/// The rent endpoint returns the newly created pod, but depending on how far provisioning
+/// got it may omit the SSH command or port mappings, so everything except the pod ID is
+/// optional. A missing HUID is derived from the ID. Field aliases accept the CLI's own
+/// naming (`name`, `ssh_cmd`, `ports`) as well as the API's.
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+pub struct RentPodResponse {
+    pub id: String,
+    #[serde(default, alias = "name")]
+    pub pod_name: Option<String>,
+    #[serde(default)]
+    pub status: Option<String>,
+    #[serde(default, alias = "ssh_cmd")]
+    pub ssh_connect_cmd: Option<String>,
+    #[serde(default, alias = "ports")]
+    pub ports_mapping: HashMap<String, i32>,
+    #[serde(default)]
+    pub huid: Option<String>,
+    #[serde(flatten)]
+    pub extra: HashMap<String, serde_json::Value>, // Catch unknown fields
+}
+
+impl RentPodResponse {
+    /// Returns the pod's HUID, generating it from the pod ID if the API did not send one.
+    pub fn huid(&self) -> String {
+        self.huid
+            .clone()
+            .unwrap_or_else(|| generate_human_id_simple(&self.id))
+    }
+}
+
+/// Response returned when unrenting an executor (`DELETE executors/{id}/rent`).
+///
+/// The API only confirms the removal, so both fields are optional and anything else it
+/// sends is kept in `extra`.
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+pub struct UnrentPodResponse {
+    #[serde(default, alias = "message")]
+    pub detail: Option<String>,
+    #[serde(default)]
+    pub pod_id: Option<String>,
+    #[serde(flatten)]
+    pub extra: HashMap<String, serde_json::Value>, // Catch unknown fields
+}
+
+/// Profile of the authenticated user (`GET users/me`).
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+pub struct UserProfile {
+    pub id: String,
+    #[serde(default)]
+    pub email: Option<String>,
+    #[serde(default)]
+    pub access_key: Option<String>,
+    pub app_id: String,
+    #[serde(default)]
+    pub balance: Option<f64>,
+    #[serde(flatten)]
+    pub extra: HashMap<String, serde_json::Value>, // Catch unknown fields
+}
+
+/// A funding wallet linked to the user's account (`GET funding/wallets`).
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct FundingWallet {
+    #[serde(alias = "coldkey_ss58")]
+    pub wallet_hash: String,
+    #[serde(default)]
+    pub created_at: Option<String>,
+    #[serde(flatten)]
+    pub extra: HashMap<String, serde_json::Value>, // Catch unknown fields
+}
+
+/// Response returned when registering a Docker image (`POST images`).
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+pub struct ImageRegistration {
+    pub id: String,
+    #[serde(default)]
+    pub image_name: Option<String>,
+    #[serde(default)]
+    pub tag: Option<String>,
+    #[serde(default)]
+    pub digest: Option<String>,
+    #[serde(default)]
+    pub status: Option<String>,
+    #[serde(flatten)]
+    pub extra: HashMap<String, serde_json::Value>, // Catch unknown fields
+}
+
 // Utility functions for conversions
 impl From<ApiExecutorResponse> for ExecutorInfo {
     fn from(api_response: ApiExecutorResponse) -> Self {
@@ -261,6 +496,7 @@ impl From<ApiTemplateResponse> for TemplateInfo {
             name: api_response.name,
             docker_image: api_response.docker_image,
             docker_image_tag: api_response.docker_image_tag,
+            docker_image_digest: api_response.docker_image_digest,
             status: api_response.status,
             description: api_response.description,
         }
@@ -270,3 +506,113 @@ impl From<ApiTemplateResponse> for TemplateInfo {
 // TODO: Add validation functions for each model
 // TODO: Add builder patterns for creating instances
 // TODO: Add display formatters for CLI output
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_rent_pod_response_accepts_api_and_cli_field_names() {
+        let api: RentPodResponse = serde_json::from_value(json!({
+            "id": "pod-1234abcd",
+            "pod_name": "trainer",
+            "status": "PENDING",
+            "ssh_connect_cmd": "ssh root@1.2.3.4 -p 2222",
+            "ports_mapping": {"22": 2222},
+            "executor": {"id": "exec-1"},
+        }))
+        .unwrap();
+        assert_eq!(api.pod_name.as_deref(), Some("trainer"));
+        assert_eq!(api.ports_mapping.get("22"), Some(&2222));
+        assert_eq!(api.huid(), "exec-pod-1234");
+        assert!(api.extra.contains_key("executor"));
+
+        let cli: RentPodResponse = serde_json::from_value(json!({
+            "id": "pod-5678efgh",
+            "huid": "brave-cat-1a2b",
+            "name": "trainer",
+            "ssh_cmd": "ssh root@1.2.3.4",
+        }))
+        .unwrap();
+        assert_eq!(cli.huid(), "brave-cat-1a2b");
+        assert_eq!(cli.ssh_connect_cmd.as_deref(), Some("ssh root@1.2.3.4"));
+    }
+
+    #[test]
+    fn test_rent_pod_request_serialization() {
+        let minimal = RentPodRequest::new("pod", "tmpl");
+        assert_eq!(
+            serde_json::to_value(&minimal).unwrap(),
+            json!({"pod_name": "pod", "template_id": "tmpl", "user_public_key": []})
+        );
+
+        let full = RentPodRequest::new("pod", "tmpl")
+            .with_ssh_keys(vec!["ssh-ed25519 AAAA".to_string()])
+            .with_env(HashMap::from([("DEBUG".to_string(), "1".to_string())]))
+            .with_ports(HashMap::from([("8080".to_string(), "80".to_string())]))
+            .with_volume_size_gb(100)
+            .with_startup_command("python train.py");
+        assert_eq!(
+            serde_json::to_value(&full).unwrap(),
+            json!({
+                "pod_name": "pod",
+                "template_id": "tmpl",
+                "user_public_key": ["ssh-ed25519 AAAA"],
+                "env": {"DEBUG": "1"},
+                "ports": {"8080": "80"},
+                "volume_size_gb": 100,
+                "startup_command": "python train.py",
+            })
+        );
+        assert!(!full.spec.is_empty());
+        assert!(minimal.spec.is_empty());
+    }
+
+    #[test]
+    fn test_typed_responses_reject_wrong_shapes() {
+        assert!(serde_json::from_value::<FundingWallet>(json!({"created_at": "x"})).is_err());
+        assert!(serde_json::from_value::<UserProfile>(json!({"balance": "lots"})).is_err());
+        assert!(serde_json::from_value::<RentPodResponse>(json!({"ports_mapping": []})).is_err());
+
+        let user: UserProfile = serde_json::from_value(json!({
+            "id": "user-1",
+            "app_id": "app-1",
+            "balance": 12.5,
+            "plan": "pro",
+        }))
+        .unwrap();
+        assert_eq!(user.app_id, "app-1");
+        assert_eq!(user.extra["plan"], "pro");
+    }
+
+    #[test]
+    fn test_typed_responses_require_identifying_fields() {
+        let rented = json!({"pod_name": "trainer", "huid": "brave-cat-1a2b"});
+        assert!(serde_json::from_value::<RentPodResponse>(rented).is_err());
+
+        let no_app = json!({"id": "user-1", "email": "dev@lium.local"});
+        assert!(serde_json::from_value::<UserProfile>(no_app).is_err());
+        let no_id = json!({"app_id": "app-1"});
+        assert!(serde_json::from_value::<UserProfile>(no_id).is_err());
+
+        let image = json!({"image_name": "myorg/app", "tag": "v1"});
+        assert!(serde_json::from_value::<ImageRegistration>(image).is_err());
+    }
+
+    #[test]
+    fn test_unrent_pod_response_keeps_unknown_fields() {
+        let response: UnrentPodResponse = serde_json::from_value(json!({
+            "detail": "Pod removed",
+            "pod_id": "pod-1",
+            "refund": 0.25,
+        }))
+        .unwrap();
+        assert_eq!(response.detail.as_deref(), Some("Pod removed"));
+        assert_eq!(response.pod_id.as_deref(), Some("pod-1"));
+        assert_eq!(response.extra["refund"], 0.25);
+
+        let empty: UnrentPodResponse = serde_json::from_value(json!({})).unwrap();
+        assert!(empty.detail.is_none());
+    }
+}
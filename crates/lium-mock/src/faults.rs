@@ -0,0 +1,123 @@
+use rand::Rng;
+use std::time::Duration;
+
+/// A fault injected in place of (or in addition to) the normal response.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Fault {
+    /// Respond with 429 Too Many Requests
+    RateLimited,
+    /// Respond with 503 Service Unavailable
+    Unavailable,
+    /// Respond with 200 and a body that is not valid JSON
+    MalformedJson,
+}
+
+/// Fault injection settings for the mock server.
+///
+/// Each probability is in the range 0.0 - 1.0 and is rolled independently per request,
+/// in the order rate limit, unavailable, malformed. Latency is added to every request.
+///
+/// # Fields
+/// * `rate_limit` - Probability of answering with 429
+/// * `unavailable` - Probability of answering with 503
+/// * `malformed` - Probability of answering with malformed JSON
+/// * `latency` - Delay added before every response
+/// * `retry_after` - `Retry-After` seconds sent with 429/503 responses
+/// * `max_faults` - Stop injecting faults after this many (unlimited if `None`)
+///
+/// # Examples
+/// ```rust
+/// use lium_mock::FaultConfig;
+/// use std::time::Duration;
+///
+/// let faults = FaultConfig::default()
+///     .with_unavailable(0.2)
+///     .with_latency(Duration::from_millis(150));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FaultConfig {
+    pub rate_limit: f64,
+    pub unavailable: f64,
+    pub malformed: f64,
+    pub latency: Duration,
+    pub retry_after: Option<u64>,
+    pub max_faults: Option<u32>,
+}
+
+impl FaultConfig {
+    /// Sets the probability of 429 responses.
+    pub fn with_rate_limit(mut self, probability: f64) -> Self {
+        self.rate_limit = probability.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Sets the probability of 503 responses.
+    pub fn with_unavailable(mut self, probability: f64) -> Self {
+        self.unavailable = probability.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Sets the probability of malformed JSON responses.
+    pub fn with_malformed(mut self, probability: f64) -> Self {
+        self.malformed = probability.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Sets the latency added to every response.
+    pub fn with_latency(mut self, latency: Duration) -> Self {
+        self.latency = latency;
+        self
+    }
+
+    /// Sets the `Retry-After` header value for 429/503 responses.
+    pub fn with_retry_after(mut self, seconds: u64) -> Self {
+        self.retry_after = Some(seconds);
+        self
+    }
+
+    /// Limits the total number of injected faults.
+    pub fn with_max_faults(mut self, max_faults: u32) -> Self {
+        self.max_faults = Some(max_faults);
+        self
+    }
+
+    /// Rolls the dice for a request, given how many faults were injected so far.
+    pub fn pick(&self, injected_so_far: u32) -> Option<Fault> {
+        if self.max_faults.is_some_and(|max| injected_so_far >= max) {
+            return None;
+        }
+
+        let mut rng = rand::thread_rng();
+        if self.rate_limit > 0.0 && rng.gen_bool(self.rate_limit) {
+            Some(Fault::RateLimited)
+        } else if self.unavailable > 0.0 && rng.gen_bool(self.unavailable) {
+            Some(Fault::Unavailable)
+        } else if self.malformed > 0.0 && rng.gen_bool(self.malformed) {
+            Some(Fault::MalformedJson)
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pick_respects_probabilities_and_budget() {
+        assert_eq!(FaultConfig::default().pick(0), None);
+
+        let faults = FaultConfig::default()
+            .with_unavailable(1.0)
+            .with_max_faults(2);
+        assert_eq!(faults.pick(0), Some(Fault::Unavailable));
+        assert_eq!(faults.pick(1), Some(Fault::Unavailable));
+        assert_eq!(faults.pick(2), None);
+
+        let faults = FaultConfig::default()
+            .with_rate_limit(1.0)
+            .with_malformed(1.0);
+        assert_eq!(faults.pick(0), Some(Fault::RateLimited));
+    }
+}
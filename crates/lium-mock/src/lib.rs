@@ -0,0 +1,41 @@
+//! # Lium Mock
+//!
+//! An in-memory mock of the Celium Compute API for offline development, demos and
+//! end-to-end tests of the CLI and SDK.
+//!
+//! ## Features
+//!
+//! - Serves the endpoints used by `LiumApiClient` (executors, pods, rent/unrent, templates,
+//!   images, users, funding wallets, health, exec)
+//! - Stateful: renting an executor creates a pod and marks the executor as unavailable
+//! - Fault injection: configurable 429/503 responses, malformed JSON and added latency
+//!
+//! ## Core Components
+//!
+//! - `state`: In-memory resources stored in the real API's JSON shape
+//! - `faults`: Fault injection settings
+//! - `server`: Hyper-based HTTP server routing requests to the state
+//!
+//! ## Usage
+//!
+//! ```rust
+//! use lium_api::LiumApiClient;
+//! use lium_mock::{FaultConfig, MockServer, MockState};
+//! # #[tokio::main]
+//! # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//!
+//! let running = MockServer::new(MockState::seeded(), FaultConfig::default())
+//!     .start("127.0.0.1:0".parse()?)?;
+//! let client = LiumApiClient::with_base_url("any-key".to_string(), running.base_url());
+//! # assert!(!client.get_executors().await?.is_empty());
+//! # Ok(())
+//! # }
+//! ```
+
+pub mod faults;
+pub mod server;
+pub mod state;
+
+pub use faults::*;
+pub use server::*;
+pub use state::*;
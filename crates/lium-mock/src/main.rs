@@ -0,0 +1,107 @@
+//! # Lium Mock Server
+//!
+//! Runs the mock Celium API locally. Point the CLI at it by setting `base_url` under `[api]`
+//! in `~/.lium/config.toml` to try commands without touching real infrastructure.
+
+use clap::Parser;
+use lium_mock::{FaultConfig, MockServer, MockState};
+use std::net::SocketAddr;
+use std::process;
+use std::time::Duration;
+
+/// Mock Celium Compute API server for local development
+#[derive(Parser, Debug)]
+#[command(name = "lium-mock", version)]
+struct Args {
+    /// Address to bind to
+    #[arg(long, default_value = "127.0.0.1")]
+    host: String,
+
+    /// Port to listen on (0 picks a free port)
+    #[arg(short, long, default_value_t = 8000)]
+    port: u16,
+
+    /// Start without seeded executors and templates
+    #[arg(long)]
+    empty: bool,
+
+    /// Require this API key on every request
+    #[arg(long)]
+    api_key: Option<String>,
+
+    /// Probability (0.0-1.0) of answering with 429 Too Many Requests
+    #[arg(long, default_value_t = 0.0)]
+    rate_limit: f64,
+
+    /// Probability (0.0-1.0) of answering with 503 Service Unavailable
+    #[arg(long, default_value_t = 0.0)]
+    unavailable: f64,
+
+    /// Probability (0.0-1.0) of answering with malformed JSON
+    #[arg(long, default_value_t = 0.0)]
+    malformed: f64,
+
+    /// Latency added to every response, in milliseconds
+    #[arg(long, default_value_t = 0)]
+    latency_ms: u64,
+
+    /// Retry-After seconds sent with 429/503 responses
+    #[arg(long)]
+    retry_after: Option<u64>,
+
+    /// Stop injecting faults after this many
+    #[arg(long)]
+    max_faults: Option<u32>,
+}
+
+#[tokio::main]
+async fn main() {
+    let args = Args::parse();
+
+    let addr: SocketAddr = match format!("{}:{}", args.host, args.port).parse() {
+        Ok(addr) => addr,
+        Err(e) => {
+            eprintln!("Error: invalid address {}:{}: {}", args.host, args.port, e);
+            process::exit(1);
+        }
+    };
+
+    let mut faults = FaultConfig::default()
+        .with_rate_limit(args.rate_limit)
+        .with_unavailable(args.unavailable)
+        .with_malformed(args.malformed)
+        .with_latency(Duration::from_millis(args.latency_ms));
+    if let Some(seconds) = args.retry_after {
+        faults = faults.with_retry_after(seconds);
+    }
+    if let Some(max_faults) = args.max_faults {
+        faults = faults.with_max_faults(max_faults);
+    }
+
+    let state = if args.empty {
+        MockState::empty()
+    } else {
+        MockState::seeded()
+    };
+    let mut server = MockServer::new(state, faults);
+    if let Some(api_key) = args.api_key {
+        server = server.with_api_key(api_key);
+    }
+
+    let running = match server.start(addr) {
+        Ok(running) => running,
+        Err(e) => {
+            eprintln!("Error: failed to bind {}: {}", addr, e);
+            process::exit(1);
+        }
+    };
+
+    println!("Mock Celium API listening on {}", running.base_url());
+    println!("Set api.base_url = \"{}\" to use it", running.base_url());
+    println!("Press Ctrl+C to stop");
+
+    if let Err(e) = tokio::signal::ctrl_c().await {
+        eprintln!("Error: failed to listen for Ctrl+C: {}", e);
+    }
+    running.shutdown().await;
+}
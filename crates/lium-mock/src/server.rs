@@ -0,0 +1,396 @@
+use crate::faults::{Fault, FaultConfig};
+use crate::state::{MockState, StateError};
+use hyper::header::{HeaderValue, CONTENT_TYPE, RETRY_AFTER};
+use hyper::service::{make_service_fn, service_fn};
+use hyper::{Body, Method, Request, Response, Server, StatusCode};
+use log::{debug, info};
+use serde_json::{json, Value};
+use std::convert::Infallible;
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard};
+use tokio::sync::oneshot;
+use tokio::task::JoinHandle;
+
+/// Mock Celium API server.
+///
+/// Serves the endpoints used by `LiumApiClient` from an in-memory [`MockState`], with
+/// optional fault injection. Paths are accepted both at the root (`/executors`) and under
+/// `/api` (`/api/executors`), so `api.base_url` can point at either form.
+///
+/// # Examples
+/// ```rust
+/// use lium_mock::{FaultConfig, MockServer, MockState};
+/// # #[tokio::main]
+/// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+///
+/// let server = MockServer::new(MockState::seeded(), FaultConfig::default());
+/// let running = server.start("127.0.0.1:0".parse()?)?;
+/// println!("Mock API listening on {}", running.base_url());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct MockServer {
+    state: Arc<Mutex<MockState>>,
+    faults: FaultConfig,
+    api_key: Option<String>,
+    faults_injected: Arc<AtomicU32>,
+}
+
+/// Handle to a mock server running in the background.
+///
+/// The server shuts down when [`RunningMockServer::shutdown`] is called or the handle is dropped.
+#[derive(Debug)]
+pub struct RunningMockServer {
+    addr: SocketAddr,
+    state: Arc<Mutex<MockState>>,
+    shutdown: Option<oneshot::Sender<()>>,
+    task: Option<JoinHandle<()>>,
+}
+
+impl RunningMockServer {
+    /// Returns the address the server is listening on.
+    pub fn addr(&self) -> SocketAddr {
+        self.addr
+    }
+
+    /// Returns the base URL to configure as `api.base_url`.
+    pub fn base_url(&self) -> String {
+        format!("http://{}", self.addr)
+    }
+
+    /// Returns a snapshot of the current server state.
+    pub fn state(&self) -> MockState {
+        lock(&self.state).clone()
+    }
+
+    /// Stops the server and waits for it to finish.
+    pub async fn shutdown(mut self) {
+        if let Some(tx) = self.shutdown.take() {
+            let _ = tx.send(());
+        }
+        if let Some(task) = self.task.take() {
+            let _ = task.await;
+        }
+    }
+}
+
+impl Drop for RunningMockServer {
+    fn drop(&mut self) {
+        if let Some(tx) = self.shutdown.take() {
+            let _ = tx.send(());
+        }
+    }
+}
+
+impl MockServer {
+    /// Creates a server with the given initial state and fault settings.
+    pub fn new(state: MockState, faults: FaultConfig) -> Self {
+        Self {
+            state: Arc::new(Mutex::new(state)),
+            faults,
+            api_key: None,
+            faults_injected: Arc::new(AtomicU32::new(0)),
+        }
+    }
+
+    /// Requires requests to carry this API key (`X-API-Key` or `Authorization: Bearer`).
+    pub fn with_api_key(mut self, api_key: impl Into<String>) -> Self {
+        self.api_key = Some(api_key.into());
+        self
+    }
+
+    /// Binds to `addr` and serves requests on a background task.
+    ///
+    /// Must be called from within a Tokio runtime. Use port 0 to pick a free port.
+    pub fn start(self, addr: SocketAddr) -> Result<RunningMockServer, hyper::Error> {
+        let state = self.state.clone();
+        let server = self;
+
+        let make_service = make_service_fn(move |_| {
+            let server = server.clone();
+            async move {
+                Ok::<_, Infallible>(service_fn(move |request| {
+                    let server = server.clone();
+                    async move { Ok::<_, Infallible>(server.handle(request).await) }
+                }))
+            }
+        });
+
+        let (tx, rx) = oneshot::channel::<()>();
+        let http = Server::try_bind(&addr)?.serve(make_service);
+        let addr = http.local_addr();
+        let task = tokio::spawn(async move {
+            let graceful = http.with_graceful_shutdown(async {
+                let _ = rx.await;
+            });
+            if let Err(e) = graceful.await {
+                log::error!("Mock server error: {}", e);
+            }
+        });
+
+        info!("Mock Celium API listening on http://{}", addr);
+
+        Ok(RunningMockServer {
+            addr,
+            state,
+            shutdown: Some(tx),
+            task: Some(task),
+        })
+    }
+
+    /// Handles a single request.
+    pub async fn handle(&self, request: Request<Body>) -> Response<Body> {
+        let method = request.method().clone();
+        let path = request.uri().path().to_string();
+        debug!("{} {}", method, path);
+
+        if !self.faults.latency.is_zero() {
+            tokio::time::sleep(self.faults.latency).await;
+        }
+
+        let segments: Vec<String> = path
+            .trim_matches('/')
+            .split('/')
+            .filter(|s| !s.is_empty())
+            .map(|s| s.to_string())
+            .collect();
+        let segments: Vec<&str> = match segments.first().map(|s| s.as_str()) {
+            Some("api") => segments[1..].iter().map(|s| s.as_str()).collect(),
+            _ => segments.iter().map(|s| s.as_str()).collect(),
+        };
+
+        if segments != ["health"] && !self.is_authorized(&request) {
+            return error_response(StatusCode::UNAUTHORIZED, "Invalid or missing API key");
+        }
+
+        let injected = self.faults_injected.load(Ordering::SeqCst);
+        if let Some(fault) = self.faults.pick(injected) {
+            self.faults_injected.fetch_add(1, Ordering::SeqCst);
+            debug!("Injecting fault {:?} for {} {}", fault, method, path);
+            return self.fault_response(fault);
+        }
+
+        let body = match hyper::body::to_bytes(request.into_body()).await {
+            Ok(bytes) if bytes.is_empty() => Value::Null,
+            Ok(bytes) => match serde_json::from_slice(&bytes) {
+                Ok(value) => value,
+                Err(e) => {
+                    return error_response(
+                        StatusCode::UNPROCESSABLE_ENTITY,
+                        &format!("Invalid JSON body: {}", e),
+                    )
+                }
+            },
+            Err(e) => return error_response(StatusCode::BAD_REQUEST, &e.to_string()),
+        };
+
+        let result = self.route(&method, &segments, &body);
+        match result {
+            Ok(value) => json_response(StatusCode::OK, &value),
+            Err(StateError::NotFound(what)) => {
+                error_response(StatusCode::NOT_FOUND, &format!("{} not found", what))
+            }
+            Err(StateError::Conflict(message)) => error_response(StatusCode::CONFLICT, &message),
+            Err(StateError::BadRequest(message)) => {
+                error_response(StatusCode::UNPROCESSABLE_ENTITY, &message)
+            }
+        }
+    }
+
+    fn route(&self, method: &Method, segments: &[&str], body: &Value) -> Result<Value, StateError> {
+        let mut state = lock(&self.state);
+
+        match (method, segments) {
+            (&Method::GET, ["health"]) => Ok(json!({ "status": "ok" })),
+            (&Method::GET, ["executors"]) => Ok(Value::Array(state.executors.clone())),
+            (&Method::GET, ["pods"]) => Ok(Value::Array(state.pods.clone())),
+            (&Method::POST, ["executors", id, "rent"]) => state.rent(id, body),
+            (&Method::DELETE, ["executors", id, "rent"]) => state.unrent(id),
+            (&Method::GET, ["templates"]) => Ok(Value::Array(state.templates.clone())),
+            (&Method::GET, ["templates", id]) => state.template(id),
+            (&Method::PUT, ["templates", id]) => state.update_template(id, body),
+            (&Method::DELETE, ["templates", id]) => state.delete_template(id),
+            (&Method::GET, ["images"]) => Ok(Value::Array(state.images.clone())),
+            (&Method::POST, ["images"]) => state.register_image(body),
+            (&Method::GET, ["users", "me"]) => Ok(state.user.clone()),
+            (&Method::GET, ["funding", "wallets"]) => Ok(Value::Array(state.wallets.clone())),
+            (&Method::POST, ["funding", "wallets"]) => state.add_wallet(body),
+            (&Method::POST, ["pods", "exec"]) => state.exec(body),
+            _ => Err(StateError::NotFound(format!(
+                "{} /{}",
+                method,
+                segments.join("/")
+            ))),
+        }
+    }
+
+    fn is_authorized(&self, request: &Request<Body>) -> bool {
+        let Some(expected) = &self.api_key else {
+            return true;
+        };
+
+        let header = |name: &str| {
+            request
+                .headers()
+                .get(name)
+                .and_then(|v| v.to_str().ok())
+                .map(|v| v.to_string())
+        };
+
+        header("x-api-key").as_deref() == Some(expected.as_str())
+            || header("authorization").as_deref() == Some(&format!("Bearer {}", expected))
+    }
+
+    fn fault_response(&self, fault: Fault) -> Response<Body> {
+        let mut response = match fault {
+            Fault::RateLimited => error_response(StatusCode::TOO_MANY_REQUESTS, "Rate limited"),
+            Fault::Unavailable => {
+                error_response(StatusCode::SERVICE_UNAVAILABLE, "Service unavailable")
+            }
+            Fault::MalformedJson => {
+                let mut response = Response::new(Body::from(r#"{"detail": "truncated"#));
+                response
+                    .headers_mut()
+                    .insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+                return response;
+            }
+        };
+
+        if let Some(seconds) = self.faults.retry_after {
+            if let Ok(value) = HeaderValue::from_str(&seconds.to_string()) {
+                response.headers_mut().insert(RETRY_AFTER, value);
+            }
+        }
+        response
+    }
+}
+
+fn lock(state: &Mutex<MockState>) -> MutexGuard<'_, MockState> {
+    state.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+fn json_response(status: StatusCode, value: &Value) -> Response<Body> {
+    let mut response = Response::new(Body::from(value.to_string()));
+    *response.status_mut() = status;
+    response
+        .headers_mut()
+        .insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+    response
+}
+
+fn error_response(status: StatusCode, detail: &str) -> Response<Body> {
+    json_response(status, &json!({ "detail": detail }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lium_api::{LiumApiClient, RetryPolicy};
+    use lium_core::RentPodRequest;
+    use std::collections::HashMap;
+    use std::time::Duration;
+
+    fn start(server: MockServer) -> (RunningMockServer, LiumApiClient) {
+        let running = server.start("127.0.0.1:0".parse().unwrap()).unwrap();
+        let client = LiumApiClient::with_base_url("mock-key".to_string(), running.base_url())
+            .with_retry_policy(
+                RetryPolicy::default()
+                    .with_base_delay(Duration::from_millis(1))
+                    .with_jitter(0.0),
+            );
+        (running, client)
+    }
+
+    #[tokio::test]
+    async fn test_rent_shows_pod_and_marks_executor_unavailable() {
+        let (running, client) = start(MockServer::new(MockState::seeded(), FaultConfig::default()));
+
+        let executors = client.get_executors().await.unwrap();
+        assert!(executors.iter().all(|e| e.available));
+        let executor_id = executors[0].id.clone();
+
+        client
+            .rent_pod(
+                &executor_id,
+                &RentPodRequest::new("demo", "tmpl-ubuntu")
+                    .with_env(HashMap::from([("DEBUG".to_string(), "1".to_string())])),
+            )
+            .await
+            .unwrap();
+
+        let pods = client.get_pods().await.unwrap();
+        assert_eq!(pods.len(), 1);
+        assert_eq!(pods[0].name, "demo");
+        assert!(pods[0].ssh_cmd.is_some());
+        assert_eq!(running.state().pods[0]["env"]["DEBUG"], "1");
+
+        let executors = client.get_executors().await.unwrap();
+        let rented = executors.iter().find(|e| e.id == executor_id).unwrap();
+        assert!(!rented.available);
+
+        client.unrent_pod(&executor_id).await.unwrap();
+        assert!(client.get_pods().await.unwrap().is_empty());
+
+        running.shutdown().await;
+    }
+
+    #[tokio::test]
+    async fn test_serves_all_client_endpoints() {
+        let (running, client) = start(MockServer::new(MockState::seeded(), FaultConfig::default()));
+
+        assert!(client.test_connection().await.unwrap());
+        assert!(!client.get_templates().await.unwrap().is_empty());
+        assert_eq!(client.get_app_id().await.unwrap(), "mock-app-id");
+        let image = client
+            .post_image("myorg/app", "sha256:abc", "v1")
+            .await
+            .unwrap();
+        let template_id = image.id;
+        let template = client.get_template(&template_id).await.unwrap();
+        assert_eq!(template.docker_image_digest.as_deref(), Some("sha256:abc"));
+        let update = lium_core::TemplateUpdate::new().with_description("Nightly");
+        let updated = client.update_template(&template_id, &update).await.unwrap();
+        assert_eq!(updated.description.as_deref(), Some("Nightly"));
+        client.delete_template(&template_id).await.unwrap();
+        assert!(client.get_template(&template_id).await.is_err());
+        client
+            .add_wallet("5Grw", "mock-access-key", "0x00", "mock-app-id")
+            .await
+            .unwrap();
+        let wallets = client.get_funding_wallets().await.unwrap();
+        assert_eq!(wallets.len(), 1);
+        assert_eq!(wallets[0].wallet_hash, "5Grw");
+
+        running.shutdown().await;
+    }
+
+    #[tokio::test]
+    async fn test_injected_faults_are_retried_by_client() {
+        let faults = FaultConfig::default()
+            .with_unavailable(1.0)
+            .with_retry_after(0)
+            .with_max_faults(2);
+        let (running, client) = start(MockServer::new(MockState::seeded(), faults));
+
+        assert_eq!(client.get_executors().await.unwrap().len(), 5);
+
+        running.shutdown().await;
+    }
+
+    #[tokio::test]
+    async fn test_malformed_json_and_auth() {
+        let faults = FaultConfig::default().with_malformed(1.0);
+        let (running, client) = start(MockServer::new(MockState::seeded(), faults));
+        assert!(client.get_pods().await.is_err());
+        running.shutdown().await;
+
+        let server =
+            MockServer::new(MockState::seeded(), FaultConfig::default()).with_api_key("secret");
+        let (running, client) = start(server);
+        assert!(client.get_pods().await.is_err());
+        assert!(client.test_connection().await.unwrap());
+        running.shutdown().await;
+    }
+}
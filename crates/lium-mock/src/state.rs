@@ -0,0 +1,370 @@
+use chrono::Utc;
+use serde_json::{json, Value};
+use thiserror::Error;
+
+/// Errors returned by state operations, mapped to HTTP statuses by the server.
+#[derive(Error, Debug, PartialEq)]
+pub enum StateError {
+    #[error("{0} not found")]
+    NotFound(String),
+
+    #[error("{0}")]
+    Conflict(String),
+
+    #[error("{0}")]
+    BadRequest(String),
+}
+
+/// In-memory state of the mock Celium API.
+///
+/// Resources are stored as raw JSON in the same shape the real API returns, so the
+/// client's `ApiExecutorResponse`/`ApiPodResponse`/`ApiTemplateResponse` parsing is
+/// exercised exactly as it would be against production.
+///
+/// Renting an executor creates a pod that shows up in `pods` and marks the executor
+/// as `active` (which the client reports as `available = false`); unrenting reverses both.
+#[derive(Debug, Clone)]
+pub struct MockState {
+    pub executors: Vec<Value>,
+    pub pods: Vec<Value>,
+    pub templates: Vec<Value>,
+    pub images: Vec<Value>,
+    pub wallets: Vec<Value>,
+    pub user: Value,
+    next_ssh_port: u16,
+}
+
+impl Default for MockState {
+    fn default() -> Self {
+        Self::empty()
+    }
+}
+
+impl MockState {
+    /// Creates state with no resources and a default user.
+    pub fn empty() -> Self {
+        Self {
+            executors: Vec::new(),
+            pods: Vec::new(),
+            templates: Vec::new(),
+            images: Vec::new(),
+            wallets: Vec::new(),
+            user: json!({
+                "id": "user-mock",
+                "email": "dev@lium.local",
+                "access_key": "mock-access-key",
+                "app_id": "mock-app-id",
+                "balance": 100.0,
+            }),
+            next_ssh_port: 22001,
+        }
+    }
+
+    /// Creates state seeded with a handful of executors and templates for demos.
+    pub fn seeded() -> Self {
+        let mut state = Self::empty();
+
+        let machines = [
+            ("8x NVIDIA H100 80GB HBM3", 8, 19.92, "US"),
+            ("4x NVIDIA A100-SXM4-80GB", 4, 5.6, "DE"),
+            ("2x NVIDIA GeForce RTX 4090", 2, 0.98, "CA"),
+            ("1x NVIDIA GeForce RTX 4090", 1, 0.49, "US"),
+            ("1x NVIDIA RTX A6000", 1, 0.45, "FR"),
+        ];
+        for (i, (machine_name, gpu_count, price, country)) in machines.iter().enumerate() {
+            state.add_executor(json!({
+                "id": format!("00000000-0000-4000-8000-00000000000{}", i + 1),
+                "machine_name": machine_name,
+                "gpu_count": gpu_count,
+                "price_per_hour": price,
+                "location": { "country": country },
+                "specs": {
+                    "gpu": { "count": gpu_count, "details": [{ "name": machine_name }] },
+                    "ram": { "total": 256 * 1024 * 1024 },
+                },
+                "active": false,
+            }));
+        }
+
+        state.templates.push(json!({
+            "id": "tmpl-pytorch",
+            "name": "PyTorch 2.1 (CUDA 12.1)",
+            "docker_image": "pytorch/pytorch",
+            "docker_image_tag": "2.1.0-cuda12.1-cudnn8-runtime",
+            "status": "VERIFY_SUCCESS",
+            "description": "PyTorch with CUDA and SSH",
+        }));
+        state.templates.push(json!({
+            "id": "tmpl-ubuntu",
+            "name": "Ubuntu 22.04",
+            "docker_image": "ubuntu",
+            "docker_image_tag": "22.04",
+            "status": "VERIFY_SUCCESS",
+            "description": "Plain Ubuntu with SSH",
+        }));
+
+        state
+    }
+
+    /// Adds an executor (raw API JSON).
+    pub fn add_executor(&mut self, executor: Value) {
+        self.executors.push(executor);
+    }
+
+    /// Rents an executor, creating a pod and marking the executor as taken.
+    ///
+    /// # Arguments
+    /// * `executor_id` - ID of the executor to rent
+    /// * `body` - The rent request body (`pod_name`, `template_id`, `user_public_key` and
+    ///   optional pod options such as `env` and `ports`)
+    ///
+    /// # Returns
+    /// * `Ok(Value)` - The created pod
+    /// * `Err(StateError)` - If the executor is unknown, already rented, or the body is invalid
+    pub fn rent(&mut self, executor_id: &str, body: &Value) -> Result<Value, StateError> {
+        let pod_name = body
+            .get("pod_name")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| StateError::BadRequest("pod_name is required".to_string()))?
+            .to_string();
+        let template_id = body
+            .get("template_id")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| StateError::BadRequest("template_id is required".to_string()))?;
+
+        let template = self
+            .templates
+            .iter()
+            .find(|t| t["id"] == template_id)
+            .cloned()
+            .unwrap_or_else(|| json!({ "id": template_id }));
+
+        let executor = self
+            .executors
+            .iter_mut()
+            .find(|e| e["id"] == executor_id)
+            .ok_or_else(|| StateError::NotFound(format!("Executor {}", executor_id)))?;
+
+        if executor["active"].as_bool().unwrap_or(false) {
+            return Err(StateError::Conflict(format!(
+                "Executor {} is already rented",
+                executor_id
+            )));
+        }
+        executor["active"] = json!(true);
+        let executor = executor.clone();
+
+        let ssh_port = self.next_ssh_port;
+        self.next_ssh_port = self.next_ssh_port.wrapping_add(1).max(22001);
+
+        let now = Utc::now().to_rfc3339();
+        let pod = json!({
+            "id": uuid::Uuid::new_v4().to_string(),
+            "pod_name": pod_name,
+            "status": "RUNNING",
+            "ssh_connect_cmd": format!("ssh root@127.0.0.1 -p {}", ssh_port),
+            "ports_mapping": { "22": ssh_port },
+            "created_at": now,
+            "updated_at": now,
+            "executor": executor,
+            "template": template,
+            "user_public_key": body.get("user_public_key").cloned().unwrap_or(json!([])),
+            "env": body.get("env").cloned().unwrap_or(json!({})),
+        });
+        self.pods.push(pod.clone());
+
+        Ok(pod)
+    }
+
+    /// Unrents an executor, removing its pod and making the executor available again.
+    pub fn unrent(&mut self, executor_id: &str) -> Result<Value, StateError> {
+        let position = self
+            .pods
+            .iter()
+            .position(|p| p["executor"]["id"] == executor_id)
+            .ok_or_else(|| StateError::NotFound(format!("Rental for executor {}", executor_id)))?;
+        let pod = self.pods.remove(position);
+
+        if let Some(executor) = self.executors.iter_mut().find(|e| e["id"] == executor_id) {
+            executor["active"] = json!(false);
+        }
+
+        Ok(json!({ "detail": "Pod removed", "pod_id": pod["id"] }))
+    }
+
+    /// Registers a Docker image and a matching template.
+    pub fn register_image(&mut self, body: &Value) -> Result<Value, StateError> {
+        let image_name = body
+            .get("image_name")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| StateError::BadRequest("image_name is required".to_string()))?;
+        let tag = body.get("tag").and_then(|v| v.as_str()).unwrap_or("latest");
+        let digest = body.get("digest").and_then(|v| v.as_str()).unwrap_or("");
+
+        let id = uuid::Uuid::new_v4().to_string();
+        let image = json!({
+            "id": id,
+            "image_name": image_name,
+            "tag": tag,
+            "digest": digest,
+            "status": "VERIFY_PENDING",
+        });
+        self.images.push(image.clone());
+        self.templates.push(json!({
+            "id": id,
+            "name": format!("{}:{}", image_name, tag),
+            "docker_image": image_name,
+            "docker_image_tag": tag,
+            "docker_image_digest": digest,
+            "status": "VERIFY_PENDING",
+            "description": null,
+        }));
+
+        Ok(image)
+    }
+
+    /// Looks up a template by ID.
+    pub fn template(&self, template_id: &str) -> Result<Value, StateError> {
+        self.templates
+            .iter()
+            .find(|t| t["id"] == template_id)
+            .cloned()
+            .ok_or_else(|| StateError::NotFound(format!("Template {}", template_id)))
+    }
+
+    /// Updates a template's name and/or description.
+    pub fn update_template(
+        &mut self,
+        template_id: &str,
+        body: &Value,
+    ) -> Result<Value, StateError> {
+        let template = self
+            .templates
+            .iter_mut()
+            .find(|t| t["id"] == template_id)
+            .ok_or_else(|| StateError::NotFound(format!("Template {}", template_id)))?;
+
+        for field in ["name", "description"] {
+            if let Some(value) = body.get(field).filter(|v| !v.is_null()) {
+                if !value.is_string() {
+                    return Err(StateError::BadRequest(format!(
+                        "{} must be a string",
+                        field
+                    )));
+                }
+                template[field] = value.clone();
+            }
+        }
+
+        Ok(template.clone())
+    }
+
+    /// Deletes a template.
+    pub fn delete_template(&mut self, template_id: &str) -> Result<Value, StateError> {
+        let position = self
+            .templates
+            .iter()
+            .position(|t| t["id"] == template_id)
+            .ok_or_else(|| StateError::NotFound(format!("Template {}", template_id)))?;
+        self.templates.remove(position);
+
+        Ok(json!({ "detail": "Template deleted", "template_id": template_id }))
+    }
+
+    /// Adds a funding wallet.
+    pub fn add_wallet(&mut self, body: &Value) -> Result<Value, StateError> {
+        let coldkey = body
+            .get("coldkey_ss58")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| StateError::BadRequest("coldkey_ss58 is required".to_string()))?;
+
+        if self.wallets.iter().any(|w| w["wallet_hash"] == coldkey) {
+            return Err(StateError::Conflict(format!(
+                "Wallet {} already added",
+                coldkey
+            )));
+        }
+
+        let wallet = json!({
+            "wallet_hash": coldkey,
+            "created_at": Utc::now().to_rfc3339(),
+        });
+        self.wallets.push(wallet.clone());
+        Ok(wallet)
+    }
+
+    /// Simulates command execution in a pod.
+    pub fn exec(&self, body: &Value) -> Result<Value, StateError> {
+        let pod_id = body
+            .get("pod_id")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| StateError::BadRequest("pod_id is required".to_string()))?;
+        let command = body.get("command").and_then(|v| v.as_str()).unwrap_or("");
+
+        if !self.pods.iter().any(|p| p["id"] == pod_id) {
+            return Err(StateError::NotFound(format!("Pod {}", pod_id)));
+        }
+
+        Ok(json!({ "output": format!("[mock] {}", command), "exit_code": 0 }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rent_and_unrent_round_trip() {
+        let mut state = MockState::seeded();
+        let executor_id = state.executors[0]["id"].as_str().unwrap().to_string();
+
+        let pod = state
+            .rent(
+                &executor_id,
+                &json!({"pod_name": "demo", "template_id": "tmpl-ubuntu"}),
+            )
+            .unwrap();
+        assert_eq!(pod["pod_name"], "demo");
+        assert_eq!(state.pods.len(), 1);
+        assert_eq!(state.executors[0]["active"], true);
+
+        let again = state.rent(
+            &executor_id,
+            &json!({"pod_name": "demo2", "template_id": "tmpl-ubuntu"}),
+        );
+        assert!(matches!(again, Err(StateError::Conflict(_))));
+
+        state.unrent(&executor_id).unwrap();
+        assert!(state.pods.is_empty());
+        assert_eq!(state.executors[0]["active"], false);
+        assert!(matches!(
+            state.unrent(&executor_id),
+            Err(StateError::NotFound(_))
+        ));
+    }
+
+    #[test]
+    fn test_template_update_and_delete() {
+        let mut state = MockState::seeded();
+        let id = state.templates[0]["id"].as_str().unwrap().to_string();
+
+        let updated = state
+            .update_template(&id, &json!({"description": "Updated"}))
+            .unwrap();
+        assert_eq!(updated["description"], "Updated");
+        assert_eq!(state.template(&id).unwrap()["description"], "Updated");
+
+        state.delete_template(&id).unwrap();
+        assert!(matches!(state.template(&id), Err(StateError::NotFound(_))));
+    }
+
+    #[test]
+    fn test_rent_requires_pod_name() {
+        let mut state = MockState::seeded();
+        let result = state.rent(
+            "00000000-0000-4000-8000-000000000001",
+            &json!({"template_id": "x"}),
+        );
+        assert!(matches!(result, Err(StateError::BadRequest(_))));
+    }
+}
@@ -41,6 +41,15 @@ pub enum SshError {
 
     #[error("SSH key error: {0}")]
     KeyError(String),
+
+    #[error("SSH host key verification failed: {0}")]
+    HostKeyMismatch(String),
+
+    #[error("SSH command timed out: {0}")]
+    Timeout(String),
+
+    #[error("SSH command cancelled: {0}")]
+    Cancelled(String),
 }
 
 #[derive(Error, Debug)]
@@ -0,0 +1,154 @@
+//! Locking and atomic writes for the files lium keeps under `~/.lium`.
+//!
+//! Several lium processes may run at once (for example `lium ps` in one terminal
+//! and `lium exec` in another). Every read-modify-write of a shared file holds an
+//! exclusive advisory lock on a `.lock` file next to it, and every rewrite goes
+//! through a uniquely named temporary file that is renamed into place, so readers
+//! never see a half-written file and concurrent writers never lose each other's changes.
+
+use std::fs::{self, File, OpenOptions, TryLockError};
+use std::io::{self, ErrorKind, Write};
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// How long to keep retrying before giving up on a lock held by another process.
+pub const LOCK_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// First delay between lock attempts; doubled after each attempt up to `MAX_RETRY_DELAY`.
+const INITIAL_RETRY_DELAY: Duration = Duration::from_millis(5);
+const MAX_RETRY_DELAY: Duration = Duration::from_millis(200);
+
+/// An exclusive advisory lock on `<path>.lock`, released when dropped
+#[derive(Debug)]
+pub struct FileLock {
+    _file: File,
+}
+
+impl FileLock {
+    /// Locks `path` for writing, retrying with backoff until [`LOCK_TIMEOUT`].
+    ///
+    /// The lock is taken on a sidecar `<path>.lock` file rather than on `path` itself,
+    /// because writes replace `path` with a renamed temporary file. Locks taken by
+    /// different threads of one process exclude each other too.
+    ///
+    /// # Arguments
+    /// * `path` - The file about to be read and rewritten
+    ///
+    /// # Returns
+    /// * `io::Result<FileLock>` - The held lock
+    ///
+    /// # Errors
+    /// * `ErrorKind::TimedOut` - If another process held the lock for the whole timeout
+    /// * Any other error - If the lock file or its directory cannot be created
+    ///
+    /// # Examples
+    /// ```rust,no_run
+    /// use lium_utils::files::{write_atomic, FileLock};
+    /// use std::path::Path;
+    ///
+    /// let path = Path::new("/home/me/.lium/known_hosts");
+    /// let _lock = FileLock::acquire(path)?;
+    /// let contents = std::fs::read_to_string(path)?;
+    /// write_atomic(path, contents.to_uppercase().as_bytes())?;
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn acquire(path: &Path) -> io::Result<Self> {
+        Self::acquire_with_timeout(path, LOCK_TIMEOUT)
+    }
+
+    /// Like [`FileLock::acquire`], giving up after `timeout`
+    pub fn acquire_with_timeout(path: &Path, timeout: Duration) -> io::Result<Self> {
+        let lock_path = lock_path(path);
+        if let Some(parent) = lock_path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            fs::create_dir_all(parent)?;
+        }
+        let file = OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(&lock_path)?;
+
+        let deadline = Instant::now() + timeout;
+        let mut delay = INITIAL_RETRY_DELAY;
+        loop {
+            match file.try_lock() {
+                Ok(()) => return Ok(FileLock { _file: file }),
+                Err(TryLockError::WouldBlock) if Instant::now() < deadline => {
+                    thread::sleep(delay);
+                    delay = (delay * 2).min(MAX_RETRY_DELAY);
+                }
+                Err(TryLockError::WouldBlock) => {
+                    return Err(io::Error::new(
+                        ErrorKind::TimedOut,
+                        format!("{} is locked by another process", lock_path.display()),
+                    ))
+                }
+                Err(TryLockError::Error(e)) => return Err(e),
+            }
+        }
+    }
+}
+
+/// Path of the sidecar lock file for `path`
+pub fn lock_path(path: &Path) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(".lock");
+    PathBuf::from(name)
+}
+
+/// Writes `content` to `path` atomically.
+///
+/// The content goes to a uniquely named temporary file in the same directory, which
+/// is synced and then renamed over `path`. Missing parent directories are created.
+///
+/// # Errors
+/// * Any I/O error from creating the directory, writing or renaming
+pub fn write_atomic(path: &Path, content: &[u8]) -> io::Result<()> {
+    let dir = match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    };
+    fs::create_dir_all(dir)?;
+
+    let file_name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let mut temp = tempfile::Builder::new()
+        .prefix(&format!(".{}.", file_name))
+        .suffix(".tmp")
+        .tempfile_in(dir)?;
+    temp.write_all(content)?;
+    // Not critical: the rename below is what makes the write atomic
+    let _ = temp.as_file().sync_all();
+    temp.persist(path).map_err(|e| e.error)?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lock_excludes_other_holders_until_dropped() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("known_hosts");
+
+        let held = FileLock::acquire(&path).unwrap();
+        let err = FileLock::acquire_with_timeout(&path, Duration::from_millis(30)).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::TimedOut);
+
+        drop(held);
+        FileLock::acquire_with_timeout(&path, Duration::from_millis(30)).unwrap();
+
+        write_atomic(&path, b"lium-pod-1 ssh-ed25519 AAAA\n").unwrap();
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            "lium-pod-1 ssh-ed25519 AAAA\n"
+        );
+        // Only the target and the lock file remain; no temp files are left behind
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 2);
+    }
+}
@@ -0,0 +1,419 @@
+//! Trust-on-first-use store of pod host keys, `~/.lium/known_hosts`.
+//!
+//! The first connection to a pod records the key its SSH server presents; later
+//! connections must present the same key or fail with [`SshError::HostKeyMismatch`].
+//! Pods are recorded under the alias `lium-pod-<pod id>` rather than their address,
+//! because providers reuse host and port pairs for new pods with new keys.
+//!
+//! The file is in OpenSSH `known_hosts` format, so the `ssh` binary that rsync runs
+//! can verify against the same entries (see [`openssh_host_key_options`]).
+//!
+//! Several lium processes may connect to pods at once, so changes to the file hold
+//! its [`FileLock`] and rewrites replace it atomically.
+
+use crate::errors::{Result, SshError, UtilsError};
+use crate::files::{write_atomic, FileLock};
+use crate::session::SshTarget;
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use ssh2::{HashType, HostKeyType, Session};
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// A server's public host key.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HostKey {
+    /// Algorithm name as written in `known_hosts`, e.g. `ssh-ed25519`
+    pub key_type: String,
+    /// Base64-encoded public key blob
+    pub key: String,
+    /// `SHA256:` fingerprint as shown by `ssh-keygen -l`, if known
+    pub fingerprint: Option<String>,
+}
+
+impl HostKey {
+    /// Reads the key presented by the server during the handshake of `session`
+    pub(crate) fn from_session(session: &Session) -> Option<Self> {
+        let (key, key_type) = session.host_key()?;
+        let key_type = match key_type {
+            HostKeyType::Rsa => "ssh-rsa",
+            HostKeyType::Dss => "ssh-dss",
+            HostKeyType::Ecdsa256 => "ecdsa-sha2-nistp256",
+            HostKeyType::Ecdsa384 => "ecdsa-sha2-nistp384",
+            HostKeyType::Ecdsa521 => "ecdsa-sha2-nistp521",
+            HostKeyType::Ed25519 => "ssh-ed25519",
+            HostKeyType::Unknown => return None,
+        };
+        let fingerprint = session.host_key_hash(HashType::Sha256).map(|hash| {
+            format!(
+                "SHA256:{}",
+                base64::engine::general_purpose::STANDARD_NO_PAD.encode(hash)
+            )
+        });
+        Some(Self {
+            key_type: key_type.to_string(),
+            key: STANDARD.encode(key),
+            fingerprint,
+        })
+    }
+
+    /// Describes the key for messages, e.g. `ssh-ed25519 SHA256:...`
+    fn describe(&self) -> String {
+        match &self.fingerprint {
+            Some(fingerprint) => format!("{} {}", self.key_type, fingerprint),
+            None => format!("{} key", self.key_type),
+        }
+    }
+}
+
+/// Outcome of checking a presented key against the store.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HostKeyStatus {
+    /// First connection; the key was recorded
+    Recorded,
+    /// The key matches the recorded one
+    Verified,
+}
+
+/// The `known_hosts` file holding recorded host keys.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KnownHosts {
+    path: PathBuf,
+}
+
+impl Default for KnownHosts {
+    /// The store at `~/.lium/known_hosts`
+    fn default() -> Self {
+        let home = home::home_dir().unwrap_or_default();
+        Self::new(home.join(".lium").join("known_hosts"))
+    }
+}
+
+impl KnownHosts {
+    /// Uses the store at `path`; the file is created on the first recorded key
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    /// Location of the store
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Returns the key recorded for `target`, if any.
+    ///
+    /// # Errors
+    /// * `UtilsError::Io` - If the store exists but cannot be read
+    pub fn get(&self, target: &SshTarget) -> Result<Option<HostKey>> {
+        self.find(&target.host_key_alias())
+    }
+
+    /// Verifies `key` for `target`, recording it if the target has no entry yet.
+    ///
+    /// # Arguments
+    /// * `target` - The host being connected to; its pod id selects the entry
+    /// * `key` - The key the server presented
+    ///
+    /// # Returns
+    /// * `Result<HostKeyStatus>` - Whether the key was recorded or verified
+    ///
+    /// # Errors
+    /// * `SshError::HostKeyMismatch` - If a different key is recorded for the target
+    /// * `UtilsError::Io` - If the store cannot be read or written, or another process
+    ///   keeps it locked
+    ///
+    /// # Examples
+    /// ```rust
+    /// use lium_utils::{HostKey, HostKeyStatus, KnownHosts, SshTarget};
+    ///
+    /// let dir = std::env::temp_dir().join(format!("lium-doc-{}", std::process::id()));
+    /// let known_hosts = KnownHosts::new(dir.join("known_hosts"));
+    /// let target = SshTarget::new("10.0.0.1", 2222, "root").for_pod("pod-1");
+    /// let key = HostKey {
+    ///     key_type: "ssh-ed25519".to_string(),
+    ///     key: "AAAAC3NzaC1lZDI1NTE5AAAAIDoc".to_string(),
+    ///     fingerprint: None,
+    /// };
+    ///
+    /// assert_eq!(known_hosts.check(&target, &key).unwrap(), HostKeyStatus::Recorded);
+    /// assert_eq!(known_hosts.check(&target, &key).unwrap(), HostKeyStatus::Verified);
+    /// # std::fs::remove_dir_all(dir).unwrap();
+    /// ```
+    pub fn check(&self, target: &SshTarget, key: &HostKey) -> Result<HostKeyStatus> {
+        // Verified keys need no lock: rewrites replace the file atomically
+        if let Some(known) = self.find(&target.host_key_alias())? {
+            if known.key_type == key.key_type && known.key == key.key {
+                return Ok(HostKeyStatus::Verified);
+            }
+        }
+        let _lock = FileLock::acquire(&self.path)?;
+        let alias = target.host_key_alias();
+
+        match self.find(&alias)? {
+            Some(known) if known.key_type == key.key_type && known.key == key.key => {
+                Ok(HostKeyStatus::Verified)
+            }
+            Some(known) => Err(UtilsError::Ssh(SshError::HostKeyMismatch(format!(
+                "the host key for {} has changed!\n  \
+                 recorded:  {}\n  \
+                 presented: {}\n\
+                 Someone may be intercepting the connection (man-in-the-middle attack), or \
+                 the pod's SSH server was reinstalled. Refusing to connect. If you are sure \
+                 the new key is legitimate, delete the '{}' line from {} and try again.",
+                describe_target(target),
+                known.describe(),
+                key.describe(),
+                alias,
+                self.path.display()
+            )))),
+            None => {
+                self.append(&alias, target, key)?;
+                Ok(HostKeyStatus::Recorded)
+            }
+        }
+    }
+
+    /// Removes the entry for the pod with id `pod_id`, e.g. after it was terminated.
+    ///
+    /// # Returns
+    /// * `Result<bool>` - True if an entry was removed
+    ///
+    /// # Errors
+    /// * `UtilsError::Io` - If the store cannot be read or rewritten, or another
+    ///   process keeps it locked
+    pub fn remove_pod(&self, pod_id: &str) -> Result<bool> {
+        if !self.path.exists() {
+            return Ok(false);
+        }
+        let _lock = FileLock::acquire(&self.path)?;
+        let alias = pod_host_key_alias(pod_id);
+        let Some(contents) = self.read()? else {
+            return Ok(false);
+        };
+
+        let kept: Vec<&str> = contents
+            .lines()
+            .filter(|line| line.split_whitespace().next() != Some(alias.as_str()))
+            .collect();
+        if kept.len() == contents.lines().count() {
+            return Ok(false);
+        }
+
+        let mut rewritten = kept.join("\n");
+        if !rewritten.is_empty() {
+            rewritten.push('\n');
+        }
+        write_atomic(&self.path, rewritten.as_bytes())?;
+        Ok(true)
+    }
+
+    fn read(&self) -> Result<Option<String>> {
+        match fs::read_to_string(&self.path) {
+            Ok(contents) => Ok(Some(contents)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    fn find(&self, alias: &str) -> Result<Option<HostKey>> {
+        let Some(contents) = self.read()? else {
+            return Ok(None);
+        };
+        Ok(contents.lines().find_map(|line| parse_line(line, alias)))
+    }
+
+    /// Appends an entry while the caller holds the store's lock; the comment records
+    /// the address and fingerprint for people reading the file
+    fn append(&self, alias: &str, target: &SshTarget, key: &HostKey) -> Result<()> {
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        let mut line = format!(
+            "{} {} {} {}:{}",
+            alias, key.key_type, key.key, target.host, target.port
+        );
+        if let Some(fingerprint) = &key.fingerprint {
+            line.push(' ');
+            line.push_str(fingerprint);
+        }
+        writeln!(file, "{}", line)?;
+        Ok(())
+    }
+}
+
+/// Name of a pod's entry in `known_hosts`
+pub fn pod_host_key_alias(pod_id: &str) -> String {
+    format!("lium-pod-{}", pod_id)
+}
+
+/// Parses a `known_hosts` line if it is the entry for `alias`
+fn parse_line(line: &str, alias: &str) -> Option<HostKey> {
+    let mut fields = line.split_whitespace();
+    if fields.next()? != alias {
+        return None;
+    }
+    let key_type = fields.next()?.to_string();
+    let key = fields.next()?.to_string();
+    let fingerprint = fields
+        .find(|field| field.starts_with("SHA256:"))
+        .map(str::to_string);
+    Some(HostKey {
+        key_type,
+        key,
+        fingerprint,
+    })
+}
+
+fn describe_target(target: &SshTarget) -> String {
+    match &target.pod_id {
+        Some(pod_id) => format!("pod {} ({}:{})", pod_id, target.host, target.port),
+        None => format!("{}:{}", target.host, target.port),
+    }
+}
+
+/// OpenSSH options that make the `ssh` binary verify `target` against `known_hosts`.
+///
+/// Used for rsync's `-e` command. The target must already have an entry, which any
+/// [`SshSession`](crate::SshSession) connection to it records.
+///
+/// # Examples
+/// ```rust
+/// use lium_utils::{openssh_host_key_options, KnownHosts, SshTarget};
+///
+/// let target = SshTarget::new("10.0.0.1", 2222, "root").for_pod("pod-1");
+/// let options = openssh_host_key_options(&target, &KnownHosts::new("/home/me/.lium/known_hosts"));
+/// assert_eq!(
+///     options,
+///     "-o StrictHostKeyChecking=yes -o UserKnownHostsFile='/home/me/.lium/known_hosts' -o HostKeyAlias=lium-pod-pod-1"
+/// );
+/// ```
+pub fn openssh_host_key_options(target: &SshTarget, known_hosts: &KnownHosts) -> String {
+    format!(
+        "-o StrictHostKeyChecking=yes -o UserKnownHostsFile='{}' -o HostKeyAlias={}",
+        known_hosts
+            .path()
+            .display()
+            .to_string()
+            .replace('\'', "'\"'\"'"),
+        target.host_key_alias()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(blob: &str) -> HostKey {
+        HostKey {
+            key_type: "ssh-ed25519".to_string(),
+            key: blob.to_string(),
+            fingerprint: Some(format!("SHA256:{}", blob)),
+        }
+    }
+
+    #[test]
+    fn test_known_hosts_trusts_first_key_per_pod() {
+        let dir = tempfile::tempdir().unwrap();
+        let known_hosts = KnownHosts::new(dir.path().join(".lium").join("known_hosts"));
+        let pod = SshTarget::new("10.0.0.1", 2222, "root").for_pod("pod-1");
+
+        assert_eq!(
+            known_hosts.check(&pod, &key("AAAAfirst")).unwrap(),
+            HostKeyStatus::Recorded
+        );
+        assert_eq!(
+            known_hosts.check(&pod, &key("AAAAfirst")).unwrap(),
+            HostKeyStatus::Verified
+        );
+
+        // A changed key for the same pod is refused
+        let err = known_hosts.check(&pod, &key("AAAAother")).unwrap_err();
+        let message = err.to_string();
+        assert!(matches!(err, UtilsError::Ssh(SshError::HostKeyMismatch(_))));
+        assert!(message.contains("pod pod-1 (10.0.0.1:2222)"));
+        assert!(message.contains("SHA256:AAAAfirst") && message.contains("SHA256:AAAAother"));
+
+        // A new pod reusing the address is a different entry
+        let reused = SshTarget::new("10.0.0.1", 2222, "root").for_pod("pod-2");
+        assert_eq!(
+            known_hosts.check(&reused, &key("AAAAother")).unwrap(),
+            HostKeyStatus::Recorded
+        );
+
+        let written = fs::read_to_string(known_hosts.path()).unwrap();
+        assert_eq!(
+            written,
+            "lium-pod-pod-1 ssh-ed25519 AAAAfirst 10.0.0.1:2222 SHA256:AAAAfirst\n\
+             lium-pod-pod-2 ssh-ed25519 AAAAother 10.0.0.1:2222 SHA256:AAAAother\n"
+        );
+    }
+
+    #[test]
+    fn test_known_hosts_remove_forgets_pod() {
+        let dir = tempfile::tempdir().unwrap();
+        let known_hosts = KnownHosts::new(dir.path().join("known_hosts"));
+        let pod = SshTarget::new("10.0.0.1", 2222, "root").for_pod("pod-1");
+
+        // Removing from a missing store neither fails nor creates the file
+        assert!(!known_hosts.remove_pod("pod-1").unwrap());
+        assert!(!known_hosts.path().exists());
+
+        known_hosts.check(&pod, &key("AAAAfirst")).unwrap();
+        assert!(known_hosts.remove_pod("pod-1").unwrap());
+        assert_eq!(known_hosts.get(&pod).unwrap(), None);
+        assert_eq!(
+            known_hosts.check(&pod, &key("AAAAnew")).unwrap(),
+            HostKeyStatus::Recorded
+        );
+        // The rewrite left no temporary file behind, only the store and its lock
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 2);
+    }
+
+    #[test]
+    fn test_known_hosts_keeps_concurrent_writers_entries() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("known_hosts");
+        KnownHosts::new(&path)
+            .check(
+                &SshTarget::new("10.0.0.9", 22, "root").for_pod("gone"),
+                &key("AAAAgone"),
+            )
+            .unwrap();
+
+        // Each thread stands in for another lium process with its own handle
+        let writers = (0..8)
+            .map(|i| {
+                let known_hosts = KnownHosts::new(&path);
+                std::thread::spawn(move || {
+                    let pod =
+                        SshTarget::new("10.0.0.1", 2222, "root").for_pod(format!("pod-{}", i));
+                    known_hosts
+                        .check(&pod, &key(&format!("AAAA{}", i)))
+                        .unwrap();
+                    if i == 0 {
+                        assert!(known_hosts.remove_pod("gone").unwrap());
+                    }
+                })
+            })
+            .collect::<Vec<_>>();
+        for writer in writers {
+            writer.join().unwrap();
+        }
+
+        let known_hosts = KnownHosts::new(&path);
+        for i in 0..8 {
+            let pod = SshTarget::new("10.0.0.1", 2222, "root").for_pod(format!("pod-{}", i));
+            assert_eq!(
+                known_hosts.get(&pod).unwrap(),
+                Some(key(&format!("AAAA{}", i)))
+            );
+        }
+        let gone = SshTarget::new("10.0.0.9", 22, "root").for_pod("gone");
+        assert_eq!(known_hosts.get(&gone).unwrap(), None);
+    }
+}
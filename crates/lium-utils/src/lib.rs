@@ -6,11 +6,15 @@
 
 pub mod docker;
 pub mod errors;
+pub mod files;
 pub mod formatters;
 pub mod gpu;
 pub mod id_generator;
+pub mod known_hosts;
 pub mod parsers;
 pub mod pod;
+pub mod redact;
+pub mod session;
 pub mod ssh;
 
 // Re-export common types for convenience
@@ -19,6 +23,9 @@ pub use errors::*;
 pub use formatters::*;
 pub use gpu::*;
 pub use id_generator::*;
+pub use known_hosts::*;
 pub use parsers::*;
 pub use pod::*;
+pub use redact::*;
+pub use session::*;
 pub use ssh::*;
@@ -0,0 +1,1194 @@
+//! Native SSH sessions built on libssh2.
+//!
+//! [`SshSession`] runs remote commands, interactive shells and SFTP transfers without
+//! an OpenSSH client installed on the local machine. [`SshPool`] keeps one
+//! authenticated session per host, port and user for the life of the process, so a
+//! command that talks to the same pod several times (a `mkdir` followed by uploads,
+//! a remote check followed by a command) only connects once.
+
+use crate::errors::{Result, SshError, UtilsError};
+use crate::known_hosts::{pod_host_key_alias, HostKey, HostKeyStatus, KnownHosts};
+use crate::parsers::parse_ssh_command;
+use ssh2::{Channel, OpenFlags, OpenType, Session, Sftp};
+use std::collections::HashMap;
+use std::fmt;
+use std::fs::File;
+use std::io::{self, ErrorKind, Read, Write};
+use std::net::{TcpStream, ToSocketAddrs};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc, Mutex, MutexGuard, OnceLock, PoisonError};
+use std::time::{Duration, Instant};
+
+/// How long to wait for the TCP connection, handshake and authentication.
+pub const DEFAULT_CONNECT_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Interval between keepalive messages on idle sessions, in seconds.
+///
+/// Keepalives are sent while a command is running but quiet, and before an idle
+/// pooled session is reused.
+const KEEPALIVE_INTERVAL_SECS: u32 = 30;
+
+/// Pause between polls of a channel that had no data ready.
+const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// Chunks of local stdin queued per receiver before reading pauses.
+const STDIN_QUEUE_CHUNKS: usize = 16;
+
+/// Where a pod can be reached over SSH; also the key of [`SshPool`].
+///
+/// `pod_id` selects the pod's entry in [`KnownHosts`]. Targets without one are
+/// recorded under their address.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SshTarget {
+    pub host: String,
+    pub port: u16,
+    pub user: String,
+    pub pod_id: Option<String>,
+}
+
+impl SshTarget {
+    /// Creates a target from its parts
+    pub fn new(host: impl Into<String>, port: u16, user: impl Into<String>) -> Self {
+        Self {
+            host: host.into(),
+            port,
+            user: user.into(),
+            pod_id: None,
+        }
+    }
+
+    /// Marks the target as the pod with id `pod_id`, whose host key is pinned per pod
+    pub fn for_pod(mut self, pod_id: impl Into<String>) -> Self {
+        self.pod_id = Some(pod_id.into());
+        self
+    }
+
+    /// Name of the target's entry in `known_hosts`: `lium-pod-<pod id>` for pods,
+    /// otherwise `[host]:port` as OpenSSH writes it
+    pub fn host_key_alias(&self) -> String {
+        match &self.pod_id {
+            Some(pod_id) => pod_host_key_alias(pod_id),
+            None => format!("[{}]:{}", self.host, self.port),
+        }
+    }
+
+    /// Builds a target from a pod's SSH command, e.g. `ssh root@10.0.0.1 -p 2222`.
+    ///
+    /// # Errors
+    /// * `UtilsError::Parse` - If the command has no `user@host`
+    ///
+    /// # Examples
+    /// ```rust
+    /// use lium_utils::SshTarget;
+    ///
+    /// let target = SshTarget::from_ssh_command("ssh root@10.0.0.1 -p 2222").unwrap();
+    /// assert_eq!(target, SshTarget::new("10.0.0.1", 2222, "root"));
+    /// ```
+    pub fn from_ssh_command(ssh_cmd: &str) -> Result<Self> {
+        let (host, port, user) = parse_ssh_command(ssh_cmd)?;
+        Ok(Self::new(host, port, user))
+    }
+}
+
+impl fmt::Display for SshTarget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}@{}:{}", self.user, self.host, self.port)
+    }
+}
+
+/// Credentials used to authenticate a session.
+///
+/// Identity files are offered in order, then (if enabled) every key held by the
+/// ssh-agent at `SSH_AUTH_SOCK`. Passphrase-protected and hardware-backed keys
+/// therefore work once they are added to the agent.
+///
+/// # Examples
+/// ```rust
+/// use lium_utils::SshAuth;
+///
+/// let auth = SshAuth::key_file("/home/me/.ssh/pod_key")
+///     .with_identity("/home/me/.ssh/id_ed25519")
+///     .with_agent(true);
+/// assert_eq!(auth.identities.len(), 2);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SshAuth {
+    /// Private key files offered to the server, most specific first
+    pub identities: Vec<PathBuf>,
+    /// Whether to fall back to the keys held by the running ssh-agent
+    pub agent: bool,
+}
+
+impl SshAuth {
+    /// Authenticates with the private key at `path` only
+    pub fn key_file(path: impl Into<PathBuf>) -> Self {
+        Self::default().with_identity(path)
+    }
+
+    /// Authenticates with the keys held by the running ssh-agent only
+    pub fn agent() -> Self {
+        Self::default().with_agent(true)
+    }
+
+    /// Also offers the private key at `path`, after the identities added so far
+    pub fn with_identity(mut self, path: impl Into<PathBuf>) -> Self {
+        let path = path.into();
+        if !self.identities.contains(&path) {
+            self.identities.push(path);
+        }
+        self
+    }
+
+    /// Enables or disables falling back to the ssh-agent
+    pub fn with_agent(mut self, agent: bool) -> Self {
+        self.agent = agent;
+        self
+    }
+
+    /// Authenticates `session` as `target.user`, trying each credential in turn
+    fn authenticate(&self, session: &Session, target: &SshTarget) -> Result<()> {
+        let mut attempts = Vec::new();
+
+        for identity in &self.identities {
+            if !identity.exists() {
+                log::debug!("Skipping missing identity {}", identity.display());
+                continue;
+            }
+            let mut public_key = identity.clone().into_os_string();
+            public_key.push(".pub");
+            let public_key = PathBuf::from(public_key);
+            let public_key = public_key.exists().then_some(public_key.as_path());
+            match session.userauth_pubkey_file(&target.user, public_key, identity, None) {
+                Ok(()) => {
+                    log::debug!("Authenticated to {} with {}", target, identity.display());
+                    return Ok(());
+                }
+                Err(e) => attempts.push(format!("{}: {}", identity.display(), e.message())),
+            }
+        }
+
+        if self.agent {
+            match self.authenticate_with_agent(session, target, &mut attempts) {
+                Ok(true) => return Ok(()),
+                Ok(false) => {}
+                Err(e) => attempts.push(format!("ssh-agent: {}", e.message())),
+            }
+        }
+
+        let reason = if attempts.is_empty() {
+            "no SSH key found and no ssh-agent available".to_string()
+        } else {
+            format!(
+                "tried {}. Keys protected by a passphrase must be added to ssh-agent \
+                 (ssh-add <key>)",
+                attempts.join("; ")
+            )
+        };
+        Err(UtilsError::Ssh(SshError::AuthenticationFailed(format!(
+            "{} rejected authentication: {}",
+            target, reason
+        ))))
+    }
+
+    /// Offers every agent identity; returns false if the server accepted none
+    fn authenticate_with_agent(
+        &self,
+        session: &Session,
+        target: &SshTarget,
+        attempts: &mut Vec<String>,
+    ) -> std::result::Result<bool, ssh2::Error> {
+        if std::env::var_os("SSH_AUTH_SOCK").is_none() {
+            log::debug!("SSH_AUTH_SOCK is not set; skipping ssh-agent");
+            return Ok(false);
+        }
+
+        let mut agent = session.agent()?;
+        agent.connect()?;
+        agent.list_identities()?;
+        for identity in agent.identities()? {
+            match agent.userauth(&target.user, &identity) {
+                Ok(()) => {
+                    log::debug!(
+                        "Authenticated to {} with agent key {}",
+                        target,
+                        identity.comment()
+                    );
+                    return Ok(true);
+                }
+                Err(e) => {
+                    attempts.push(format!("agent key {}: {}", identity.comment(), e.message()))
+                }
+            }
+        }
+        Ok(false)
+    }
+}
+
+/// The remote stream a line of command output was read from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputStream {
+    Stdout,
+    Stderr,
+}
+
+/// Standard input of a command run with [`SshSession::exec_cancellable`].
+///
+/// `data` is written first; then, if there is a `stream`, its chunks are forwarded as
+/// they arrive until an empty chunk or a disconnected sender ends the input. The
+/// command's stdin is closed afterwards, so it sees end-of-file.
+#[derive(Debug, Default)]
+pub struct ExecInput<'a> {
+    /// Bytes written before anything else, e.g. secret exports
+    pub data: &'a [u8],
+    /// Chunks forwarded after `data`, e.g. from [`stdin_fan_out`]
+    pub stream: Option<mpsc::Receiver<Vec<u8>>>,
+}
+
+impl<'a> ExecInput<'a> {
+    /// Input consisting of `data` only
+    pub fn bytes(data: &'a [u8]) -> Self {
+        Self { data, stream: None }
+    }
+
+    /// Forwards the chunks of `stream` after the data given so far
+    pub fn with_stream(mut self, stream: mpsc::Receiver<Vec<u8>>) -> Self {
+        self.stream = Some(stream);
+        self
+    }
+}
+
+/// Stops commands started with [`SshSession::exec_cancellable`] from another thread.
+///
+/// Clones share the same flag, so one token can stop commands on many pods at once,
+/// e.g. when `lium exec --fail-fast` sees the first failure.
+#[derive(Debug, Clone, Default)]
+pub struct CancelToken(Arc<AtomicBool>);
+
+impl CancelToken {
+    /// Creates a token that has not been cancelled
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Asks every command watching this token to stop
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::SeqCst);
+    }
+
+    /// Whether [`CancelToken::cancel`] has been called on this token or a clone
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::SeqCst)
+    }
+}
+
+/// An authenticated SSH connection to one host.
+///
+/// Operations on the same session run one at a time; use separate sessions (or
+/// separate [`SshPool`] targets) to work on several pods concurrently.
+pub struct SshSession {
+    target: SshTarget,
+    session: Session,
+    busy: Mutex<()>,
+}
+
+impl fmt::Debug for SshSession {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SshSession")
+            .field("target", &self.target)
+            .finish_non_exhaustive()
+    }
+}
+
+impl SshSession {
+    /// Connects to `target`, verifies its host key and authenticates with `auth`.
+    ///
+    /// The server's host key is checked against `known_hosts` before any credentials
+    /// are sent: the first connection to a target records it, later connections must
+    /// present the same key.
+    ///
+    /// # Arguments
+    /// * `target` - Host, port and user to connect as
+    /// * `auth` - Credentials to authenticate with
+    /// * `known_hosts` - Store of trusted host keys
+    /// * `timeout` - Limit for connecting, the handshake and authentication
+    ///
+    /// # Returns
+    /// * `Result<SshSession>` - A session ready to run commands and transfers
+    ///
+    /// # Errors
+    /// * `SshError::ConnectionFailed` - If the host is unreachable or the handshake fails
+    /// * `SshError::HostKeyMismatch` - If the host key differs from the recorded one
+    /// * `SshError::AuthenticationFailed` - If the server rejects every identity
+    ///
+    /// # Examples
+    /// ```rust,no_run
+    /// use lium_utils::{KnownHosts, SshAuth, SshSession, SshTarget, DEFAULT_CONNECT_TIMEOUT};
+    ///
+    /// let target = SshTarget::new("10.0.0.1", 2222, "root").for_pod("pod-1");
+    /// let auth = SshAuth::key_file("/home/me/.ssh/id_ed25519");
+    /// let session =
+    ///     SshSession::connect(&target, &auth, &KnownHosts::default(), DEFAULT_CONNECT_TIMEOUT)?;
+    /// let (stdout, _, code) = session.exec("nvidia-smi -L")?;
+    /// # Ok::<(), lium_utils::UtilsError>(())
+    /// ```
+    pub fn connect(
+        target: &SshTarget,
+        auth: &SshAuth,
+        known_hosts: &KnownHosts,
+        timeout: Duration,
+    ) -> Result<Self> {
+        let addr = (target.host.as_str(), target.port)
+            .to_socket_addrs()
+            .map_err(failed(SshError::ConnectionFailed, target))?
+            .next()
+            .ok_or_else(|| {
+                UtilsError::Ssh(SshError::ConnectionFailed(format!(
+                    "{}: host did not resolve to an address",
+                    target
+                )))
+            })?;
+        let stream = TcpStream::connect_timeout(&addr, timeout)
+            .map_err(failed(SshError::ConnectionFailed, target))?;
+
+        let mut session = Session::new().map_err(failed(SshError::ConnectionFailed, target))?;
+        session.set_timeout(timeout.as_millis().try_into().unwrap_or(u32::MAX));
+        session.set_tcp_stream(stream);
+        session
+            .handshake()
+            .map_err(failed(SshError::ConnectionFailed, target))?;
+
+        let host_key = HostKey::from_session(&session).ok_or_else(|| {
+            UtilsError::Ssh(SshError::ConnectionFailed(format!(
+                "{}: server presented no supported host key",
+                target
+            )))
+        })?;
+        match known_hosts.check(target, &host_key)? {
+            HostKeyStatus::Recorded => log::info!(
+                "Recorded host key for {} in {}",
+                target.host_key_alias(),
+                known_hosts.path().display()
+            ),
+            HostKeyStatus::Verified => {
+                log::debug!("Host key for {} verified", target.host_key_alias())
+            }
+        }
+
+        auth.authenticate(&session, target)?;
+
+        // Long-running commands and transfers are not bounded by the connect timeout;
+        // keepalives (see `keep_alive`) stop idle connections being dropped by NAT and
+        // firewalls, and fail once the connection is gone
+        session.set_timeout(0);
+        session.set_keepalive(false, KEEPALIVE_INTERVAL_SECS);
+        log::debug!("SSH session established with {}", target);
+
+        Ok(Self {
+            target: target.clone(),
+            session,
+            busy: Mutex::new(()),
+        })
+    }
+
+    /// The host, port and user this session is connected to
+    pub fn target(&self) -> &SshTarget {
+        &self.target
+    }
+
+    /// Runs `command` and passes each line of its output to `on_output` as it arrives.
+    ///
+    /// Standard input receives `stdin` (if any) and is then closed, so commands that
+    /// read their input see end-of-file instead of waiting forever.
+    ///
+    /// # Arguments
+    /// * `command` - Shell command line to run on the remote host
+    /// * `stdin` - Bytes to write to the command's standard input
+    /// * `on_output` - Called with the stream and text of every output line
+    ///
+    /// # Returns
+    /// * `Result<i32>` - The command's exit status
+    ///
+    /// # Errors
+    /// * `SshError::CommandFailed` - If the channel cannot be opened or breaks mid-command
+    pub fn exec_streaming(
+        &self,
+        command: &str,
+        stdin: Option<&[u8]>,
+        on_output: impl FnMut(OutputStream, &str),
+    ) -> Result<i32> {
+        self.exec_cancellable(
+            command,
+            ExecInput::bytes(stdin.unwrap_or_default()),
+            &CancelToken::new(),
+            None,
+            on_output,
+        )
+    }
+
+    /// Like [`SshSession::exec_streaming`], but can stream standard input and gives up
+    /// when `cancel` is cancelled or `timeout` elapses.
+    ///
+    /// Giving up closes the channel; the remote command is not signalled and may keep
+    /// running if it ignores the closed connection.
+    ///
+    /// # Arguments
+    /// * `command` - Shell command line to run on the remote host
+    /// * `stdin` - What the command reads on standard input
+    /// * `cancel` - Token checked while the command runs
+    /// * `timeout` - Limit for the command's run time, if any
+    /// * `on_output` - Called with the stream and text of every output line
+    ///
+    /// # Returns
+    /// * `Result<i32>` - The command's exit status
+    ///
+    /// # Errors
+    /// * `SshError::CommandFailed` - If the channel cannot be opened or breaks mid-command
+    /// * `SshError::Timeout` - If the command ran longer than `timeout`
+    /// * `SshError::Cancelled` - If `cancel` was cancelled before the command finished
+    ///
+    /// # Examples
+    /// ```rust,no_run
+    /// use lium_utils::{stdin_fan_out, CancelToken, ExecInput, SshAuth, SshPool, SshTarget};
+    /// use std::time::Duration;
+    ///
+    /// let target = SshTarget::from_ssh_command("ssh root@10.0.0.1 -p 2222")?;
+    /// let session = SshPool::global().get(&target, &SshAuth::agent())?;
+    /// let cancel = CancelToken::new();
+    /// let local_stdin = stdin_fan_out(1).remove(0);
+    /// let code = session.exec_cancellable(
+    ///     "wc -l",
+    ///     ExecInput::default().with_stream(local_stdin),
+    ///     &cancel,
+    ///     Some(Duration::from_secs(3600)),
+    ///     |_, line| println!("{}", line),
+    /// )?;
+    /// # Ok::<(), lium_utils::UtilsError>(())
+    /// ```
+    pub fn exec_cancellable(
+        &self,
+        command: &str,
+        stdin: ExecInput<'_>,
+        cancel: &CancelToken,
+        timeout: Option<Duration>,
+        mut on_output: impl FnMut(OutputStream, &str),
+    ) -> Result<i32> {
+        let deadline = timeout.map(|timeout| Instant::now() + timeout);
+        let _busy = self.lock();
+        let mut channel = self.open_channel()?;
+        channel
+            .exec(command)
+            .map_err(failed(SshError::CommandFailed, &self.target))?;
+        channel
+            .write_all(stdin.data)
+            .map_err(failed(SshError::CommandFailed, &self.target))?;
+        if stdin.stream.is_none() {
+            channel
+                .send_eof()
+                .map_err(failed(SshError::CommandFailed, &self.target))?;
+        }
+
+        self.session.set_blocking(false);
+        let relayed = relay_lines(
+            &self.session,
+            &mut channel,
+            stdin.stream,
+            &mut on_output,
+            || cancel.is_cancelled() || deadline.is_some_and(|deadline| Instant::now() >= deadline),
+        );
+        self.session.set_blocking(true);
+        let finished = relayed.map_err(failed(SshError::CommandFailed, &self.target))?;
+
+        if !finished {
+            // Best effort: the server may already be gone
+            let _ = channel.close();
+            return Err(UtilsError::Ssh(match timeout {
+                Some(timeout) if !cancel.is_cancelled() => SshError::Timeout(format!(
+                    "{}: no exit after {}s",
+                    self.target,
+                    timeout.as_secs()
+                )),
+                _ => SshError::Cancelled(self.target.to_string()),
+            }));
+        }
+        self.finish(channel)
+    }
+
+    /// Runs `command` and collects its output.
+    ///
+    /// # Returns
+    /// * `Result<(String, String, i32)>` - Standard output, standard error and exit status
+    ///
+    /// # Errors
+    /// * `SshError::CommandFailed` - If the channel cannot be opened or breaks mid-command
+    pub fn exec(&self, command: &str) -> Result<(String, String, i32)> {
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+        let code = self.exec_streaming(command, None, |stream, line| match stream {
+            OutputStream::Stdout => stdout.push(line.to_string()),
+            OutputStream::Stderr => stderr.push(line.to_string()),
+        })?;
+        Ok((stdout.join("\n"), stderr.join("\n"), code))
+    }
+
+    /// Opens an interactive login shell attached to the local terminal.
+    ///
+    /// A pseudo-terminal matching the local terminal's type and size is requested, the
+    /// local terminal is switched to raw mode for the duration of the shell, and
+    /// keystrokes and output are relayed until the remote shell exits.
+    ///
+    /// # Returns
+    /// * `Result<i32>` - The exit status of the remote shell
+    ///
+    /// # Errors
+    /// * `SshError::CommandFailed` - If the shell cannot be started or the connection drops
+    pub fn shell(&self) -> Result<i32> {
+        self.run_in_terminal(None)
+    }
+
+    /// Runs `command` in a pseudo-terminal attached to the local terminal.
+    ///
+    /// Like [`SshSession::shell`], but for a single command such as `htop` or
+    /// `python -i`: keystrokes and output are relayed until the command exits.
+    ///
+    /// # Returns
+    /// * `Result<i32>` - The command's exit status
+    ///
+    /// # Errors
+    /// * `SshError::CommandFailed` - If the command cannot be started or the connection drops
+    pub fn exec_tty(&self, command: &str) -> Result<i32> {
+        self.run_in_terminal(Some(command))
+    }
+
+    /// Runs `command` (or a login shell) in a pty relayed to the local terminal
+    fn run_in_terminal(&self, command: Option<&str>) -> Result<i32> {
+        let _busy = self.lock();
+        let mut channel = self.open_channel()?;
+        let term = std::env::var("TERM").unwrap_or_else(|_| "xterm-256color".to_string());
+        let size = terminal::size().unwrap_or((80, 24));
+        channel
+            .request_pty(&term, None, Some((size.0, size.1, 0, 0)))
+            .map_err(failed(SshError::CommandFailed, &self.target))?;
+        match command {
+            Some(command) => channel.exec(command),
+            None => channel.shell(),
+        }
+        .map_err(failed(SshError::CommandFailed, &self.target))?;
+
+        let raw_mode = terminal::RawMode::enable();
+        let input = stdin_fan_out(1).remove(0);
+        self.session.set_blocking(false);
+        let relayed = relay_terminal(&self.session, &mut channel, &input, size);
+        self.session.set_blocking(true);
+        drop(raw_mode);
+        relayed.map_err(failed(SshError::CommandFailed, &self.target))?;
+
+        self.finish(channel)
+    }
+
+    /// Uploads a local file over SFTP.
+    ///
+    /// Remote paths starting with `~/` are relative to the remote home directory. If
+    /// `remote` ends with `/` or names an existing directory, the file keeps its local
+    /// name inside it. Missing parent directories are created and the local file's
+    /// permissions are preserved.
+    ///
+    /// # Arguments
+    /// * `local` - File to upload
+    /// * `remote` - Destination file or directory on the remote host
+    ///
+    /// # Returns
+    /// * `Result<PathBuf>` - The remote path the file was written to
+    ///
+    /// # Errors
+    /// * `SshError::TransferFailed` - If the file cannot be read, created or written
+    pub fn upload(&self, local: &Path, remote: &str) -> Result<PathBuf> {
+        let _busy = self.lock();
+        let sftp = self.sftp()?;
+        let mut path = remote_path(remote);
+        if remote.ends_with('/') || sftp.stat(&path).map(|s| s.is_dir()).unwrap_or(false) {
+            let name = local.file_name().ok_or_else(|| {
+                UtilsError::Ssh(SshError::TransferFailed(format!(
+                    "'{}' is not a file",
+                    local.display()
+                )))
+            })?;
+            path.push(name);
+        }
+        if let Some(parent) = path.parent() {
+            create_dir_all(&sftp, parent)?;
+        }
+
+        let mut source =
+            File::open(local).map_err(failed(SshError::TransferFailed, local.display()))?;
+        let mode = source
+            .metadata()
+            .map(|metadata| file_mode(&metadata))
+            .unwrap_or(0o644);
+        let mut dest = sftp
+            .open_mode(
+                &path,
+                OpenFlags::WRITE | OpenFlags::CREATE | OpenFlags::TRUNCATE,
+                mode,
+                OpenType::File,
+            )
+            .map_err(failed(SshError::TransferFailed, path.display()))?;
+        io::copy(&mut source, &mut dest)
+            .map_err(failed(SshError::TransferFailed, path.display()))?;
+        Ok(path)
+    }
+
+    /// Downloads a remote file over SFTP.
+    ///
+    /// If `local` is an existing directory, the file keeps its remote name inside it.
+    ///
+    /// # Arguments
+    /// * `remote` - File on the remote host; `~/` is the remote home directory
+    /// * `local` - Destination file or directory
+    ///
+    /// # Returns
+    /// * `Result<PathBuf>` - The local path the file was written to
+    ///
+    /// # Errors
+    /// * `SshError::TransferFailed` - If the file cannot be read or written
+    pub fn download(&self, remote: &str, local: &Path) -> Result<PathBuf> {
+        let _busy = self.lock();
+        let sftp = self.sftp()?;
+        let path = remote_path(remote);
+        let mut source = sftp
+            .open(&path)
+            .map_err(failed(SshError::TransferFailed, path.display()))?;
+
+        let local = match path.file_name() {
+            Some(name) if local.is_dir() => local.join(name),
+            _ => local.to_path_buf(),
+        };
+        let mut dest =
+            File::create(&local).map_err(failed(SshError::TransferFailed, local.display()))?;
+        io::copy(&mut source, &mut dest)
+            .map_err(failed(SshError::TransferFailed, path.display()))?;
+        Ok(local)
+    }
+
+    /// Creates a remote directory and any missing parents, like `mkdir -p`.
+    ///
+    /// # Errors
+    /// * `SshError::TransferFailed` - If a directory cannot be created
+    pub fn create_dir_all(&self, remote: &str) -> Result<()> {
+        let _busy = self.lock();
+        let sftp = self.sftp()?;
+        create_dir_all(&sftp, &remote_path(remote))
+    }
+
+    /// Sends a keepalive if the session has been idle for the keepalive interval.
+    ///
+    /// Returns false if the connection is gone. A session busy with another
+    /// operation is in use, and so alive.
+    fn keep_alive(&self) -> bool {
+        match self.busy.try_lock() {
+            Ok(_busy) => self.session.keepalive_send().is_ok(),
+            Err(_) => true,
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, ()> {
+        self.busy.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn open_channel(&self) -> Result<Channel> {
+        self.session
+            .channel_session()
+            .map_err(failed(SshError::CommandFailed, &self.target))
+    }
+
+    fn sftp(&self) -> Result<Sftp> {
+        self.session
+            .sftp()
+            .map_err(failed(SshError::TransferFailed, &self.target))
+    }
+
+    /// Waits for the remote side to close `channel` and returns its exit status
+    fn finish(&self, mut channel: Channel) -> Result<i32> {
+        channel
+            .wait_close()
+            .map_err(failed(SshError::CommandFailed, &self.target))?;
+        channel
+            .exit_status()
+            .map_err(failed(SshError::CommandFailed, &self.target))
+    }
+}
+
+/// Authenticated sessions shared by every command in one CLI invocation.
+///
+/// Sessions are keyed by [`SshTarget`] and stay open until the pool is dropped or the
+/// target is evicted. New connections verify host keys against the pool's
+/// [`KnownHosts`].
+///
+/// # Examples
+/// ```rust,no_run
+/// use lium_utils::{SshAuth, SshPool, SshTarget};
+///
+/// let target = SshTarget::from_ssh_command("ssh root@10.0.0.1 -p 2222")?;
+/// let auth = SshAuth::key_file("/home/me/.ssh/id_ed25519");
+///
+/// let session = SshPool::global().get(&target, &auth)?;
+/// session.create_dir_all("~/data")?;
+/// // Reuses the connection opened above
+/// SshPool::global().get(&target, &auth)?.upload("train.csv".as_ref(), "~/data/")?;
+/// # Ok::<(), lium_utils::UtilsError>(())
+/// ```
+#[derive(Debug)]
+pub struct SshPool {
+    sessions: Mutex<HashMap<SshTarget, Arc<SshSession>>>,
+    known_hosts: KnownHosts,
+}
+
+impl SshPool {
+    /// Creates an empty pool that trusts host keys recorded in `known_hosts`
+    pub fn new(known_hosts: KnownHosts) -> Self {
+        Self {
+            sessions: Mutex::default(),
+            known_hosts,
+        }
+    }
+
+    /// The pool used by the current process, backed by `~/.lium/known_hosts`
+    pub fn global() -> &'static SshPool {
+        static POOL: OnceLock<SshPool> = OnceLock::new();
+        POOL.get_or_init(|| SshPool::new(KnownHosts::default()))
+    }
+
+    /// The host key store new connections are verified against
+    pub fn known_hosts(&self) -> &KnownHosts {
+        &self.known_hosts
+    }
+
+    /// Returns the open session for `target`, connecting with `auth` if there is none.
+    ///
+    /// # Errors
+    /// * `SshError::ConnectionFailed` / `SshError::AuthenticationFailed` - If a new
+    ///   connection cannot be established
+    /// * `SshError::HostKeyMismatch` - If the host key differs from the recorded one
+    pub fn get(&self, target: &SshTarget, auth: &SshAuth) -> Result<Arc<SshSession>> {
+        let pooled = self.sessions().get(target).cloned();
+        if let Some(session) = pooled {
+            if session.keep_alive() {
+                return Ok(session);
+            }
+            log::debug!("Pooled SSH session with {} is gone, reconnecting", target);
+            self.evict(target);
+        }
+
+        // Connect without holding the lock so other targets can connect concurrently
+        let session = Arc::new(SshSession::connect(
+            target,
+            auth,
+            &self.known_hosts,
+            DEFAULT_CONNECT_TIMEOUT,
+        )?);
+        Ok(self
+            .sessions()
+            .entry(target.clone())
+            .or_insert(session)
+            .clone())
+    }
+
+    /// Closes the pooled session for `target`, if any
+    pub fn evict(&self, target: &SshTarget) {
+        self.sessions().remove(target);
+    }
+
+    /// Number of open sessions
+    pub fn len(&self) -> usize {
+        self.sessions().len()
+    }
+
+    /// Returns true if no sessions are open
+    pub fn is_empty(&self) -> bool {
+        self.sessions().is_empty()
+    }
+
+    fn sessions(&self) -> MutexGuard<'_, HashMap<SshTarget, Arc<SshSession>>> {
+        self.sessions.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/// Maps an error to `kind`, prefixed with what was being worked on
+fn failed<E: fmt::Display>(
+    kind: fn(String) -> SshError,
+    context: impl fmt::Display,
+) -> impl FnOnce(E) -> UtilsError {
+    move |e| UtilsError::Ssh(kind(format!("{}: {}", context, e)))
+}
+
+/// Converts a remote path as typed by users into an SFTP path.
+///
+/// SFTP resolves relative paths against the home directory, so `~` and `~/` prefixes
+/// are dropped.
+fn remote_path(remote: &str) -> PathBuf {
+    match remote {
+        "" | "~" | "~/" => PathBuf::from("."),
+        _ => PathBuf::from(remote.strip_prefix("~/").unwrap_or(remote)),
+    }
+}
+
+fn create_dir_all(sftp: &Sftp, dir: &Path) -> Result<()> {
+    let mut current = PathBuf::new();
+    for component in dir.components() {
+        current.push(component);
+        if current.as_os_str() == "." || current.as_os_str() == "/" {
+            continue;
+        }
+        if sftp.stat(&current).is_err() {
+            sftp.mkdir(&current, 0o755)
+                .map_err(failed(SshError::TransferFailed, current.display()))?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(unix)]
+fn file_mode(metadata: &std::fs::Metadata) -> i32 {
+    use std::os::unix::fs::PermissionsExt;
+    (metadata.permissions().mode() & 0o777) as i32
+}
+
+#[cfg(not(unix))]
+fn file_mode(_: &std::fs::Metadata) -> i32 {
+    0o644
+}
+
+/// Collects bytes from one stream and splits them into lines
+#[derive(Default)]
+struct LineBuffer(Vec<u8>);
+
+impl LineBuffer {
+    /// Appends `data` and passes every completed line to `emit`
+    fn push(&mut self, data: &[u8], mut emit: impl FnMut(&str)) {
+        self.0.extend_from_slice(data);
+        while let Some(end) = self.0.iter().position(|&b| b == b'\n') {
+            let line: Vec<u8> = self.0.drain(..=end).collect();
+            emit(String::from_utf8_lossy(&line[..end]).trim_end_matches('\r'));
+        }
+    }
+
+    /// Passes any unterminated last line to `emit`
+    fn finish(&mut self, mut emit: impl FnMut(&str)) {
+        if !self.0.is_empty() {
+            emit(String::from_utf8_lossy(&self.0).trim_end_matches('\r'));
+            self.0.clear();
+        }
+    }
+}
+
+/// Reads from a non-blocking stream, treating "no data yet" as zero bytes
+fn read_ready(stream: &mut impl Read, buf: &mut [u8]) -> io::Result<usize> {
+    match stream.read(buf) {
+        Err(e) if e.kind() == ErrorKind::WouldBlock => Ok(0),
+        result => result,
+    }
+}
+
+/// Relays output lines from a non-blocking channel until the remote side closes it.
+///
+/// Stdout and stderr are read alternately, so a command writing a lot to one stream
+/// cannot stall waiting for the other to be drained. Chunks from `input` are written
+/// to the command's stdin as the channel accepts them, and stdin is closed once
+/// `input` ends. Keepalives are sent on `session` while the command is quiet. Returns
+/// false if `stop` asked to give up before the channel closed.
+fn relay_lines(
+    session: &Session,
+    channel: &mut Channel,
+    mut input: Option<mpsc::Receiver<Vec<u8>>>,
+    on_output: &mut impl FnMut(OutputStream, &str),
+    stop: impl Fn() -> bool,
+) -> io::Result<bool> {
+    let mut stdout = LineBuffer::default();
+    let mut stderr = LineBuffer::default();
+    let mut buf = [0u8; 16 * 1024];
+    let mut pending = Vec::new();
+    let mut finished = true;
+
+    loop {
+        let out = read_ready(channel, &mut buf)?;
+        stdout.push(&buf[..out], |line| on_output(OutputStream::Stdout, line));
+        let err = read_ready(&mut channel.stderr(), &mut buf)?;
+        stderr.push(&buf[..err], |line| on_output(OutputStream::Stderr, line));
+
+        // Only take the next chunk once the last one is written, so a bounded
+        // sender is slowed down to the pace the remote command reads at
+        let mut written = 0;
+        if let Some(receiver) = &input {
+            if pending.is_empty() {
+                match receiver.try_recv() {
+                    Ok(chunk) if !chunk.is_empty() => pending = chunk,
+                    Err(mpsc::TryRecvError::Empty) => {}
+                    Ok(_) | Err(mpsc::TryRecvError::Disconnected) => {
+                        // Best effort: the command may have exited already
+                        let _ = channel.send_eof();
+                        input = None;
+                    }
+                }
+            }
+            if !pending.is_empty() {
+                match channel.write(&pending) {
+                    Ok(count) => written = count,
+                    Err(e) if e.kind() == ErrorKind::WouldBlock => {}
+                    // The command closed its stdin; keep relaying its output
+                    Err(_) => input = None,
+                }
+                pending.drain(..written);
+            }
+        }
+
+        let idle = out == 0 && err == 0 && written == 0;
+        if idle && channel.eof() {
+            break;
+        }
+        if stop() {
+            finished = false;
+            break;
+        }
+        if idle {
+            // Best effort: a dead connection also shows up as a read error
+            let _ = session.keepalive_send();
+            std::thread::sleep(POLL_INTERVAL);
+        }
+    }
+
+    stdout.finish(|line| on_output(OutputStream::Stdout, line));
+    stderr.finish(|line| on_output(OutputStream::Stderr, line));
+    Ok(finished)
+}
+
+/// Reads local stdin on a background thread and sends every chunk to `count`
+/// receivers, e.g. one per pod a command runs on.
+///
+/// An empty chunk marks end-of-file. The channels are bounded, so reading pauses
+/// while the slowest receiver catches up instead of buffering all of stdin; a
+/// receiver that is dropped no longer holds the others back.
+///
+/// # Examples
+/// ```rust,no_run
+/// use lium_utils::stdin_fan_out;
+///
+/// for receiver in stdin_fan_out(2) {
+///     std::thread::spawn(move || {
+///         while let Ok(chunk) = receiver.recv() {
+///             if chunk.is_empty() {
+///                 break;
+///             }
+///         }
+///     });
+/// }
+/// ```
+pub fn stdin_fan_out(count: usize) -> Vec<mpsc::Receiver<Vec<u8>>> {
+    let (mut senders, receivers): (Vec<_>, Vec<_>) = (0..count)
+        .map(|_| mpsc::sync_channel(STDIN_QUEUE_CHUNKS))
+        .unzip();
+    std::thread::spawn(move || {
+        let mut stdin = io::stdin();
+        let mut buf = [0u8; 16 * 1024];
+        while !senders.is_empty() {
+            let read = stdin.read(&mut buf).unwrap_or(0);
+            senders.retain(|sender| sender.send(buf[..read].to_vec()).is_ok());
+            if read == 0 {
+                break;
+            }
+        }
+    });
+    receivers
+}
+
+/// Relays a non-blocking pty channel to the local terminal until the remote shell
+/// exits, sending keepalives on `session` while it is quiet
+fn relay_terminal(
+    session: &Session,
+    channel: &mut Channel,
+    input: &mpsc::Receiver<Vec<u8>>,
+    mut size: (u32, u32),
+) -> io::Result<()> {
+    let mut stdout = io::stdout();
+    let mut stderr = io::stderr();
+    let mut pending = Vec::new();
+    let mut stdin_open = true;
+    let mut eof_sent = false;
+    let mut buf = [0u8; 16 * 1024];
+
+    loop {
+        let out = read_ready(channel, &mut buf)?;
+        if out > 0 {
+            stdout.write_all(&buf[..out])?;
+            stdout.flush()?;
+        }
+        let err = read_ready(&mut channel.stderr(), &mut buf)?;
+        if err > 0 {
+            stderr.write_all(&buf[..err])?;
+            stderr.flush()?;
+        }
+
+        while let Ok(chunk) = input.try_recv() {
+            if chunk.is_empty() {
+                stdin_open = false;
+            }
+            pending.extend_from_slice(&chunk);
+        }
+        let mut written = 0;
+        if !pending.is_empty() {
+            written = match channel.write(&pending) {
+                Err(e) if e.kind() == ErrorKind::WouldBlock => 0,
+                result => result?,
+            };
+            pending.drain(..written);
+        } else if !stdin_open && !eof_sent {
+            eof_sent = channel.send_eof().is_ok();
+        }
+
+        if let Some(current) = terminal::size().filter(|current| *current != size) {
+            if channel
+                .request_pty_size(current.0, current.1, None, None)
+                .is_ok()
+            {
+                size = current;
+            }
+        }
+
+        if out == 0 && err == 0 && written == 0 {
+            if channel.eof() {
+                return Ok(());
+            }
+            let _ = session.keepalive_send();
+            std::thread::sleep(POLL_INTERVAL);
+        }
+    }
+}
+
+/// Local terminal handling for interactive shells.
+#[cfg(unix)]
+mod terminal {
+    /// Columns and rows of the terminal attached to stdout
+    pub fn size() -> Option<(u32, u32)> {
+        // SAFETY: TIOCGWINSZ only writes into the winsize struct passed to it
+        let mut ws: libc::winsize = unsafe { std::mem::zeroed() };
+        let ok = unsafe { libc::ioctl(libc::STDOUT_FILENO, libc::TIOCGWINSZ, &mut ws) } == 0;
+        (ok && ws.ws_col > 0).then(|| (u32::from(ws.ws_col), u32::from(ws.ws_row)))
+    }
+
+    /// Puts the terminal on stdin into raw mode and restores it when dropped
+    pub struct RawMode(libc::termios);
+
+    impl RawMode {
+        /// Returns `None` if stdin is not a terminal
+        pub fn enable() -> Option<Self> {
+            // SAFETY: the termios struct is initialised by tcgetattr before use
+            unsafe {
+                if libc::isatty(libc::STDIN_FILENO) != 1 {
+                    return None;
+                }
+                let mut termios: libc::termios = std::mem::zeroed();
+                if libc::tcgetattr(libc::STDIN_FILENO, &mut termios) != 0 {
+                    return None;
+                }
+                let original = termios;
+                libc::cfmakeraw(&mut termios);
+                libc::tcsetattr(libc::STDIN_FILENO, libc::TCSANOW, &termios);
+                Some(Self(original))
+            }
+        }
+    }
+
+    impl Drop for RawMode {
+        fn drop(&mut self) {
+            // SAFETY: restores the settings read in `enable`
+            unsafe {
+                libc::tcsetattr(libc::STDIN_FILENO, libc::TCSANOW, &self.0);
+            }
+        }
+    }
+}
+
+/// Local terminal handling for interactive shells (no raw mode on this platform).
+#[cfg(not(unix))]
+mod terminal {
+    pub fn size() -> Option<(u32, u32)> {
+        None
+    }
+
+    pub struct RawMode;
+
+    impl RawMode {
+        pub fn enable() -> Option<Self> {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::TcpListener;
+
+    #[test]
+    fn test_line_buffer_splits_chunks_into_lines() {
+        let mut buffer = LineBuffer::default();
+        let mut lines = Vec::new();
+
+        buffer.push(b"GPU 0: H100\r\nGPU", |l| lines.push(l.to_string()));
+        buffer.push(b" 1: H100\n\nlast", |l| lines.push(l.to_string()));
+        buffer.finish(|l| lines.push(l.to_string()));
+
+        assert_eq!(lines, vec!["GPU 0: H100", "GPU 1: H100", "", "last"]);
+    }
+
+    #[test]
+    fn test_remote_path_is_relative_to_home() {
+        assert_eq!(
+            remote_path("~/data/train.csv"),
+            PathBuf::from("data/train.csv")
+        );
+        assert_eq!(remote_path("~"), PathBuf::from("."));
+        assert_eq!(remote_path(""), PathBuf::from("."));
+        assert_eq!(
+            remote_path("/workspace/out"),
+            PathBuf::from("/workspace/out")
+        );
+    }
+
+    #[test]
+    fn test_auth_offers_identities_in_order_once() {
+        let auth = SshAuth::key_file("/keys/pod")
+            .with_identity("/keys/default")
+            .with_identity("/keys/pod");
+        assert_eq!(
+            auth.identities,
+            vec![PathBuf::from("/keys/pod"), PathBuf::from("/keys/default")]
+        );
+        assert!(!auth.agent);
+        assert!(SshAuth::agent().identities.is_empty());
+    }
+
+    #[test]
+    fn test_pool_only_keeps_established_sessions() {
+        // Bind and drop a listener to get a local port that refuses connections
+        let port = TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap()
+            .port();
+        let target = SshTarget::new("127.0.0.1", port, "root");
+        let pool = SshPool::new(KnownHosts::new("/nonexistent/known_hosts"));
+
+        let result = pool.get(&target, &SshAuth::key_file("/nonexistent/id_ed25519"));
+
+        assert!(matches!(
+            result,
+            Err(UtilsError::Ssh(SshError::ConnectionFailed(_)))
+        ));
+        assert!(pool.is_empty());
+        assert_eq!(target.to_string(), format!("root@127.0.0.1:{}", port));
+        assert_eq!(
+            SshTarget::from_ssh_command("ssh -p 2222 ubuntu@example.com").unwrap(),
+            SshTarget::new("example.com", 2222, "ubuntu")
+        );
+    }
+}
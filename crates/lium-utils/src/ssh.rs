@@ -1,31 +1,61 @@
+//! SSH helpers for running commands and copying files on pods.
+//!
+//! Commands, shells and SFTP transfers go through the process-wide [`SshPool`], so
+//! they need no OpenSSH client and reuse one connection per pod. Every helper takes
+//! the pod's id, which its host key is pinned to. Only rsync still shells out, since
+//! it needs an `ssh` binary to carry its own protocol; that binary verifies host keys
+//! against the same `~/.lium/known_hosts`.
+//!
+//! libssh2 calls block, so the `async` helpers run them on tokio's blocking thread
+//! pool instead of stalling the runtime.
+
 use crate::errors::{Result, SshError, UtilsError};
+use crate::known_hosts::openssh_host_key_options;
+use crate::session::{OutputStream, SshAuth, SshPool, SshSession, SshTarget};
 use std::collections::HashMap;
-use std::io::{BufRead, BufReader};
 use std::path::Path;
-use std::process::{Command, Stdio};
+use std::process::Command;
+use std::sync::Arc;
+
+/// Returns the pooled session for a pod, connecting with `private_key_path` (or,
+/// failing that, the ssh-agent's keys) if needed.
+///
+/// Host keys are pinned to `pod_id`, so a pod reusing another pod's address is not
+/// mistaken for it.
+fn pooled_session(
+    host: &str,
+    port: u16,
+    user: &str,
+    pod_id: &str,
+    private_key_path: &Path,
+) -> Result<Arc<SshSession>> {
+    SshPool::global().get(
+        &SshTarget::new(host, port, user).for_pod(pod_id),
+        &SshAuth::key_file(private_key_path).with_agent(true),
+    )
+}
+
+/// Runs blocking libssh2 work on tokio's blocking thread pool
+async fn run_blocking<T, F>(work: F) -> Result<T>
+where
+    T: Send + 'static,
+    F: FnOnce() -> Result<T> + Send + 'static,
+{
+    tokio::task::spawn_blocking(work)
+        .await
+        .map_err(|e| UtilsError::Ssh(SshError::CommandFailed(format!("SSH task failed: {}", e))))?
+}
 
 /// Execute a remote command via SSH
 pub async fn execute_remote_command(
     host: &str,
     port: u16,
     user: &str,
+    pod_id: &str,
     private_key_path: &Path,
     command: &str,
     env_vars: Option<HashMap<String, String>>,
 ) -> Result<(String, String, i32)> {
-    let mut ssh_command = Command::new("ssh");
-
-    // SSH options
-    ssh_command
-        .arg("-o")
-        .arg("StrictHostKeyChecking=no")
-        .arg("-o")
-        .arg("UserKnownHostsFile=/dev/null")
-        .arg("-i")
-        .arg(private_key_path)
-        .arg("-p")
-        .arg(port.to_string());
-
     // Add environment variables to the command if provided
     let final_command = if let Some(env) = env_vars {
         let env_exports: Vec<String> = env
@@ -42,146 +72,75 @@ pub async fn execute_remote_command(
         command.to_string()
     };
 
-    ssh_command
-        .arg(format!("{}@{}", user, host))
-        .arg(final_command)
-        .stdout(Stdio::piped())
-        .stderr(Stdio::piped());
-
-    let mut child = ssh_command.spawn().map_err(|e| {
-        UtilsError::Ssh(SshError::CommandFailed(format!(
-            "Failed to execute SSH command: {}",
-            e
-        )))
-    })?;
-
-    let stdout = child.stdout.take().unwrap();
-    let stderr = child.stderr.take().unwrap();
-
-    let stdout_reader = BufReader::new(stdout);
-    let stderr_reader = BufReader::new(stderr);
-
-    let mut stdout_lines = Vec::new();
-    let mut stderr_lines = Vec::new();
-
-    // Read stdout
-    for line in stdout_reader.lines() {
-        match line {
-            Ok(line) => {
-                println!("{}", line); // Stream to console
-                stdout_lines.push(line);
-            }
-            Err(e) => eprintln!("Error reading stdout: {}", e),
-        }
-    }
-
-    // Read stderr
-    for line in stderr_reader.lines() {
-        match line {
-            Ok(line) => {
-                eprintln!("{}", line); // Stream to console
-                stderr_lines.push(line);
-            }
-            Err(e) => eprintln!("Error reading stderr: {}", e),
-        }
-    }
-
-    let exit_status = child.wait().map_err(|e| {
-        UtilsError::Ssh(SshError::CommandFailed(format!(
-            "Failed to wait for SSH command: {}",
-            e
-        )))
-    })?;
-
-    let exit_code = exit_status.code().unwrap_or(-1);
-    let stdout_str = stdout_lines.join("\n");
-    let stderr_str = stderr_lines.join("\n");
-
-    Ok((stdout_str, stderr_str, exit_code))
+    let (host, user, pod_id) = (host.to_string(), user.to_string(), pod_id.to_string());
+    let private_key_path = private_key_path.to_path_buf();
+    run_blocking(move || {
+        let session = pooled_session(&host, port, &user, &pod_id, &private_key_path)?;
+
+        let mut stdout_lines = Vec::new();
+        let mut stderr_lines = Vec::new();
+
+        // Stream output to the console while collecting it
+        let exit_code =
+            session.exec_streaming(&final_command, None, |stream, line| match stream {
+                OutputStream::Stdout => {
+                    println!("{}", line);
+                    stdout_lines.push(line.to_string());
+                }
+                OutputStream::Stderr => {
+                    eprintln!("{}", line);
+                    stderr_lines.push(line.to_string());
+                }
+            })?;
+
+        let stdout_str = stdout_lines.join("\n");
+        let stderr_str = stderr_lines.join("\n");
+
+        Ok((stdout_str, stderr_str, exit_code))
+    })
+    .await
 }
 
-/// Upload a file via SFTP/SCP
+/// Upload a file via SFTP
 pub async fn upload_file_sftp(
     host: &str,
     port: u16,
     user: &str,
+    pod_id: &str,
     private_key_path: &Path,
     local_path: &Path,
     remote_path: &str,
 ) -> Result<()> {
-    // Use scp command for file upload
-    let mut scp_command = Command::new("scp");
-
-    scp_command
-        .arg("-o")
-        .arg("StrictHostKeyChecking=no")
-        .arg("-o")
-        .arg("UserKnownHostsFile=/dev/null")
-        .arg("-i")
-        .arg(private_key_path)
-        .arg("-P")
-        .arg(port.to_string())
-        .arg(local_path)
-        .arg(format!("{}@{}:{}", user, host, remote_path));
-
-    let output = scp_command.output().map_err(|e| {
-        UtilsError::Ssh(SshError::TransferFailed(format!(
-            "Failed to execute SCP command: {}",
-            e
-        )))
-    })?;
-
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        return Err(UtilsError::Ssh(SshError::TransferFailed(format!(
-            "SCP upload failed: {}",
-            stderr
-        ))));
-    }
-
-    Ok(())
+    let (host, user, pod_id) = (host.to_string(), user.to_string(), pod_id.to_string());
+    let remote_path = remote_path.to_string();
+    let (private_key_path, local_path) = (private_key_path.to_path_buf(), local_path.to_path_buf());
+    run_blocking(move || {
+        pooled_session(&host, port, &user, &pod_id, &private_key_path)?
+            .upload(&local_path, &remote_path)?;
+        Ok(())
+    })
+    .await
 }
 
-/// Download a file via SFTP/SCP
+/// Download a file via SFTP
 pub async fn download_file_sftp(
     host: &str,
     port: u16,
     user: &str,
+    pod_id: &str,
     private_key_path: &Path,
     remote_path: &str,
     local_path: &Path,
 ) -> Result<()> {
-    // Use scp command for file download
-    let mut scp_command = Command::new("scp");
-
-    scp_command
-        .arg("-o")
-        .arg("StrictHostKeyChecking=no")
-        .arg("-o")
-        .arg("UserKnownHostsFile=/dev/null")
-        .arg("-i")
-        .arg(private_key_path)
-        .arg("-P")
-        .arg(port.to_string())
-        .arg(format!("{}@{}:{}", user, host, remote_path))
-        .arg(local_path);
-
-    let output = scp_command.output().map_err(|e| {
-        UtilsError::Ssh(SshError::TransferFailed(format!(
-            "Failed to execute SCP command: {}",
-            e
-        )))
-    })?;
-
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        return Err(UtilsError::Ssh(SshError::TransferFailed(format!(
-            "SCP download failed: {}",
-            stderr
-        ))));
-    }
-
-    Ok(())
+    let (host, user, pod_id) = (host.to_string(), user.to_string(), pod_id.to_string());
+    let remote_path = remote_path.to_string();
+    let (private_key_path, local_path) = (private_key_path.to_path_buf(), local_path.to_path_buf());
+    run_blocking(move || {
+        pooled_session(&host, port, &user, &pod_id, &private_key_path)?
+            .download(&remote_path, &local_path)?;
+        Ok(())
+    })
+    .await
 }
 
 /// Execute interactive SSH session (for CLI ssh command)
@@ -189,80 +148,39 @@ pub fn execute_ssh_interactive(
     host: &str,
     port: u16,
     user: &str,
+    pod_id: &str,
     private_key_path: &Path,
 ) -> Result<()> {
-    let mut ssh_command = Command::new("ssh");
+    let exit_code = pooled_session(host, port, user, pod_id, private_key_path)?.shell()?;
 
-    ssh_command
-        .arg("-o")
-        .arg("StrictHostKeyChecking=no")
-        .arg("-o")
-        .arg("UserKnownHostsFile=/dev/null")
-        .arg("-i")
-        .arg(private_key_path)
-        .arg("-p")
-        .arg(port.to_string())
-        .arg(format!("{}@{}", user, host));
-
-    let status = ssh_command.status().map_err(|e| {
-        UtilsError::Ssh(SshError::ConnectionFailed(format!(
-            "Failed to execute SSH command: {}",
-            e
-        )))
-    })?;
-
-    if !status.success() {
-        return Err(UtilsError::Ssh(SshError::ConnectionFailed(
-            "SSH session failed".to_string(),
-        )));
+    if exit_code != 0 {
+        return Err(UtilsError::Ssh(SshError::CommandFailed(format!(
+            "Remote shell exited with code {}",
+            exit_code
+        ))));
     }
 
     Ok(())
 }
 
-/// Execute SCP command (for CLI scp command)
+/// Copy a file over SFTP (for CLI scp command)
+#[allow(clippy::too_many_arguments)]
 pub fn execute_scp_command(
     host: &str,
     port: u16,
     user: &str,
+    pod_id: &str,
     private_key_path: &Path,
     source: &str,
     destination: &str,
     is_upload: bool,
 ) -> Result<()> {
-    let mut scp_command = Command::new("scp");
-
-    scp_command
-        .arg("-o")
-        .arg("StrictHostKeyChecking=no")
-        .arg("-o")
-        .arg("UserKnownHostsFile=/dev/null")
-        .arg("-i")
-        .arg(private_key_path)
-        .arg("-P")
-        .arg(port.to_string());
+    let session = pooled_session(host, port, user, pod_id, private_key_path)?;
 
     if is_upload {
-        scp_command
-            .arg(source)
-            .arg(format!("{}@{}:{}", user, host, destination));
+        session.upload(Path::new(source), destination)?;
     } else {
-        scp_command
-            .arg(format!("{}@{}:{}", user, host, source))
-            .arg(destination);
-    }
-
-    let status = scp_command.status().map_err(|e| {
-        UtilsError::Ssh(SshError::TransferFailed(format!(
-            "Failed to execute SCP command: {}",
-            e
-        )))
-    })?;
-
-    if !status.success() {
-        return Err(UtilsError::Ssh(SshError::TransferFailed(
-            "SCP command failed".to_string(),
-        )));
+        session.download(source, Path::new(destination))?;
     }
 
     Ok(())
@@ -273,6 +191,7 @@ pub fn execute_rsync_command(
     host: &str,
     port: u16,
     user: &str,
+    pod_id: &str,
     private_key_path: &Path,
     source: &str,
     destination: &str,
@@ -291,9 +210,12 @@ pub fn execute_rsync_command(
         }
     }
 
-    // SSH options for rsync
+    // Connecting once records or verifies the host key, which OpenSSH then checks
+    // against the same store
+    let session = pooled_session(host, port, user, pod_id, private_key_path)?;
     let ssh_opts = format!(
-        "ssh -o StrictHostKeyChecking=no -o UserKnownHostsFile=/dev/null -i {} -p {}",
+        "ssh {} -i '{}' -p {}",
+        openssh_host_key_options(session.target(), SshPool::global().known_hosts()),
         private_key_path.display(),
         port
     );
@@ -330,24 +252,18 @@ pub async fn ensure_remote_directory(
     host: &str,
     port: u16,
     user: &str,
+    pod_id: &str,
     private_key_path: &Path,
     remote_path: &str,
 ) -> Result<()> {
-    let command = format!("mkdir -p '{}'", remote_path.replace('\'', "'\"'\"'"));
-    let (_, _, exit_code) =
-        execute_remote_command(host, port, user, private_key_path, &command, None).await?;
-
-    if exit_code != 0 {
-        return Err(UtilsError::Ssh(SshError::CommandFailed(format!(
-            "Failed to create remote directory: {}",
-            remote_path
-        ))));
-    }
-
-    Ok(())
+    let (host, user, pod_id) = (host.to_string(), user.to_string(), pod_id.to_string());
+    let remote_path = remote_path.to_string();
+    let private_key_path = private_key_path.to_path_buf();
+    run_blocking(move || {
+        pooled_session(&host, port, &user, &pod_id, &private_key_path)?.create_dir_all(&remote_path)
+    })
+    .await
 }
 
-// TODO: Add support for SSH agent authentication
 // TODO: Add support for password authentication (if needed)
 // TODO: Add progress callbacks for file transfers
-// TODO: Add connection pooling/reuse for multiple operations
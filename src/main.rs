@@ -15,12 +15,15 @@
 //!
 //! ## Error Handling
 //! - Panics are caught and displayed with file and line information
-//! - CLI errors are displayed and result in a non-zero exit code
+//! - CLI errors are displayed and result in a non-zero exit code; `lium exec` exits
+//!   with the remote command's status
+//! - Both are redacted first, so a key echoed in an error message is never printed
 //!
 //! ## Dependencies
 //! - tokio: Async runtime
 //! - lium-cli: Core CLI functionality
 
+use lium_cli::redact::redact;
 use std::process;
 
 #[tokio::main]
@@ -29,7 +32,7 @@ async fn main() {
     // This provides detailed error information including file and line numbers
     // when a panic occurs, making debugging easier
     std::panic::set_hook(Box::new(|panic_info| {
-        eprintln!("Error: {}", panic_info);
+        eprintln!("Error: {}", redact(&panic_info.to_string()));
         if let Some(location) = panic_info.location() {
             eprintln!("Location: {}:{}", location.file(), location.line());
         }
@@ -39,7 +42,7 @@ async fn main() {
     // Run the CLI - all functionality is now in lium-cli crate
     // Any errors from the CLI execution are caught and displayed
     if let Err(e) = lium_cli::run().await {
-        eprintln!("Error: {}", e);
-        process::exit(1);
+        lium_cli::report_error(&e);
+        process::exit(e.exit_code());
     }
 }